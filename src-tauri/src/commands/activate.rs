@@ -1,38 +1,44 @@
 use std::path::PathBuf;
-use std::process::Command;
 use tauri::{AppHandle, Manager};
 
+use crate::domain::UserEntry;
+use crate::launcher::Launcher;
+
+/// 启动条目。`query` 为选中时搜索框里的内容，用于选择学习（可省略）。
+///
+/// 按条目类型启动（`.desktop`、AppImage、脚本、程序、目录、文档，见 [`crate::launcher`]）；
+/// 用户自定义条目（`user-entry://<id>`）按保存的命令、参数、工作目录与环境变量启动。
 #[tauri::command]
-pub async fn activate(app: AppHandle, path: String) -> Result<(), String> {
+pub async fn activate(app: AppHandle, path: String, query: Option<String>) -> Result<(), String> {
     let path = PathBuf::from(&path);
-    
+
     // 先隐藏窗口（无论启动成功与否都隐藏）
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.hide();
     }
 
-    // 检查路径是否存在
-    if !path.exists() {
-        return Err(format!("路径不存在: {}", path.display()));
-    }
-    
-    // 检查是否是 exe 文件
-    if !path.extension().map_or(false, |e| e.eq_ignore_ascii_case("exe")) {
-        return Err(format!("不是可执行文件: {}", path.display()));
-    }
-
-    // 启动进程
-    let mut cmd = Command::new(&path);
-    if let Some(cwd) = path.parent() {
-        cmd.current_dir(cwd);
+    let state = app.state::<crate::commands::search::SearchState>();
+    let launcher = Launcher::system();
+    if let Some(id) = UserEntry::id_from_path(&path) {
+        let entry = state
+            .storage()
+            .sqlite()
+            .get_user_entry(id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("用户条目不存在: {}", id))?;
+        launcher.launch_user_entry(&entry)
+    } else {
+        launcher.launch_path(&path)
     }
-    cmd.spawn().map_err(|e| format!("启动失败: {}", e))?;
+    .map_err(|e| format!("启动失败: {:#}", e))?;
 
-    // 更新频次
-    let state = app.state::<crate::commands::search::SearchState>();
-    if let Some(ref path_str) = path.to_str() {
+    // 更新频次与选择学习
+    if let Some(path_str) = path.to_str() {
         state.record_launch(path_str);
+        if let Some(query) = query.as_deref() {
+            state.record_selection(query, path_str);
+        }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,52 @@
+use tauri::{AppHandle, Manager};
+
+use crate::commands::search::SearchState;
+
+/// 列出全部应用别名：`[{ path, aliases }]`。
+#[tauri::command]
+pub async fn list_aliases(app: AppHandle) -> Result<Vec<serde_json::Value>, String> {
+    let state = app.state::<SearchState>();
+    let all = state.storage().sqlite().list_aliases().map_err(|e| e.to_string())?;
+    Ok(all
+        .into_iter()
+        .map(|(path, aliases)| serde_json::json!({ "path": path, "aliases": aliases }))
+        .collect())
+}
+
+/// 给应用添加一个别名（如 Photoshop → `ps`），返回该应用的全部别名。
+#[tauri::command]
+pub async fn add_alias(app: AppHandle, path: String, alias: String) -> Result<Vec<String>, String> {
+    let state = app.state::<SearchState>();
+    state.storage().sqlite().add_alias(&path, &alias).map_err(|e| e.to_string())?;
+    aliases_changed(&state, &path)
+}
+
+/// 删除应用的一个别名，返回该应用剩余的别名。
+#[tauri::command]
+pub async fn remove_alias(
+    app: AppHandle,
+    path: String,
+    alias: String,
+) -> Result<Vec<String>, String> {
+    let state = app.state::<SearchState>();
+    state.storage().sqlite().remove_alias(&path, &alias).map_err(|e| e.to_string())?;
+    aliases_changed(&state, &path)
+}
+
+/// 整体替换应用的别名（空列表即清除），返回保存后的别名。
+#[tauri::command]
+pub async fn set_aliases(
+    app: AppHandle,
+    path: String,
+    aliases: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let state = app.state::<SearchState>();
+    state.storage().sqlite().set_aliases(&path, &aliases).map_err(|e| e.to_string())?;
+    aliases_changed(&state, &path)
+}
+
+/// 别名变动后刷新搜索引擎，并返回 `path` 当前的别名。
+fn aliases_changed(state: &SearchState, path: &str) -> Result<Vec<String>, String> {
+    state.reload_aliases();
+    state.storage().sqlite().get_aliases(path).map_err(|e| e.to_string())
+}
@@ -1,5 +1,7 @@
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
+use crate::commands::search::SearchState;
 use crate::config::UserConfig;
+use crate::search::{DoublePinyinScheme, FuzzyRule, ScoringPreset, ScoringProfile};
 
 #[tauri::command]
 pub async fn get_config(_app: AppHandle) -> Result<serde_json::Value, String> {
@@ -9,12 +11,22 @@ pub async fn get_config(_app: AppHandle) -> Result<serde_json::Value, String> {
         "theme_mode": config.theme_mode,
         "show_main_on_start": config.show_main_on_start,
         "show_notes_on_start": config.show_notes_on_start,
+        "double_pinyin": config.double_pinyin,
+        "fuzzy_pinyin": config.fuzzy_pinyin,
+        "frecency_half_life_days": config.frecency_half_life_days,
+        "scoring_preset": config
+            .scoring_profile
+            .matching_preset()
+            .map_or("custom", ScoringPreset::as_str),
+        "scoring_profile": config.scoring_profile,
+        "scope_aliases": config.scope_aliases,
     }))
 }
 
 #[tauri::command]
-pub async fn save_config(_app: AppHandle, config: serde_json::Value) -> Result<(), String> {
+pub async fn save_config(app: AppHandle, config: serde_json::Value) -> Result<(), String> {
     let mut user_config = UserConfig::load().map_err(|e| e.to_string())?;
+    let old_scan_dirs = user_config.scan_dirs.clone();
 
     if let Some(scan_dirs) = config.get("scan_dirs").and_then(|v| v.as_array()) {
         user_config.scan_dirs = scan_dirs
@@ -31,6 +43,58 @@ pub async fn save_config(_app: AppHandle, config: serde_json::Value) -> Result<(
     if let Some(show) = config.get("show_notes_on_start").and_then(|v| v.as_bool()) {
         user_config.show_notes_on_start = show;
     }
+    if let Some(scheme) = config.get("double_pinyin").and_then(|v| v.as_str()) {
+        // 空串表示关闭双拼；其他取值必须是已知方案
+        if !scheme.trim().is_empty() && DoublePinyinScheme::parse(scheme).is_none() {
+            return Err(format!("未知的双拼方案: {}", scheme));
+        }
+        user_config.double_pinyin = scheme.to_string();
+    }
+    if let Some(days) = config.get("frecency_half_life_days").and_then(|v| v.as_u64()) {
+        user_config.frecency_half_life_days = days.clamp(1, u32::MAX as u64) as u32;
+    }
+    if let Some(rules) = config.get("fuzzy_pinyin").and_then(|v| v.as_array()) {
+        let rules: Vec<String> =
+            rules.iter().filter_map(|v| v.as_str().map(String::from)).collect();
+        // 与双拼一致：无法识别的规则直接拒绝，而不是保存后被静默忽略
+        if let Some(unknown) = rules.iter().find(|r| FuzzyRule::parse(r).is_none()) {
+            return Err(format!("未知的模糊音规则: {}", unknown));
+        }
+        user_config.fuzzy_pinyin = rules;
+    }
+
+    if let Some(aliases) = config.get("scope_aliases").and_then(|v| v.as_array()) {
+        user_config.scope_aliases = aliases
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+    }
+
+    // 选了内置预设则直接采用；否则（custom）使用提交的完整配置
+    let preset = config
+        .get("scoring_preset")
+        .and_then(|v| v.as_str())
+        .and_then(ScoringPreset::parse);
+    if let Some(preset) = preset {
+        user_config.scoring_profile = ScoringProfile::preset(preset);
+    } else if let Some(profile) = config.get("scoring_profile") {
+        let profile: ScoringProfile =
+            serde_json::from_value(profile.clone()).map_err(|e| e.to_string())?;
+        profile.validate().map_err(|e| e.to_string())?;
+        user_config.scoring_profile = profile;
+    }
+
+    let scan_dirs_changed = user_config.scan_dirs != old_scan_dirs;
+    user_config.save().map_err(|e| e.to_string())?;
 
-    user_config.save().map_err(|e| e.to_string())
+    // 扫描目录可能变化，按新配置重启文件监听并立即重扫用户目录；
+    // 双拼、模糊音、频率半衰期、打分配置、范围别名即时生效
+    if let Some(state) = app.try_state::<SearchState>() {
+        state.start_watcher();
+        if scan_dirs_changed {
+            state.rescan_user_dirs();
+        }
+        state.apply_search_settings();
+    }
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+use tauri::{AppHandle, Manager};
+
+use crate::commands::search::SearchState;
+use crate::domain::HiddenRule;
+
+/// 列出全部隐藏规则：`[{ kind: "path" | "pattern", value }]`。
+#[tauri::command]
+pub async fn list_hidden(app: AppHandle) -> Result<Vec<HiddenRule>, String> {
+    let state = app.state::<SearchState>();
+    state.storage().sqlite().list_hidden().map_err(|e| e.to_string())
+}
+
+/// 按路径隐藏一个条目，返回新的规则列表。
+#[tauri::command]
+pub async fn hide_entry(app: AppHandle, path: String) -> Result<Vec<HiddenRule>, String> {
+    update_hidden(&app, HiddenRule::Path(path), true)
+}
+
+/// 按通配符隐藏（如 `Uninstall *`、`*crash*.exe`），返回新的规则列表。
+#[tauri::command]
+pub async fn hide_pattern(app: AppHandle, pattern: String) -> Result<Vec<HiddenRule>, String> {
+    update_hidden(&app, HiddenRule::Pattern(pattern), true)
+}
+
+/// 删除一条隐藏规则（`list_hidden` 返回的 `{ kind, value }`），返回新的规则列表。
+#[tauri::command]
+pub async fn unhide(app: AppHandle, rule: HiddenRule) -> Result<Vec<HiddenRule>, String> {
+    update_hidden(&app, rule, false)
+}
+
+/// 增删规则后刷新搜索引擎，返回最新的规则列表。
+fn update_hidden(app: &AppHandle, rule: HiddenRule, hide: bool) -> Result<Vec<HiddenRule>, String> {
+    let state = app.state::<SearchState>();
+    let db = state.storage().sqlite();
+    let result = if hide { db.add_hidden(&rule) } else { db.remove_hidden(&rule) };
+    result.map_err(|e| e.to_string())?;
+    state.reload_hidden();
+    db.list_hidden().map_err(|e| e.to_string())
+}
@@ -0,0 +1,36 @@
+use tauri::{AppHandle, Manager};
+
+use crate::commands::search::SearchState;
+
+/// 首页“最近使用”“常用”两栏各自的条数。
+const HOME_SECTION_LIMIT: usize = 8;
+
+/// 空 query 首页：返回 `{ pinned, recent, frequent }` 三栏 `SearchResult`，
+/// 每个应用只出现在一栏。
+#[tauri::command]
+pub async fn home(app: AppHandle) -> Result<serde_json::Value, String> {
+    let state = app.state::<SearchState>();
+    let sections = state.home(HOME_SECTION_LIMIT).map_err(|e| e.to_string())?;
+    serde_json::to_value(sections).map_err(|e| e.to_string())
+}
+
+/// 固定应用到首页末尾，返回新的固定顺序（应用路径）。
+#[tauri::command]
+pub async fn pin_entry(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let state = app.state::<SearchState>();
+    state.pin(&path).map_err(|e| e.to_string())
+}
+
+/// 取消固定，返回新的固定顺序。
+#[tauri::command]
+pub async fn unpin_entry(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let state = app.state::<SearchState>();
+    state.pins().unpin(&path).map_err(|e| e.to_string())
+}
+
+/// 按 `paths` 重排固定条目（未列出的接在后面），返回新的固定顺序。
+#[tauri::command]
+pub async fn reorder_pins(app: AppHandle, paths: Vec<String>) -> Result<Vec<String>, String> {
+    let state = app.state::<SearchState>();
+    state.pins().reorder(&paths).map_err(|e| e.to_string())
+}
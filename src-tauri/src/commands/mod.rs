@@ -1,4 +1,8 @@
 pub mod activate;
+pub mod aliases;
 pub mod config;
+pub mod hidden;
+pub mod home;
 pub mod notes;
-pub mod search;
\ No newline at end of file
+pub mod search;
+pub mod user_entries;
\ No newline at end of file
@@ -1,13 +1,34 @@
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::thread;
 use tauri::{AppHandle, Manager};
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
+
+use crate::provider::{home_sections, AppProvider, HomeSections, ResultPipeline, RERANK_POOL};
+
+/// query 返回的结果条数。
+const RESULT_LIMIT: usize = 8;
 
 pub struct SearchState {
     engine: Arc<RwLock<Option<Arc<crate::search::SearchEngine>>>>,
-    pub frequency_cache: Arc<RwLock<HashMap<String, u32>>>,
+    /// 应用路径 → 最近的启动时间戳（秒，升序）
+    pub frequency_cache: Arc<RwLock<HashMap<String, Vec<u64>>>>,
+    /// 使用频率衰减的半衰期（秒）
+    frecency_half_life: Arc<AtomicU64>,
+    /// query 前缀 → 该前缀下选过的 (应用路径, 次数)
+    selection_cache: Arc<RwLock<HashMap<String, crate::storage::Selections>>>,
+    /// 各结果提供者（应用……）的合并管线
+    pipeline: Arc<ResultPipeline>,
+    /// query 解析器（范围前缀含用户别名）
+    parser: Arc<RwLock<crate::search::QueryParser>>,
     storage: Arc<crate::storage::Storage>,
+    /// 文件监听句柄，替换/drop 即停止旧的监听
+    watcher: Arc<Mutex<Option<crate::indexer::IndexWatcher>>>,
+    /// 串行化"扫描 → 对账 → 回放到引擎"：全量扫描线程与文件监听回调不能交错，
+    /// 否则后写的对账结果会覆盖先写的，增量也可能乱序到达引擎
+    scan_lock: Arc<Mutex<()>>,
 }
 
 impl Clone for SearchState {
@@ -15,43 +36,113 @@ impl Clone for SearchState {
         Self {
             engine: self.engine.clone(),
             frequency_cache: self.frequency_cache.clone(),
+            frecency_half_life: self.frecency_half_life.clone(),
+            selection_cache: self.selection_cache.clone(),
+            pipeline: self.pipeline.clone(),
+            parser: self.parser.clone(),
             storage: self.storage.clone(),
+            watcher: self.watcher.clone(),
+            scan_lock: self.scan_lock.clone(),
         }
     }
 }
 
 impl SearchState {
     pub fn new(storage: Arc<crate::storage::Storage>) -> Self {
-        // 从 redb 加载已有频次数据（轻量操作，可以同步）
+        // 从 redb 加载已有启动记录（轻量操作，可以同步）
         let frequency_cache = storage.frequency()
+            .get_launch_history()
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<HashMap<String, Vec<u64>>>();
+        let config = crate::config::UserConfig::load_from_sqlite(storage.sqlite()).ok();
+        let half_life = config
+            .as_ref()
+            .map(|c| c.frecency_half_life_secs())
+            .unwrap_or(crate::search::DEFAULT_HALF_LIFE_SECS);
+        let parser = config
+            .map(|c| crate::search::QueryParser::new(&c.scope_alias_pairs()))
+            .unwrap_or_default();
+        let selection_cache = storage.selections()
             .get_all()
             .unwrap_or_default()
             .into_iter()
-            .collect::<HashMap<String, u32>>();
+            .collect::<HashMap<String, crate::storage::Selections>>();
+
+        let engine = Arc::new(RwLock::new(None));
+        let frequency_cache = Arc::new(RwLock::new(frequency_cache));
+        let frecency_half_life = Arc::new(AtomicU64::new(half_life));
+        let selection_cache = Arc::new(RwLock::new(selection_cache));
+
+        let mut pipeline = ResultPipeline::new();
+        pipeline.register(
+            Box::new(AppProvider::new(
+                engine.clone(),
+                frequency_cache.clone(),
+                selection_cache.clone(),
+                frecency_half_life.clone(),
+            )),
+            RESULT_LIMIT,
+        );
         
         Self {
-            engine: Arc::new(RwLock::new(None)),
-            frequency_cache: Arc::new(RwLock::new(frequency_cache)),
+            engine,
+            frequency_cache,
+            frecency_half_life,
+            selection_cache,
+            pipeline: Arc::new(pipeline),
+            parser: Arc::new(RwLock::new(parser)),
             storage,
+            watcher: Arc::new(Mutex::new(None)),
+            scan_lock: Arc::new(Mutex::new(())),
         }
     }
     
     /// 异步初始化搜索引擎（在后台线程加载索引）
     pub fn init_async(&self, storage: Arc<crate::storage::Storage>) {
         let state = self.clone();
-        thread::spawn(move || {
-            info!("开始后台加载搜索索引...");
-            match storage.index_cache().load() {
-                Ok(apps) => {
-                    let engine = Arc::new(crate::search::SearchEngine::new(apps));
-                    *state.engine.write().unwrap() = Some(engine);
-                    info!("搜索索引加载完成");
-                }
-                Err(e) => {
-                    warn!("加载搜索索引失败: {:?}", e);
+        thread::spawn(move || state.load_engine(&storage.index_cache()));
+    }
+
+    /// 冷启动：从缓存加载索引并构建引擎。
+    ///
+    /// 持有 `scan_lock`，且只在引擎仍未构建时安装：加载完成前窗口已显示并触发了扫描时，
+    /// 扫描已按对账后的完整索引构建好引擎，较早读出的缓存不能再覆盖它。
+    fn load_engine(&self, cache: &crate::storage::IndexCache) {
+        let _scan = self.scan_lock.lock().unwrap();
+        if self.engine.read().unwrap().is_some() {
+            info!("搜索引擎已由扫描构建，跳过缓存加载");
+            return;
+        }
+
+        info!("开始后台加载搜索索引...");
+        match cache.load_records() {
+            Ok(records) => {
+                // 拼音规则升级后缓存的拼音过期：引擎内重算，并回写缓存供下次冷启动使用
+                let stale: Vec<crate::domain::AppEntry> = records
+                    .iter()
+                    .filter(|r| !r.pinyin.is_current())
+                    .map(|r| r.entry.clone())
+                    .collect();
+
+                let apps = records.into_iter().map(|r| (r.entry, r.pinyin)).collect();
+                let engine = crate::search::SearchEngine::with_pinyin(apps);
+                self.configure_engine(&engine);
+                *self.engine.write().unwrap() = Some(Arc::new(engine));
+                info!("搜索索引加载完成");
+
+                // 仍持有 scan_lock；只刷新缓存里还在的记录
+                if !stale.is_empty() {
+                    match cache.refresh_existing(&stale) {
+                        Ok(count) => info!(count, "已刷新过期的拼音缓存"),
+                        Err(e) => warn!("刷新拼音缓存失败: {:?}", e),
+                    }
                 }
             }
-        });
+            Err(e) => {
+                warn!("加载搜索索引失败: {:?}", e);
+            }
+        }
     }
     
     /// 触发增量扫描（如果在 show_main_window 中调用）
@@ -76,58 +167,237 @@ impl SearchState {
         
         info!("触发增量索引更新...");
         let state = self.clone();
-        let storage = self.storage.clone();
             
         thread::spawn(move || {
-            let cache = storage.index_cache();
-
-            // 执行扫描（catch_unwind 防止 panic 导致索引状态不一致）
-            let scan_result = std::panic::catch_unwind(|| crate::indexer::scan_start_menu());
-            let report = match scan_result {
-                Ok(Ok(report)) => report,
-                Ok(Err(e)) => {
-                    warn!("扫描应用失败: {:?}", e);
-                    return;
-                }
-                Err(e) => {
-                    warn!("扫描应用 panic: {:?}", e);
-                    return;
-                }
-            };
+            let _scan = state.scan_lock.lock().unwrap();
 
-            info!("扫描到 {} 个应用", report.entries.len());
+            // 执行所有可用来源（单个来源失败/panic 由注册表隔离）
+            let registry = crate::indexer::ScannerRegistry::with_builtin(state.scan_dirs());
+            let results = registry.scan_all();
+            let count: usize = results.iter().map(|r| r.report.entries.len()).sum();
+            info!("扫描到 {} 个应用", count);
 
-            // 合并到缓存
-            match cache.merge_new_entries(&report.entries) {
-                Ok(merged) => {
-                    // 更新搜索引擎
-                    let new_engine = Arc::new(crate::search::SearchEngine::new(merged));
-                    *state.engine.write().unwrap() = Some(new_engine);
+            // 按来源对账：新增、改名、移除已消失的条目
+            if state.apply_scan_results(results, "增量扫描") {
+                // 更新扫描时间
+                let _ = cache.set_last_scan_time(
+                    crate::storage::IndexCache::current_timestamp()
+                );
+                info!("增量索引更新完成");
+            }
+        });
+    }
 
-                    // 更新扫描时间
-                    let _ = cache.set_last_scan_time(
-                        crate::storage::IndexCache::current_timestamp()
-                    );
+    /// 用户配置的扫描目录（作为 UserDirectory 来源参与扫描和监听）。
+    fn scan_dirs(&self) -> Vec<PathBuf> {
+        crate::config::UserConfig::load_from_sqlite(self.storage.sqlite())
+            .map(|c| c.scan_dirs_paths())
+            .unwrap_or_default()
+    }
 
-                    info!("增量索引更新完成");
-                }
-                Err(e) => {
-                    warn!("合并索引失败: {:?}", e);
-                }
-            }
+    /// 读取用户配置；失败时记录日志并返回 None（沿用当前设置）。
+    fn load_config(&self) -> Option<crate::config::UserConfig> {
+        crate::config::UserConfig::load_from_sqlite(self.storage.sqlite())
+            .map_err(|e| warn!("读取搜索设置失败: {:?}", e))
+            .ok()
+    }
+
+    /// 把用户配置中的搜索设置（双拼方案、模糊音、打分配置）、应用别名和隐藏规则应用到引擎，
+    /// 并加入用户自定义条目。
+    fn configure_engine(&self, engine: &crate::search::SearchEngine) {
+        if let Some(config) = self.load_config() {
+            apply_engine_settings(engine, &config);
+        }
+        if let Some(aliases) = self.load_aliases() {
+            engine.set_aliases(aliases);
+        }
+        if let Some(hidden) = self.load_hidden() {
+            engine.set_hidden(hidden);
+        }
+        if let Some(entries) = self.load_user_entries() {
+            engine.replace_source(crate::domain::AppSource::UserDefined, &entries);
+        }
+    }
+
+    /// 读取应用别名（应用路径 → 别名）；失败时记录日志并返回 None（沿用当前别名）。
+    fn load_aliases(&self) -> Option<HashMap<PathBuf, Vec<String>>> {
+        self.storage
+            .sqlite()
+            .list_aliases()
+            .map(|all| all.into_iter().map(|(path, a)| (PathBuf::from(path), a)).collect())
+            .map_err(|e| warn!("读取应用别名失败: {:?}", e))
+            .ok()
+    }
+
+    /// 读取隐藏规则；失败时记录日志并返回 None（沿用当前规则）。
+    fn load_hidden(&self) -> Option<crate::search::HiddenFilter> {
+        self.storage
+            .sqlite()
+            .list_hidden()
+            .map(|rules| crate::search::HiddenFilter::new(&rules))
+            .map_err(|e| warn!("读取隐藏规则失败: {:?}", e))
+            .ok()
+    }
+
+    /// 读取用户自定义条目（转为索引条目）；失败时记录日志并返回 None（沿用当前条目）。
+    fn load_user_entries(&self) -> Option<Vec<crate::domain::AppEntry>> {
+        self.storage
+            .sqlite()
+            .list_user_entries()
+            .map(|entries| entries.iter().map(|e| e.to_app_entry()).collect())
+            .map_err(|e| warn!("读取用户条目失败: {:?}", e))
+            .ok()
+    }
+
+    /// 用户条目增删改后同步到已加载的引擎；引擎未加载时由加载流程自行读取。
+    pub fn reload_user_entries(&self) {
+        let Some(entries) = self.load_user_entries() else {
+            return;
+        };
+        if let Some(ref engine) = *self.engine.read().unwrap() {
+            engine.replace_source(crate::domain::AppSource::UserDefined, &entries);
+        }
+    }
+
+    /// 隐藏规则增删后重新加载到已加载的引擎；引擎未加载时由加载流程自行读取。
+    pub fn reload_hidden(&self) {
+        let Some(hidden) = self.load_hidden() else {
+            return;
+        };
+        if let Some(ref engine) = *self.engine.read().unwrap() {
+            engine.set_hidden(hidden);
+        }
+    }
+
+    /// 别名增删后重新加载到已加载的引擎；引擎未加载时由加载流程自行读取。
+    pub fn reload_aliases(&self) {
+        let Some(aliases) = self.load_aliases() else {
+            return;
+        };
+        if let Some(ref engine) = *self.engine.read().unwrap() {
+            engine.set_aliases(aliases);
+        }
+    }
+
+    /// 把最新的搜索设置应用到排序器、query 解析器和已加载的引擎；引擎未加载时由加载流程自行读取。
+    pub fn apply_search_settings(&self) {
+        let Some(config) = self.load_config() else {
+            return;
+        };
+        self.frecency_half_life
+            .store(config.frecency_half_life_secs(), Ordering::Relaxed);
+        *self.parser.write().unwrap() =
+            crate::search::QueryParser::new(&config.scope_alias_pairs());
+        if let Some(ref engine) = *self.engine.read().unwrap() {
+            apply_engine_settings(engine, &config);
+        }
+    }
+
+    /// 启动（或按最新配置重启）文件监听。
+    ///
+    /// 监听开始菜单、`.desktop` 目录和用户扫描目录；变动去抖后只重扫受影响的来源，
+    /// 新装的应用数秒内即可搜索到，不必等 `SCAN_INTERVAL_SECS`。
+    pub fn start_watcher(&self) {
+        let registry = Arc::new(crate::indexer::ScannerRegistry::with_builtin(self.scan_dirs()));
+        let roots = registry.watch_roots();
+
+        // 先释放旧监听，避免新旧两份同时回调
+        *self.watcher.lock().unwrap() = None;
+        if roots.is_empty() {
+            info!("没有可监听的索引目录，跳过文件监听");
+            return;
+        }
+
+        let state = self.clone();
+        let on_change = move |changed: Vec<PathBuf>| state.apply_fs_changes(&registry, &changed);
+        match crate::indexer::IndexWatcher::start(roots, on_change) {
+            Ok(watcher) => *self.watcher.lock().unwrap() = Some(watcher),
+            Err(e) => warn!("启动文件监听失败: {:?}", e),
+        }
+    }
+
+    /// 文件变动回调：重扫受影响的来源，对账后把增量回放到内存引擎。
+    fn apply_fs_changes(&self, registry: &crate::indexer::ScannerRegistry, changed: &[PathBuf]) {
+        let _scan = self.scan_lock.lock().unwrap();
+        let results = registry.scan_affected(changed);
+        self.apply_scan_results(results, "文件变动");
+    }
+
+    /// 扫描目录配置变化后在后台重扫 `UserDirectory` 来源（新增目录立即入索引，移除的目录出索引）。
+    pub fn rescan_user_dirs(&self) {
+        let state = self.clone();
+        thread::spawn(move || {
+            let registry = crate::indexer::ScannerRegistry::with_builtin(state.scan_dirs());
+            let _scan = state.scan_lock.lock().unwrap();
+            let results = registry.scan_sources(&[crate::domain::AppSource::UserDirectory]);
+            state.apply_scan_results(results, "扫描目录变动");
         });
     }
 
+    /// 对账扫描结果并把增量回放到内存引擎；调用方须持有 `scan_lock`。
+    /// 冷启动加载尚未完成（引擎为空）时按对账后的完整索引构建引擎，增量不会丢失。
+    /// 返回对账是否成功。
+    fn apply_scan_results(
+        &self,
+        results: Vec<crate::indexer::SourceReport>,
+        reason: &'static str,
+    ) -> bool {
+        if results.is_empty() {
+            return true;
+        }
+
+        let sources: Vec<crate::domain::AppSource> = results.iter().map(|r| r.source).collect();
+        let entries: Vec<crate::domain::AppEntry> = results
+            .into_iter()
+            .flat_map(|r| r.report.entries)
+            .collect();
+
+        let (merged, report) = match self.storage.index_cache().reconcile(&sources, &entries) {
+            Ok(reconciled) => reconciled,
+            Err(e) => {
+                warn!(reason, "对账失败: {:?}", e);
+                return false;
+            }
+        };
+
+        let mut engine = self.engine.write().unwrap();
+        match *engine {
+            Some(ref eng) if report.is_changed() => {
+                eng.apply_delta(&report.upserts(), &report.removed)
+            }
+            Some(_) => {
+                debug!(reason, "未影响索引");
+                return true;
+            }
+            None => {
+                let eng = crate::search::SearchEngine::new(merged);
+                self.configure_engine(&eng);
+                *engine = Some(Arc::new(eng));
+            }
+        }
+        info!(
+            reason,
+            added = report.added.len(),
+            removed = report.removed.len(),
+            updated = report.updated.len(),
+            "增量已应用到索引"
+        );
+        true
+    }
+
     /// 同步初始化（保留用于测试）
     pub fn init(&self, engine: Arc<crate::search::SearchEngine>) {
         *self.engine.write().unwrap() = Some(engine);
     }
     
-    /// 更新频次并持久化
+    /// 记录一次启动（内存中追加启动时间）并持久化
     pub fn record_launch(&self, path: &str) {
         let mut cache = self.frequency_cache.write().unwrap();
-        let count = cache.get(path).copied().unwrap_or(0);
-        cache.insert(path.to_string(), count + 1);
+        let launches = cache.entry(path.to_string()).or_default();
+        launches.push(crate::storage::IndexCache::current_timestamp());
+        if launches.len() > crate::storage::MAX_LAUNCH_HISTORY {
+            launches.remove(0);
+        }
         
         // 异步保存到 redb
         let storage = self.storage.clone();
@@ -136,26 +406,172 @@ impl SearchState {
             let _ = storage.frequency().record_launch(&path_owned);
         });
     }
+
+    /// 空 query 首页（固定、最近使用、常用）；引擎加载完成前返回空。
+    pub fn home(&self, limit: usize) -> anyhow::Result<HomeSections> {
+        let engine = self.engine.read().unwrap();
+        let Some(ref eng) = *engine else {
+            return Ok(HomeSections::default());
+        };
+        let pins = self.storage.pins().list()?;
+        let launches = self.frequency_cache.read().unwrap();
+        Ok(home_sections(
+            eng,
+            &pins,
+            &launches,
+            crate::storage::IndexCache::current_timestamp(),
+            self.frecency_half_life.load(Ordering::Relaxed),
+            limit,
+        ))
+    }
+
+    /// 固定一个应用到首页；引擎已加载时只接受索引中存在的路径。返回新的固定顺序。
+    pub fn pin(&self, path: &str) -> anyhow::Result<Vec<String>> {
+        if let Some(ref engine) = *self.engine.read().unwrap() {
+            if !engine.contains_path(std::path::Path::new(path)) {
+                anyhow::bail!("索引中没有该应用: {}", path);
+            }
+        }
+        self.storage.pins().pin(path)
+    }
+
+    /// 共享的存储（别名等命令直接读写）。
+    pub fn storage(&self) -> &crate::storage::Storage {
+        &self.storage
+    }
+
+    /// 首页固定条目访问器。
+    pub fn pins(&self) -> crate::storage::PinStore {
+        self.storage.pins()
+    }
+
+    /// 解析 query：范围前缀、来源过滤与自由文本。
+    fn parse_query(&self, query: &str) -> crate::search::ParsedQuery {
+        self.parser.read().unwrap().parse(query)
+    }
+
+    /// 记住用户输入 `query` 时选中了 `path`（按去掉前缀后的文本，各前缀下次数 +1）并持久化；
+    /// 持久化时按 LRU 淘汰的前缀同步从缓存中移除
+    pub fn record_selection(&self, query: &str, path: &str) {
+        let prefixes = crate::search::learning_prefixes(&self.parse_query(query).text);
+        if prefixes.is_empty() {
+            return;
+        }
+        if path.len() > crate::storage::MAX_PATH_BYTES {
+            warn!(len = path.len(), "应用路径过长，不记录查询选择");
+            return;
+        }
+        {
+            let mut cache = self.selection_cache.write().unwrap();
+            for prefix in &prefixes {
+                crate::storage::add_selection(cache.entry(prefix.clone()).or_default(), path);
+            }
+        }
+
+        // 异步保存到 redb
+        let storage = self.storage.clone();
+        let selection_cache = self.selection_cache.clone();
+        let path_owned = path.to_string();
+        thread::spawn(move || match storage.selections().record(&prefixes, &path_owned) {
+            Ok(evicted) if !evicted.is_empty() => {
+                let mut cache = selection_cache.write().unwrap();
+                for prefix in &evicted {
+                    cache.remove(prefix);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("保存查询选择失败: {:?}", e),
+        });
+    }
 }
 
+fn apply_engine_settings(engine: &crate::search::SearchEngine, config: &crate::config::UserConfig) {
+    engine.set_double_pinyin(config.double_pinyin_scheme());
+    engine.set_fuzzy_pinyin(config.fuzzy_pinyin_rules());
+    engine.set_scoring_profile(config.scoring_profile);
+}
+
+/// 解析 query（`app:` 等范围前缀、`source:` 过滤），查询范围内的结果提供者，
+/// 返回合并后的前 [`RESULT_LIMIT`] 条 `SearchResult`。
+///
+/// highlights：标题上的命中区间 [start, end)，按字符（Unicode 标量）计
 #[tauri::command]
 pub async fn query(app: AppHandle, q: String) -> Result<Vec<serde_json::Value>, String> {
+    let state = app.state::<SearchState>();
+    state
+        .pipeline
+        .query(&state.parse_query(&q), RESULT_LIMIT)
+        .into_iter()
+        .map(|result| serde_json::to_value(result).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// 打分解释：列出 query 的每条候选结果命中的匹配路径、各自的原始分与 bonus、
+/// 频率/选择学习加分、最终分数及排名，用于调整相关性和提交可复现的问题报告。
+/// query 限定到应用以外的范围时 `results` 为空，与 `query` 命令一致。
+#[tauri::command]
+pub async fn explain_query(app: AppHandle, q: String) -> Result<serde_json::Value, String> {
     let state = app.state::<SearchState>();
     let engine = state.engine.read().unwrap();
+    let Some(ref eng) = *engine else {
+        return Err("搜索索引尚未加载完成".to_string());
+    };
     let freq_cache = state.frequency_cache.read().unwrap();
+    let selection_cache = state.selection_cache.read().unwrap();
 
-    if let Some(ref eng) = *engine {
-        let hits = eng.search(&q, 8);
-        let reranked = crate::search::rerank(&hits, &freq_cache);
+    let now = crate::storage::IndexCache::current_timestamp();
+    let half_life = state.frecency_half_life.load(Ordering::Relaxed);
+    let parsed = state.parse_query(&q);
+    let key = crate::search::learning_key(&parsed.text);
+    // 与 ResultPipeline::query 相同的范围判定：限定到其他范围（`note:` 等）时没有应用结果
+    let (hits, strategies): (Vec<_>, Vec<_>) =
+        if parsed.targets(&[crate::search::QueryScope::App]) {
+            eng.explain(&parsed.text, RERANK_POOL, &parsed.sources).into_iter().unzip()
+        } else {
+            (Vec::new(), Vec::new())
+        };
+    let learned = crate::search::learned_selections(&selection_cache, &parsed.text);
+    let ranked = crate::search::rerank_explained(&hits, &freq_cache, learned, now, half_life);
 
-        Ok(reranked.into_iter().map(|app| serde_json::json!({
-            "name": app.name,
-            "path": app.path.to_string_lossy()
-        })).collect())
-    } else {
-        // 引擎还在加载中，返回空结果
-        Ok(vec![])
-    }
+    let results: Vec<serde_json::Value> = ranked
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (i, boost))| {
+            let hit = &hits[i];
+            let strategies: Vec<serde_json::Value> = strategies[i]
+                .iter()
+                .map(|s| serde_json::json!({
+                    "token": s.token,
+                    "strategy": s.strategy,
+                    "raw": s.raw,
+                    "bonus": s.bonus,
+                    "chosen": s.chosen
+                }))
+                .collect();
+            serde_json::json!({
+                "rank": rank + 1,
+                "text_rank": i + 1,
+                "shown": rank < RESULT_LIMIT,
+                "name": hit.entry.name,
+                "path": hit.entry.path.to_string_lossy(),
+                "text_score": hit.score,
+                "strategies": strategies,
+                "frecency": boost.frecency,
+                "frecency_bonus": boost.frecency_bonus,
+                "selections": boost.selections,
+                "learned_bonus": boost.learned_bonus,
+                "final_score": boost.final_score
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "query": q,
+        "learning_key": key,
+        "now": now,
+        "half_life_secs": half_life,
+        "results": results
+    }))
 }
 
 #[tauri::command]
@@ -175,5 +591,85 @@ pub fn register(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>>
     let state = app.state::<SearchState>();
     state.init_async(storage);
 
+    // 监听应用目录变动，实时增量更新索引
+    state.start_watcher();
+
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AppEntry, AppSource};
+    use crate::indexer::SourceReport;
+
+    fn temp_state() -> (SearchState, PathBuf) {
+        let dir = std::env::temp_dir().join(format!(
+            "sparknova_state_test_{:?}_{:?}",
+            thread::current().id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let storage = Arc::new(crate::storage::Storage::open_in(&dir).unwrap());
+        (SearchState::new(storage), dir)
+    }
+
+    fn entry(name: &str) -> AppEntry {
+        AppEntry {
+            name: name.into(),
+            path: PathBuf::from(format!("/apps/{}", name)),
+            source: AppSource::UserDirectory,
+            icon: None,
+            keywords: Vec::new(),
+        }
+    }
+
+    fn scan(entries: Vec<AppEntry>) -> Vec<SourceReport> {
+        let mut result = SourceReport {
+            source: AppSource::UserDirectory,
+            report: Default::default(),
+        };
+        result.report.entries = entries;
+        vec![result]
+    }
+
+    fn indexed(state: &SearchState, name: &str) -> bool {
+        let engine = state.engine.read().unwrap();
+        engine.as_ref().unwrap().contains_path(&entry(name).path)
+    }
+
+    #[test]
+    fn scan_before_cold_load_is_not_overwritten() {
+        let (state, dir) = temp_state();
+        let cache = state.storage.index_cache();
+        cache.save(&[entry("old")]).unwrap();
+
+        // 冷启动加载尚未完成时扫描已对账：按对账后的索引构建引擎
+        let _scan = state.scan_lock.lock().unwrap();
+        assert!(state.apply_scan_results(scan(vec![entry("new")]), "测试"));
+        drop(_scan);
+        assert!(indexed(&state, "new") && !indexed(&state, "old"));
+
+        // 随后完成的冷启动加载不能用旧记录覆盖它
+        state.load_engine(&cache);
+        assert!(indexed(&state, "new") && !indexed(&state, "old"));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn scan_after_cold_load_applies_delta() {
+        let (state, dir) = temp_state();
+        let cache = state.storage.index_cache();
+        cache.save(&[entry("old")]).unwrap();
+
+        state.load_engine(&cache);
+        assert!(indexed(&state, "old"));
+
+        let _scan = state.scan_lock.lock().unwrap();
+        assert!(state.apply_scan_results(scan(vec![entry("new")]), "测试"));
+        assert!(indexed(&state, "new") && !indexed(&state, "old"));
+        drop(_scan);
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,38 @@
+use tauri::{AppHandle, Manager};
+
+use crate::commands::search::SearchState;
+use crate::domain::UserEntry;
+
+/// 列出全部用户自定义条目。
+#[tauri::command]
+pub async fn list_user_entries(app: AppHandle) -> Result<Vec<UserEntry>, String> {
+    let state = app.state::<SearchState>();
+    state.storage().sqlite().list_user_entries().map_err(|e| e.to_string())
+}
+
+/// 新建用户自定义条目（`id` 可省略），返回带新 id 的条目；新条目立即可搜索。
+#[tauri::command]
+pub async fn create_user_entry(app: AppHandle, entry: UserEntry) -> Result<UserEntry, String> {
+    let state = app.state::<SearchState>();
+    let saved = state.storage().sqlite().insert_user_entry(&entry).map_err(|e| e.to_string())?;
+    state.reload_user_entries();
+    Ok(saved)
+}
+
+/// 按 `entry.id` 整体更新用户自定义条目，返回保存后的条目。
+#[tauri::command]
+pub async fn update_user_entry(app: AppHandle, entry: UserEntry) -> Result<UserEntry, String> {
+    let state = app.state::<SearchState>();
+    let saved = state.storage().sqlite().update_user_entry(&entry).map_err(|e| e.to_string())?;
+    state.reload_user_entries();
+    Ok(saved)
+}
+
+/// 删除用户自定义条目。
+#[tauri::command]
+pub async fn delete_user_entry(app: AppHandle, id: u64) -> Result<(), String> {
+    let state = app.state::<SearchState>();
+    state.storage().sqlite().delete_user_entry(id).map_err(|e| e.to_string())?;
+    state.reload_user_entries();
+    Ok(())
+}
@@ -7,8 +7,9 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+use crate::search::{DoublePinyinScheme, FuzzyRule, QueryScope, ScoringProfile};
 use crate::storage::SqliteDb;
 
 /// 主题模式常量。Rust 侧统一引用，避免 `"dark"`/`"light"` 散落各处。
@@ -19,6 +20,14 @@ pub const THEME_LIGHT: &str = "light";
 pub const KEY_THEME_MODE: &str = "theme_mode";
 pub const KEY_SHOW_MAIN_ON_START: &str = "show_main_on_start";
 pub const KEY_SHOW_NOTES_ON_START: &str = "show_notes_on_start";
+pub const KEY_DOUBLE_PINYIN: &str = "double_pinyin";
+pub const KEY_FUZZY_PINYIN: &str = "fuzzy_pinyin";
+pub const KEY_FRECENCY_HALF_LIFE_DAYS: &str = "frecency_half_life_days";
+pub const KEY_SCORING_PROFILE: &str = "scoring_profile";
+pub const KEY_SCOPE_ALIASES: &str = "scope_aliases";
+
+/// 使用频率衰减的默认半衰期（天）。
+pub const DEFAULT_FRECENCY_HALF_LIFE_DAYS: u32 = 14;
 
 /// 用户可配置项。
 #[derive(Clone)]
@@ -27,6 +36,16 @@ pub struct UserConfig {
     pub theme_mode: String,
     pub show_main_on_start: bool,
     pub show_notes_on_start: bool,
+    /// 双拼方案（`xiaohe` / `microsoft` / `ziranma` / `sogou`），空串表示关闭
+    pub double_pinyin: String,
+    /// 启用的模糊音规则（如 `z-zh`、`n-l`、`an-ang`），为空表示关闭
+    pub fuzzy_pinyin: Vec<String>,
+    /// 使用频率衰减的半衰期（天）：多久之前的一次启动只算半次
+    pub frecency_half_life_days: u32,
+    /// 打分权重与阈值（以 JSON 存储；缺失或无效时用默认预设）
+    pub scoring_profile: ScoringProfile,
+    /// 搜索范围前缀的别名，形如 `yy=app`、`bj=note`（内置的 `app:` 等始终可用）
+    pub scope_aliases: Vec<String>,
 }
 
 impl Default for UserConfig {
@@ -36,6 +55,11 @@ impl Default for UserConfig {
             theme_mode: THEME_DARK.to_string(),
             show_main_on_start: false,
             show_notes_on_start: false,
+            double_pinyin: String::new(),
+            fuzzy_pinyin: Vec::new(),
+            frecency_half_life_days: DEFAULT_FRECENCY_HALF_LIFE_DAYS,
+            scoring_profile: ScoringProfile::default(),
+            scope_aliases: Vec::new(),
         }
     }
 }
@@ -58,6 +82,35 @@ impl UserConfig {
             .map(|v| v == "true")
             .unwrap_or(false);
 
+        let double_pinyin = sqlite.get_setting(KEY_DOUBLE_PINYIN)?.unwrap_or_default();
+
+        // 逗号分隔存储
+        let split_list = |v: String| -> Vec<String> {
+            v.split(',')
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        let fuzzy_pinyin = sqlite
+            .get_setting(KEY_FUZZY_PINYIN)?
+            .map(split_list)
+            .unwrap_or_default();
+        let scope_aliases = sqlite
+            .get_setting(KEY_SCOPE_ALIASES)?
+            .map(split_list)
+            .unwrap_or_default();
+
+        let frecency_half_life_days = sqlite
+            .get_setting(KEY_FRECENCY_HALF_LIFE_DAYS)?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_FRECENCY_HALF_LIFE_DAYS);
+
+        let scoring_profile = sqlite
+            .get_setting(KEY_SCORING_PROFILE)?
+            .and_then(|v| parse_scoring_profile(&v))
+            .unwrap_or_default();
+
         let scan_dirs = sqlite.get_scan_dirs().unwrap_or_default();
 
         Ok(Self {
@@ -65,6 +118,11 @@ impl UserConfig {
             theme_mode,
             show_main_on_start,
             show_notes_on_start,
+            double_pinyin,
+            fuzzy_pinyin,
+            frecency_half_life_days,
+            scoring_profile,
+            scope_aliases,
         })
     }
 
@@ -87,6 +145,14 @@ impl UserConfig {
             KEY_SHOW_NOTES_ON_START,
             if self.show_notes_on_start { "true" } else { "false" },
         )?;
+        sqlite.set_setting(KEY_DOUBLE_PINYIN, &self.double_pinyin)?;
+        sqlite.set_setting(KEY_FUZZY_PINYIN, &self.fuzzy_pinyin.join(","))?;
+        sqlite.set_setting(
+            KEY_FRECENCY_HALF_LIFE_DAYS,
+            &self.frecency_half_life_days.to_string(),
+        )?;
+        sqlite.set_setting(KEY_SCORING_PROFILE, &serde_json::to_string(&self.scoring_profile)?)?;
+        sqlite.set_setting(KEY_SCOPE_ALIASES, &self.scope_aliases.join(","))?;
 
         let current_dirs = sqlite.get_scan_dirs().unwrap_or_default();
         for dir in &current_dirs {
@@ -152,6 +218,36 @@ impl UserConfig {
         }
     }
 
+    /// 当前双拼方案；未设置或取值无法识别时为 None。
+    pub fn double_pinyin_scheme(&self) -> Option<DoublePinyinScheme> {
+        DoublePinyinScheme::parse(&self.double_pinyin)
+    }
+
+    /// 当前启用的模糊音规则（忽略无法识别的取值）。
+    pub fn fuzzy_pinyin_rules(&self) -> Vec<FuzzyRule> {
+        self.fuzzy_pinyin.iter().filter_map(|r| FuzzyRule::parse(r)).collect()
+    }
+
+    /// 搜索范围别名 (前缀关键字, 范围)；忽略格式不对或范围无法识别的项。
+    pub fn scope_alias_pairs(&self) -> Vec<(String, QueryScope)> {
+        self.scope_aliases
+            .iter()
+            .filter_map(|alias| {
+                let (keyword, scope) = alias.split_once('=')?;
+                let keyword = keyword.trim();
+                if keyword.is_empty() || keyword.contains([':', ' ']) {
+                    return None;
+                }
+                Some((keyword.to_string(), QueryScope::parse(scope)?))
+            })
+            .collect()
+    }
+
+    /// 使用频率衰减的半衰期（秒），至少 1 天。
+    pub fn frecency_half_life_secs(&self) -> u64 {
+        self.frecency_half_life_days.max(1) as u64 * 24 * 3600
+    }
+
     /// 设置启动笔记窗口配置。
     pub fn set_show_notes_on_start(&mut self, show: bool) {
         self.show_notes_on_start = show;
@@ -161,6 +257,20 @@ impl UserConfig {
     }
 }
 
+/// 解析并校验存储的打分配置；无法解析或取值越界时记录日志并返回 None。
+fn parse_scoring_profile(value: &str) -> Option<ScoringProfile> {
+    let profile: ScoringProfile = serde_json::from_str(value)
+        .map_err(|e| warn!("打分配置无法解析，使用默认值: {:?}", e))
+        .ok()?;
+    match profile.validate() {
+        Ok(()) => Some(profile),
+        Err(e) => {
+            warn!("打分配置无效，使用默认值: {:?}", e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +289,12 @@ mod tests {
         assert!(!config.show_notes_on_start);
     }
 
+    #[test]
+    fn test_default_half_life_matches_ranker() {
+        let config = UserConfig::default();
+        assert_eq!(config.frecency_half_life_secs(), crate::search::DEFAULT_HALF_LIFE_SECS);
+    }
+
     #[test]
     fn test_set_theme() {
         let mut config = UserConfig::default();
@@ -204,6 +320,28 @@ mod tests {
             .contains(&temp_dir.to_string_lossy().to_string()));
     }
 
+    #[test]
+    fn test_invalid_scoring_profile_falls_back() {
+        assert_eq!(
+            parse_scoring_profile(r#"{"sw_match": 5}"#).map(|p| p.sw_match),
+            Some(5)
+        );
+        assert_eq!(parse_scoring_profile(r#"{"sw_match": 0}"#), None);
+        assert_eq!(parse_scoring_profile("not json"), None);
+    }
+
+    #[test]
+    fn test_scope_alias_pairs() {
+        let config = UserConfig {
+            scope_aliases: ["yy=app", "bj = note", "x=nope", "=app"].map(String::from).to_vec(),
+            ..UserConfig::default()
+        };
+        assert_eq!(
+            config.scope_alias_pairs(),
+            vec![("yy".to_string(), QueryScope::App), ("bj".to_string(), QueryScope::Note)]
+        );
+    }
+
     #[test]
     fn test_remove_scan_dir() {
         let mut config = UserConfig::default();
@@ -3,7 +3,8 @@ use std::path::PathBuf;
 
 /// 应用的来源类型。详见 docs/design.md §4.1「应用来源分层」。
 ///
-/// v0.1 只会产出 [`AppSource::StartMenu`] 和 [`AppSource::RegistryUninstall`]，
+/// v0.1 只会产出 [`AppSource::StartMenu`] 和 [`AppSource::RegistryUninstall`]（Windows）
+/// 以及 [`AppSource::DesktopEntry`]（Linux），
 /// 其余变体是 v0.2/v0.3 预留（存储 schema 先占位，避免后续迁移）。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)] // 预留变体；v0.2 移除 UserDirectory，v0.3 移除 UserHistory
@@ -16,6 +17,10 @@ pub enum AppSource {
     UserDirectory,
     /// 用户启动历史，"用过即学"（v0.3）
     UserHistory,
+    /// Linux XDG `.desktop` 文件
+    DesktopEntry,
+    /// 用户手动登记的条目（[`super::UserEntry`]），不经扫描，也不进索引缓存
+    UserDefined,
 }
 
 /// 单个可启动应用的元数据。
@@ -23,7 +28,9 @@ pub enum AppSource {
 /// 不同来源填充字段的来路：
 /// - `StartMenu`：`name` 取自 `.lnk` 文件名（Windows 开始菜单惯例），`path` 取自 `link_target()`
 /// - `RegistryUninstall`：`name` 取自 `DisplayName`，`path` 取自 `DisplayIcon` 或 `InstallLocation`
-#[derive(Debug, Clone)]
+/// - `DesktopEntry`：`name` 优先取 `Name[zh_CN]`，`path` 为 `.desktop` 文件本身（启动时再解析 `Exec`）
+/// - `UserDefined`：`name` 为用户填写的名称，`path` 为 `user-entry://<id>`（启动时按 id 取命令）
+#[derive(Debug, Clone, PartialEq)]
 pub struct AppEntry {
     /// UI 显示名
     pub name: String,
@@ -31,4 +38,8 @@ pub struct AppEntry {
     pub path: PathBuf,
     /// 来源标签，供排序权重和用户过滤使用
     pub source: AppSource,
+    /// 图标名或图标文件路径（`.desktop` 的 `Icon`），无则为 `None`
+    pub icon: Option<String>,
+    /// 额外搜索关键词（`.desktop` 的 `Keywords`、本地化前的原始名称等）
+    pub keywords: Vec<String>,
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+//! 隐藏规则：用户从搜索结果中屏蔽的条目（卸载程序、崩溃上报器、重复快捷方式等）。
+
+use serde::{Deserialize, Serialize};
+
+/// 一条隐藏规则。序列化为 `{ "kind": "path" | "pattern", "value": ... }`。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum HiddenRule {
+    /// 按应用路径精确隐藏
+    Path(String),
+    /// 按通配符隐藏（`*` 任意串、`?` 单个字符；仅 Windows、macOS 上不区分大小写）。
+    /// 含路径分隔符时匹配完整路径，否则匹配显示名或文件名
+    Pattern(String),
+}
+
+impl HiddenRule {
+    /// 规则类型名（即持久化时的 kind 列）。
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Path(_) => "path",
+            Self::Pattern(_) => "pattern",
+        }
+    }
+
+    /// 规则的原始取值：路径或通配符（即持久化时的 value 列）。
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Path(v) | Self::Pattern(v) => v,
+        }
+    }
+
+    /// 由 kind + value 还原；无法识别的 kind 返回 None。
+    pub fn from_parts(kind: &str, value: String) -> Option<Self> {
+        match kind {
+            "path" => Some(Self::Path(value)),
+            "pattern" => Some(Self::Pattern(value)),
+            _ => None,
+        }
+    }
+}
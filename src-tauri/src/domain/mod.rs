@@ -4,7 +4,13 @@
 //! 新数据结构先落在这里，再被 indexer / search / storage / ui 引用。
 
 mod app_entry;
+mod hidden_rule;
 mod note;
+mod search_result;
+mod user_entry;
 
 pub use app_entry::{AppEntry, AppSource};
-pub use note::{Note, NoteKind};
\ No newline at end of file
+pub use hidden_rule::HiddenRule;
+pub use note::{Note, NoteKind};
+pub use search_result::{ResultAction, ResultKind, SearchResult};
+pub use user_entry::UserEntry;
\ No newline at end of file
@@ -0,0 +1,43 @@
+//! 统一搜索结果：各结果提供者（应用、笔记、计算器、文件……）产出同一结构，
+//! 由结果管线合并后返回前端。
+
+use serde::Serialize;
+
+/// 结果类型，前端据此选择图标样式与默认动作。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)] // 预留变体；对应的提供者接入后移除
+pub enum ResultKind {
+    App,
+    Note,
+    Calculator,
+    File,
+}
+
+/// 结果上可执行的动作（第一个为回车默认动作）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResultAction {
+    /// 动作标识，如 `"open"`
+    pub id: String,
+    /// UI 显示文案
+    pub label: String,
+}
+
+/// 单条搜索结果。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchResult {
+    /// 提供者内唯一的标识（应用为可执行文件路径）
+    pub id: String,
+    /// 主标题
+    pub title: String,
+    /// 副标题（应用为路径）
+    pub subtitle: String,
+    /// 图标名或图标文件路径
+    pub icon: Option<String>,
+    pub kind: ResultKind,
+    /// 提供者内部的相关性分数（越高越好）；不同提供者之间不可直接比较，合并前先归一化
+    pub score: f64,
+    pub actions: Vec<ResultAction>,
+    /// 标题上的命中区间（按字符计，左闭右开）
+    pub highlights: Vec<(usize, usize)>,
+}
@@ -0,0 +1,93 @@
+//! 用户自定义条目：扫描器看不到、由用户手动登记的启动项
+//! （带参数的脚本、`code ~/work/project` 这类命令、网址等）。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{AppEntry, AppSource};
+
+/// 用户条目在索引中的路径前缀：`user-entry://<id>`。
+const PATH_SCHEME: &str = "user-entry://";
+
+/// 一条用户自定义条目。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserEntry {
+    /// 自增主键（SQLite 端分配；新建时忽略）
+    #[serde(default)]
+    pub id: u64,
+    /// UI 显示名，参与搜索
+    pub name: String,
+    /// 要执行的程序、脚本或网址
+    pub command: String,
+    /// 启动参数，按原样逐个传给进程（不经 shell 拆分）
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 工作目录；None 时沿用启动器的工作目录
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// 额外的环境变量
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// 图标名或图标文件路径
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// 额外搜索关键词（与应用别名同样参与匹配）
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl UserEntry {
+    /// 条目在索引中的路径（固定、隐藏、启动记录都按它关联）。
+    pub fn entry_path(id: u64) -> PathBuf {
+        PathBuf::from(format!("{}{}", PATH_SCHEME, id))
+    }
+
+    /// 从索引路径解析出条目 id；不是用户条目路径时返回 None。
+    pub fn id_from_path(path: &Path) -> Option<u64> {
+        path.to_str()?.strip_prefix(PATH_SCHEME)?.parse().ok()
+    }
+
+    /// 名称与命令不能为空；返回去掉首尾空白、丢弃空白关键词后的副本。
+    pub fn validated(&self) -> Result<Self> {
+        let name = self.name.trim();
+        let command = self.command.trim();
+        if name.is_empty() {
+            bail!("名称不能为空");
+        }
+        if command.is_empty() {
+            bail!("命令不能为空");
+        }
+        Ok(Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            working_dir: self
+                .working_dir
+                .as_deref()
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(String::from),
+            keywords: self
+                .keywords
+                .iter()
+                .map(|k| k.trim())
+                .filter(|k| !k.is_empty())
+                .map(String::from)
+                .collect(),
+            ..self.clone()
+        })
+    }
+
+    /// 转为搜索引擎中的条目（来源为 [`AppSource::UserDefined`]）。
+    pub fn to_app_entry(&self) -> AppEntry {
+        AppEntry {
+            name: self.name.clone(),
+            path: Self::entry_path(self.id),
+            source: AppSource::UserDefined,
+            icon: self.icon.clone(),
+            keywords: self.keywords.clone(),
+        }
+    }
+}
@@ -8,16 +8,32 @@ use std::path::{Path, PathBuf};
 use std::{env, fs};
 use tracing::{debug, info};
 
+use super::scanner::{ScanReport, Scanner};
 use crate::domain::{AppEntry, AppSource};
 
-/// 单次扫描结果与统计。字段为 pub 供调用方日志化；后续 search 引擎取 `entries`。
-#[derive(Debug, Default)]
-pub struct ScanReport {
-    pub entries: Vec<AppEntry>,
-    /// 遍历到的 `.lnk` 总数
-    pub scanned_lnk: usize,
-    /// 解析失败或目标非 `.exe` 被丢弃的数量
-    pub skipped: usize,
+/// 开始菜单来源（仅 Windows 可用）。
+pub struct StartMenuScanner;
+
+impl Scanner for StartMenuScanner {
+    fn name(&self) -> &'static str {
+        "start_menu"
+    }
+
+    fn source(&self) -> AppSource {
+        AppSource::StartMenu
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(windows)
+    }
+
+    fn scan(&self) -> Result<ScanReport> {
+        scan_start_menu()
+    }
+
+    fn watch_roots(&self) -> Vec<PathBuf> {
+        start_menu_roots()
+    }
 }
 
 /// 扫描开始菜单（用户级 + 系统级），返回命中的应用列表。
@@ -35,7 +51,7 @@ pub fn scan_start_menu() -> Result<ScanReport> {
 
     info!(
         entries = report.entries.len(),
-        scanned = report.scanned_lnk,
+        scanned = report.scanned,
         skipped = report.skipped,
         "开始菜单扫描完成"
     );
@@ -78,10 +94,10 @@ fn scan_dir(dir: &Path, report: &mut ScanReport) {
         if path.is_dir() {
             scan_dir(&path, report);
         } else if is_lnk(&path) {
+            report.scanned += 1;
             match parse_lnk(&path) {
                 Ok(Some(app)) => {
                     report.entries.push(app);
-                    report.scanned_lnk += 1;
                 }
                 Ok(None) => {
                     report.skipped += 1;
@@ -137,6 +153,8 @@ fn parse_lnk(lnk_path: &Path) -> Result<Option<AppEntry>> {
         name,
         path: target_path,
         source: AppSource::StartMenu,
+        icon: None,
+        keywords: Vec::new(),
     }))
 }
 
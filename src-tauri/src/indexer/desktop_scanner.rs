@@ -0,0 +1,524 @@
+//! Linux XDG `.desktop` 扫描器：遍历 `$XDG_DATA_HOME/applications` 与
+//! `$XDG_DATA_DIRS/applications`，解析 Desktop Entry 得到 [`AppEntry`]。
+//!
+//! 遵循 XDG 优先级：同一 desktop-file ID 只取最先出现的目录
+//! （用户级 `~/.local/share` 覆盖系统级 `/usr/share`）。
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use tracing::{debug, info};
+
+use super::scanner::{ScanReport, Scanner};
+use crate::domain::{AppEntry, AppSource};
+
+/// 递归最大深度（`applications/` 下允许有厂商子目录，如 `kde4/`）。
+const MAX_DEPTH: u32 = 3;
+
+/// `XDG_DATA_DIRS` 未设置时的默认值（规范约定）。
+const DEFAULT_DATA_DIRS: &str = "/usr/local/share:/usr/share";
+
+/// Desktop Entry 主分组名。
+const DESKTOP_ENTRY_GROUP: &str = "[Desktop Entry]";
+
+/// 本地化名称的查找顺序（命中即作为显示名）。
+const LOCALIZED_NAME_KEYS: &[&str] = &["Name[zh_CN]", "Name[zh]"];
+
+/// XDG `.desktop` 来源（Linux 等 freedesktop 平台可用）。
+pub struct DesktopEntryScanner;
+
+impl Scanner for DesktopEntryScanner {
+    fn name(&self) -> &'static str {
+        "desktop_entry"
+    }
+
+    fn source(&self) -> AppSource {
+        AppSource::DesktopEntry
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(all(unix, not(target_os = "macos")))
+    }
+
+    fn scan(&self) -> Result<ScanReport> {
+        scan_desktop_entries()
+    }
+
+    fn watch_roots(&self) -> Vec<PathBuf> {
+        desktop_roots()
+    }
+}
+
+/// 扫描所有 XDG 应用目录，返回命中的应用列表。
+///
+/// `skipped` 包含被过滤规则跳过的条目（NoDisplay / Hidden / OnlyShowIn / TryExec 等）。
+pub fn scan_desktop_entries() -> Result<ScanReport> {
+    let mut report = ScanReport::default();
+    let mut seen_ids = HashSet::new();
+    let current_desktops = current_desktops();
+
+    for root in desktop_roots() {
+        if !root.is_dir() {
+            debug!(?root, "applications 目录不存在，跳过");
+            continue;
+        }
+        debug!(?root, "扫描 applications 目录");
+        scan_dir(
+            &root,
+            &root,
+            0,
+            &current_desktops,
+            &mut seen_ids,
+            &mut report,
+        );
+    }
+
+    info!(
+        entries = report.entries.len(),
+        scanned = report.scanned,
+        skipped = report.skipped,
+        "desktop 文件扫描完成"
+    );
+    Ok(report)
+}
+
+/// 按 XDG 优先级返回 `applications` 目录列表（用户级在前）。
+pub fn desktop_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    // 用户级：$XDG_DATA_HOME，缺省为 ~/.local/share
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("share")));
+    if let Some(home) = data_home {
+        roots.push(home.join("applications"));
+    }
+
+    // 系统级：$XDG_DATA_DIRS
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_DATA_DIRS.to_string());
+    for dir in data_dirs.split(':').filter(|d| !d.is_empty()) {
+        let root = PathBuf::from(dir).join("applications");
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    roots
+}
+
+/// 当前桌面环境名列表（`$XDG_CURRENT_DESKTOP`，冒号分隔）。
+fn current_desktops() -> Vec<String> {
+    env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn scan_dir(
+    root: &Path,
+    dir: &Path,
+    depth: u32,
+    current_desktops: &[String],
+    seen_ids: &mut HashSet<String>,
+    report: &mut ScanReport,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_dir(root, &path, depth + 1, current_desktops, seen_ids, report);
+            continue;
+        }
+        if !is_desktop_file(&path) {
+            continue;
+        }
+
+        report.scanned += 1;
+
+        // 高优先级目录已出现同 ID 的文件时，低优先级的被覆盖
+        let id = desktop_file_id(root, &path);
+        if !seen_ids.insert(id) {
+            report.skipped += 1;
+            continue;
+        }
+
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) => {
+                debug!(?path, error = %e, "读取 desktop 文件失败");
+                report.errors += 1;
+                continue;
+            }
+        };
+
+        match to_app_entry(&parse_desktop_entry(&content), &path, current_desktops) {
+            Some(app) => report.entries.push(app),
+            None => report.skipped += 1,
+        }
+    }
+}
+
+/// 解析后的 `[Desktop Entry]` 分组：键（含 locale 后缀）→ 已反转义的值。
+#[derive(Debug, Default)]
+pub struct DesktopEntry {
+    fields: HashMap<String, String>,
+}
+
+impl DesktopEntry {
+    /// 读取原始键值（如 `Name`、`Name[zh_CN]`）。
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(|s| s.as_str())
+    }
+
+    /// 显示名：优先本地化名称（`Name[zh_CN]` 等），否则取 `Name`。
+    pub fn display_name(&self) -> Option<&str> {
+        LOCALIZED_NAME_KEYS
+            .iter()
+            .chain(&["Name"])
+            .filter_map(|k| self.get(k))
+            .map(str::trim)
+            .find(|s| !s.is_empty())
+    }
+
+    /// 读取布尔键，仅 `true` 视为真。
+    pub fn get_bool(&self, key: &str) -> bool {
+        self.get(key).map(|v| v.trim() == "true").unwrap_or(false)
+    }
+
+    /// 读取 `;` 分隔的列表键，忽略空项；`\;` 是项内的字面分号，不作分隔。
+    pub fn get_list(&self, key: &str) -> Vec<String> {
+        let Some(value) = self.get(key) else {
+            return Vec::new();
+        };
+        let mut items = Vec::new();
+        let mut item = String::new();
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(';') => item.push(';'),
+                    Some(other) => {
+                        item.push('\\');
+                        item.push(other);
+                    }
+                    None => item.push('\\'),
+                },
+                ';' => items.push(std::mem::take(&mut item)),
+                other => item.push(other),
+            }
+        }
+        items.push(item);
+        items
+            .iter()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// 解析 `.desktop` 文本，只保留 `[Desktop Entry]` 分组（忽略 `[Desktop Action ...]` 等）。
+pub fn parse_desktop_entry(content: &str) -> DesktopEntry {
+    let mut entry = DesktopEntry::default();
+    let mut in_main_group = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_main_group = line == DESKTOP_ENTRY_GROUP;
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            entry
+                .fields
+                .entry(key.trim().to_string())
+                .or_insert_with(|| unescape(value.trim()));
+        }
+    }
+
+    entry
+}
+
+/// 处理规范定义的转义序列：`\s` `\n` `\t` `\r` `\\`。
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            // 其余转义（如 `\;`）保留原样，交给列表拆分处理
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// 按过滤规则把解析结果转为 [`AppEntry`]，不可展示时返回 `None`。
+fn to_app_entry(
+    entry: &DesktopEntry,
+    path: &Path,
+    current_desktops: &[String],
+) -> Option<AppEntry> {
+    if entry.get("Type") != Some("Application") {
+        debug!(?path, "非 Application 类型，跳过");
+        return None;
+    }
+    if entry.get_bool("NoDisplay") || entry.get_bool("Hidden") {
+        debug!(?path, "NoDisplay/Hidden，跳过");
+        return None;
+    }
+    if !shown_in_desktop(entry, current_desktops) {
+        debug!(?path, "OnlyShowIn/NotShowIn 不匹配当前桌面，跳过");
+        return None;
+    }
+    if entry.get("Exec").map(str::trim).unwrap_or("").is_empty() {
+        debug!(?path, "缺少 Exec，跳过");
+        return None;
+    }
+    if let Some(try_exec) = entry.get("TryExec") {
+        if find_executable(try_exec).is_none() {
+            debug!(?path, try_exec, "TryExec 指向的程序不存在，跳过");
+            return None;
+        }
+    }
+
+    let generic_name = entry.get("Name")?.trim().to_string();
+    if generic_name.is_empty() {
+        return None;
+    }
+    // 中文名作显示名，原始英文名保留为关键词，两种输入都能命中
+    let name = entry.display_name().unwrap_or(&generic_name).to_string();
+    let mut keywords = entry.get_list("Keywords");
+    if name != generic_name {
+        keywords.insert(0, generic_name);
+    }
+
+    let icon = entry
+        .get("Icon")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    Some(AppEntry {
+        name,
+        path: path.to_path_buf(),
+        source: AppSource::DesktopEntry,
+        icon,
+        keywords,
+    })
+}
+
+/// 检查 `OnlyShowIn` / `NotShowIn` 是否允许在当前桌面显示。
+fn shown_in_desktop(entry: &DesktopEntry, current_desktops: &[String]) -> bool {
+    let only = entry.get_list("OnlyShowIn");
+    if !only.is_empty() && !only.iter().any(|d| current_desktops.contains(d)) {
+        return false;
+    }
+    let not = entry.get_list("NotShowIn");
+    !not.iter().any(|d| current_desktops.contains(d))
+}
+
+/// 解析可执行文件：绝对路径直接检查，否则在 `$PATH` 中查找。
+///
+/// Windows 上未带扩展名的命令按 `PATHEXT`（如 `.EXE;.CMD`）依次补全，`code` 可找到 `code.cmd`。
+pub fn find_executable(program: &str) -> Option<PathBuf> {
+    let program = program.trim();
+    if program.is_empty() {
+        return None;
+    }
+    let pathext = if cfg!(windows) {
+        env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string())
+    } else {
+        String::new()
+    };
+    let names = executable_names(program, &pathext);
+    let candidate = Path::new(program);
+    if candidate.is_absolute() {
+        return names.iter().map(PathBuf::from).find(|p| p.is_file());
+    }
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths)
+            .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+            .find(|p| p.is_file())
+    })
+}
+
+/// `PATHEXT` 未设置时 Windows 的默认值。
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// 依次尝试的文件名：原名在前；没有扩展名时再追加 `pathext` 中的每个扩展名。
+fn executable_names(program: &str, pathext: &str) -> Vec<String> {
+    let mut names = vec![program.to_string()];
+    if Path::new(program).extension().is_none() {
+        names.extend(
+            pathext
+                .split(';')
+                .map(str::trim)
+                .filter(|ext| !ext.is_empty())
+                .map(|ext| format!("{}{}", program, ext)),
+        );
+    }
+    names
+}
+
+/// 计算 desktop-file ID：相对 `applications/` 的路径，目录分隔符替换为 `-`。
+fn desktop_file_id(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace(['/', '\\'], "-")
+}
+
+fn is_desktop_file(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("desktop")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# comment
+[Desktop Entry]
+Type=Application
+Name=Visual Studio Code
+Name[zh_CN]=代码编辑器
+Exec=/usr/bin/code %F
+Icon=vscode
+Keywords=vscode;editor;
+
+[Desktop Action new-window]
+Name=New Window
+Exec=/usr/bin/code --new-window
+";
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "sparknova_desktop_test_{}_{:?}",
+            tag,
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_keeps_only_main_group() {
+        let entry = parse_desktop_entry(SAMPLE);
+        assert_eq!(entry.get("Name"), Some("Visual Studio Code"));
+        assert_eq!(entry.get("Name[zh_CN]"), Some("代码编辑器"));
+        assert_eq!(entry.get("Exec"), Some("/usr/bin/code %F"));
+        assert_eq!(entry.get_list("Keywords"), vec!["vscode", "editor"]);
+    }
+
+    #[test]
+    fn localized_name_preferred_and_generic_kept_as_keyword() {
+        let entry = parse_desktop_entry(SAMPLE);
+        let app = to_app_entry(&entry, Path::new("/x/code.desktop"), &[]).unwrap();
+        assert_eq!(app.name, "代码编辑器");
+        assert_eq!(app.source, AppSource::DesktopEntry);
+        assert_eq!(app.icon.as_deref(), Some("vscode"));
+        assert_eq!(app.keywords[0], "Visual Studio Code");
+        assert!(app.keywords.contains(&"editor".to_string()));
+    }
+
+    #[test]
+    fn hidden_and_nodisplay_are_skipped() {
+        let hidden =
+            parse_desktop_entry("[Desktop Entry]\nType=Application\nName=A\nExec=a\nHidden=true\n");
+        assert!(to_app_entry(&hidden, Path::new("/a.desktop"), &[]).is_none());
+
+        let nodisplay = parse_desktop_entry(
+            "[Desktop Entry]\nType=Application\nName=A\nExec=a\nNoDisplay=true\n",
+        );
+        assert!(to_app_entry(&nodisplay, Path::new("/a.desktop"), &[]).is_none());
+    }
+
+    #[test]
+    fn only_show_in_respects_current_desktop() {
+        let entry = parse_desktop_entry(
+            "[Desktop Entry]\nType=Application\nName=A\nExec=a\nOnlyShowIn=KDE;\n",
+        );
+        assert!(to_app_entry(&entry, Path::new("/a.desktop"), &["GNOME".to_string()]).is_none());
+        assert!(to_app_entry(&entry, Path::new("/a.desktop"), &["KDE".to_string()]).is_some());
+    }
+
+    #[test]
+    fn missing_try_exec_is_skipped() {
+        let entry = parse_desktop_entry(
+            "[Desktop Entry]\nType=Application\nName=A\nExec=a\nTryExec=/nonexistent/sparknova_bin_12345\n",
+        );
+        assert!(to_app_entry(&entry, Path::new("/a.desktop"), &[]).is_none());
+    }
+
+    #[test]
+    fn pathext_extends_bare_commands_only() {
+        assert_eq!(
+            executable_names("code", ".EXE; .CMD;"),
+            vec!["code", "code.EXE", "code.CMD"]
+        );
+        assert_eq!(executable_names("code.cmd", ".EXE;.CMD"), vec!["code.cmd"]);
+        assert_eq!(executable_names("code", ""), vec!["code"]);
+    }
+
+    #[test]
+    fn escapes_in_values_and_lists() {
+        assert_eq!(unescape(r"a\sb\\c"), r"a b\c");
+        // `\;` 留给列表拆分：作项内字面分号，不作分隔
+        let entry = parse_desktop_entry("[Desktop Entry]\nKeywords=a\\;b;c;\n");
+        assert_eq!(entry.get_list("Keywords"), vec!["a;b", "c"]);
+    }
+
+    #[test]
+    fn scan_dir_dedups_by_desktop_file_id() {
+        let high = temp_dir("high");
+        let low = temp_dir("low");
+        let body = "[Desktop Entry]\nType=Application\nName=Foo\nExec=foo\n";
+        fs::write(high.join("foo.desktop"), body).unwrap();
+        fs::write(low.join("foo.desktop"), body).unwrap();
+        fs::write(low.join("readme.txt"), "not a desktop file").unwrap();
+
+        let mut report = ScanReport::default();
+        let mut seen = HashSet::new();
+        scan_dir(&high, &high, 0, &[], &mut seen, &mut report);
+        scan_dir(&low, &low, 0, &[], &mut seen, &mut report);
+
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].path, high.join("foo.desktop"));
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.skipped, 1);
+    }
+}
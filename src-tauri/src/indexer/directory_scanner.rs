@@ -7,6 +7,7 @@ use std::path::{Path, PathBuf};
 use std::{fs, io};
 use tracing::{debug, info, warn};
 
+use super::scanner::{ScanReport, Scanner};
 use crate::domain::{AppEntry, AppSource};
 
 /// 递归最大深度，防止极深目录或符号链接环导致栈溢出。
@@ -36,16 +37,38 @@ const NAME_BLACKLIST_PREFIXES: &[&str] = &[
     "vcredist",
 ];
 
-/// 单次扫描结果。
-#[derive(Debug, Default)]
-pub struct ScanReport {
-    pub entries: Vec<AppEntry>,
-    /// 遍历到的 `.exe` 总数
-    pub scanned_exes: usize,
-    /// 被过滤规则跳过的数量
-    pub skipped: usize,
-    /// 无法访问的目录数
-    pub err_dirs: usize,
+/// 用户自定义目录来源，目录列表来自 `UserConfig::scan_dirs`。
+pub struct UserDirScanner {
+    dirs: Vec<PathBuf>,
+}
+
+impl UserDirScanner {
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        Self { dirs }
+    }
+}
+
+impl Scanner for UserDirScanner {
+    fn name(&self) -> &'static str {
+        "user_dirs"
+    }
+
+    fn source(&self) -> AppSource {
+        AppSource::UserDirectory
+    }
+
+    /// 始终可用：清空扫描目录后仍要扫描一次，空结果让对账移除之前收录的条目。
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> Result<ScanReport> {
+        scan_user_dirs(&self.dirs)
+    }
+
+    fn watch_roots(&self) -> Vec<PathBuf> {
+        self.dirs.clone()
+    }
 }
 
 /// 扫描用户自定义目录列表，返回收集到的应用条目。
@@ -67,9 +90,9 @@ pub fn scan_user_dirs(dirs: &[PathBuf]) -> Result<ScanReport> {
 
     info!(
         entries = report.entries.len(),
-        scanned = report.scanned_exes,
+        scanned = report.scanned,
         skipped = report.skipped,
-        err_dirs = report.err_dirs,
+        err_dirs = report.errors,
         "用户目录扫描完成"
     );
     Ok(report)
@@ -85,7 +108,7 @@ fn scan_dir(dir: &Path, depth: u32, report: &mut ScanReport) {
         Err(e) => {
             if e.kind() != io::ErrorKind::NotFound {
                 warn!(error = %e, ?dir, "读取用户目录失败，跳过子树");
-                report.err_dirs += 1;
+                report.errors += 1;
             }
             return;
         }
@@ -111,7 +134,7 @@ fn scan_dir(dir: &Path, depth: u32, report: &mut ScanReport) {
             }
             scan_dir(&path, depth + 1, report);
         } else if is_exe(&path) {
-            report.scanned_exes += 1;
+            report.scanned += 1;
             if should_skip_exe(&path) {
                 report.skipped += 1;
                 continue;
@@ -125,6 +148,8 @@ fn scan_dir(dir: &Path, depth: u32, report: &mut ScanReport) {
                 name,
                 path,
                 source: AppSource::UserDirectory,
+                icon: None,
+                keywords: Vec::new(),
             });
         }
     }
@@ -165,6 +190,11 @@ mod tests {
     fn empty_dirs_returns_empty() {
         let report = scan_user_dirs(&[]).unwrap();
         assert!(report.entries.is_empty());
+
+        // 没有配置目录时来源仍参与扫描，旧条目才能被对账移除
+        let scanner = UserDirScanner::new(Vec::new());
+        assert!(scanner.is_available());
+        assert!(scanner.scan().unwrap().entries.is_empty());
     }
 
     #[test]
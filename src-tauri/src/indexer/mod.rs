@@ -4,9 +4,19 @@
 //! - `app_scanner`   v0.1 —— 开始菜单 `.lnk` + 注册表 Uninstall
 //! - `user_dir_scanner` v0.2 —— 用户自定义目录（绿色软件）
 //! - `history_learner`  v0.3 —— SparkNova 内手动启动过的路径（"用过即学"）
+//! - `desktop_scanner`  Linux —— XDG `.desktop` 应用
+//!
+//! 所有来源实现 [`Scanner`]，由 [`ScannerRegistry`] 统一调度。
 
 mod app_scanner;
+mod desktop_scanner;
 mod directory_scanner;
+#[cfg(windows)]
 mod registry_scanner;
+mod scanner;
+mod source_registry;
+mod watcher;
 
-pub use app_scanner::scan_start_menu;
\ No newline at end of file
+pub use desktop_scanner::{find_executable, parse_desktop_entry};
+pub use source_registry::{ScannerRegistry, SourceReport};
+pub use watcher::IndexWatcher;
\ No newline at end of file
@@ -8,19 +8,33 @@ use tracing::{debug, info, warn};
 use winreg::enums::*;
 use winreg::RegKey;
 
+use super::scanner::{ScanReport, Scanner};
 use crate::domain::{AppEntry, AppSource};
 
-/// 注册表扫描结果统计。
-#[derive(Debug, Default)]
-pub struct RegistryReport {
-    pub entries: Vec<AppEntry>,
-    pub scanned_keys: usize,
-    pub skipped: usize,
+/// 注册表 Uninstall 来源（模块本身仅在 Windows 编译）。
+pub struct RegistryScanner;
+
+impl Scanner for RegistryScanner {
+    fn name(&self) -> &'static str {
+        "registry"
+    }
+
+    fn source(&self) -> AppSource {
+        AppSource::RegistryUninstall
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn scan(&self) -> anyhow::Result<ScanReport> {
+        scan_registry()
+    }
 }
 
 /// 扫描注册表 Uninstall 键，返回应用列表。
-pub fn scan_registry() -> anyhow::Result<RegistryReport> {
-    let mut report = RegistryReport::default();
+pub fn scan_registry() -> anyhow::Result<ScanReport> {
+    let mut report = ScanReport::default();
 
     let hives = [
         (
@@ -40,13 +54,16 @@ pub fn scan_registry() -> anyhow::Result<RegistryReport> {
     for (hive, path) in hives {
         match scan_hive(hive, path, &mut report) {
             Ok(_) => debug!(hive = ?hive, path = path, "注册表 hive 扫描完成"),
-            Err(e) => warn!(error = %e, hive = ?hive, ?path, "注册表 hive 扫描失败"),
+            Err(e) => {
+                warn!(error = %e, hive = ?hive, ?path, "注册表 hive 扫描失败");
+                report.errors += 1;
+            }
         }
     }
 
     info!(
         entries = report.entries.len(),
-        scanned = report.scanned_keys,
+        scanned = report.scanned,
         skipped = report.skipped,
         "注册表扫描完成"
     );
@@ -54,11 +71,11 @@ pub fn scan_registry() -> anyhow::Result<RegistryReport> {
     Ok(report)
 }
 
-fn scan_hive(hive: winreg::HKEY, path: &str, report: &mut RegistryReport) -> anyhow::Result<()> {
+fn scan_hive(hive: winreg::HKEY, path: &str, report: &mut ScanReport) -> anyhow::Result<()> {
     let key = RegKey::predef(hive).open_subkey(path)?;
 
     for subkey_name in key.enum_keys().flatten() {
-        report.scanned_keys += 1;
+        report.scanned += 1;
 
         let subkey = match key.open_subkey(&subkey_name) {
             Ok(k) => k,
@@ -96,6 +113,8 @@ fn scan_hive(hive: winreg::HKEY, path: &str, report: &mut RegistryReport) -> any
                     name: display_name,
                     path: exe_path.clone(),
                     source: AppSource::RegistryUninstall,
+                    icon: None,
+                    keywords: Vec::new(),
                 });
             } else {
                 debug!(name = display_name, path = ?path, "注册表应用路径不存在，跳过");
@@ -170,6 +189,6 @@ mod tests {
         assert!(result.is_ok());
 
         let report = result.unwrap();
-        assert!(report.scanned_keys > 0 || report.entries.is_empty());
+        assert!(report.scanned > 0 || report.entries.is_empty());
     }
 }
\ No newline at end of file
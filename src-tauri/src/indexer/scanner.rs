@@ -0,0 +1,43 @@
+//! 扫描器抽象：所有应用来源实现同一个 [`Scanner`] trait，产出统一的 [`ScanReport`]。
+//!
+//! 新增来源只需实现 trait 并在 [`super::ScannerRegistry`] 中注册，
+//! 调用方（`commands::search`）无需改动。
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::domain::{AppEntry, AppSource};
+
+/// 单次扫描结果与统计。字段为 pub 供调用方日志化；search 引擎取 `entries`。
+#[derive(Debug, Default)]
+pub struct ScanReport {
+    pub entries: Vec<AppEntry>,
+    /// 遍历到的候选项总数（`.lnk` / `.exe` / 注册表子键 / `.desktop`）
+    pub scanned: usize,
+    /// 被过滤规则跳过或解析失败的数量
+    pub skipped: usize,
+    /// 无法访问的目录/键数量
+    pub errors: usize,
+}
+
+/// 应用来源扫描器。
+pub trait Scanner: Send + Sync {
+    /// 来源名（日志用），如 `"start_menu"`。
+    fn name(&self) -> &'static str;
+
+    /// 产出条目的来源标签。
+    fn source(&self) -> AppSource;
+
+    /// 当前平台/配置下是否可用；不可用的扫描器不会被执行。
+    fn is_available(&self) -> bool;
+
+    /// 执行一次全量扫描。
+    fn scan(&self) -> Result<ScanReport>;
+
+    /// 需要文件监听的根目录；变动落在其中时重新扫描本来源。
+    /// 默认无（如注册表来源无法通过文件系统监听）。
+    fn watch_roots(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
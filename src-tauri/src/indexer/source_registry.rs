@@ -0,0 +1,198 @@
+//! 扫描来源注册表：按注册顺序执行所有可用的 [`Scanner`]。
+//!
+//! 单个来源失败或 panic 只记录日志，不影响其他来源。
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use tracing::{debug, info, warn};
+
+use super::scanner::{ScanReport, Scanner};
+use crate::domain::AppSource;
+
+/// 单个来源的扫描结果。
+#[derive(Debug)]
+pub struct SourceReport {
+    pub source: AppSource,
+    pub report: ScanReport,
+}
+
+/// 扫描器注册表。
+#[derive(Default)]
+pub struct ScannerRegistry {
+    scanners: Vec<Box<dyn Scanner>>,
+}
+
+impl ScannerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册所有内置来源：开始菜单、注册表（Windows）、`.desktop`（Linux）、用户目录。
+    pub fn with_builtin(scan_dirs: Vec<PathBuf>) -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(super::app_scanner::StartMenuScanner));
+        #[cfg(windows)]
+        registry.register(Box::new(super::registry_scanner::RegistryScanner));
+        registry.register(Box::new(super::desktop_scanner::DesktopEntryScanner));
+        registry.register(Box::new(super::directory_scanner::UserDirScanner::new(
+            scan_dirs,
+        )));
+        registry
+    }
+
+    /// 注册一个扫描器。
+    pub fn register(&mut self, scanner: Box<dyn Scanner>) {
+        self.scanners.push(scanner);
+    }
+
+    /// 依次执行所有可用来源，返回成功的来源结果。
+    ///
+    /// 失败的来源不出现在返回值中，调用方据此区分"扫描为空"和"扫描失败"。
+    pub fn scan_all(&self) -> Vec<SourceReport> {
+        self.available()
+            .filter_map(|scanner| run_scanner(scanner.as_ref()))
+            .collect()
+    }
+
+    /// 只重新扫描监听根目录覆盖了 `changed` 中任一路径的来源（文件监听增量更新用）。
+    pub fn scan_affected(&self, changed: &[PathBuf]) -> Vec<SourceReport> {
+        self.available()
+            .filter(|scanner| {
+                let roots = scanner.watch_roots();
+                changed
+                    .iter()
+                    .any(|p| roots.iter().any(|root| p.starts_with(root)))
+            })
+            .filter_map(|scanner| run_scanner(scanner.as_ref()))
+            .collect()
+    }
+
+    /// 只重新扫描指定来源（如扫描目录配置变化后重扫 `UserDirectory`）。
+    pub fn scan_sources(&self, sources: &[AppSource]) -> Vec<SourceReport> {
+        self.available()
+            .filter(|scanner| sources.contains(&scanner.source()))
+            .filter_map(|scanner| run_scanner(scanner.as_ref()))
+            .collect()
+    }
+
+    /// 所有可用来源的监听根目录（去重）。尚不存在的目录同样返回，
+    /// 由 [`super::IndexWatcher`] 在它出现后开始监听。
+    pub fn watch_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for root in self.available().flat_map(|s| s.watch_roots()) {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        roots
+    }
+
+    fn available(&self) -> impl Iterator<Item = &Box<dyn Scanner>> {
+        self.scanners.iter().filter(|scanner| {
+            let available = scanner.is_available();
+            if !available {
+                debug!(source = scanner.name(), "来源在当前环境不可用，跳过");
+            }
+            available
+        })
+    }
+}
+
+/// 执行单个来源；catch_unwind 防止单个来源 panic 导致整次扫描中断。
+fn run_scanner(scanner: &dyn Scanner) -> Option<SourceReport> {
+    let name = scanner.name();
+    match catch_unwind(AssertUnwindSafe(|| scanner.scan())) {
+        Ok(Ok(report)) => {
+            info!(
+                source = name,
+                entries = report.entries.len(),
+                scanned = report.scanned,
+                skipped = report.skipped,
+                errors = report.errors,
+                "来源扫描完成"
+            );
+            Some(SourceReport {
+                source: scanner.source(),
+                report,
+            })
+        }
+        Ok(Err(e)) => {
+            warn!(source = name, error = ?e, "来源扫描失败");
+            None
+        }
+        Err(e) => {
+            warn!(source = name, error = ?e, "来源扫描 panic");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AppEntry;
+
+    struct FakeScanner {
+        available: bool,
+        fail: bool,
+    }
+
+    impl Scanner for FakeScanner {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn source(&self) -> AppSource {
+            AppSource::UserDirectory
+        }
+
+        fn is_available(&self) -> bool {
+            self.available
+        }
+
+        fn scan(&self) -> anyhow::Result<ScanReport> {
+            if self.fail {
+                anyhow::bail!("boom");
+            }
+            Ok(ScanReport {
+                entries: vec![AppEntry {
+                    name: "Fake".into(),
+                    path: PathBuf::from("/fake"),
+                    source: AppSource::UserDirectory,
+                    icon: None,
+                    keywords: Vec::new(),
+                }],
+                scanned: 1,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn unavailable_and_failed_sources_are_excluded() {
+        let mut registry = ScannerRegistry::new();
+        registry.register(Box::new(FakeScanner {
+            available: true,
+            fail: false,
+        }));
+        registry.register(Box::new(FakeScanner {
+            available: false,
+            fail: false,
+        }));
+        registry.register(Box::new(FakeScanner {
+            available: true,
+            fail: true,
+        }));
+
+        let results = registry.scan_all();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].report.entries[0].name, "Fake");
+    }
+
+    #[test]
+    fn builtin_registers_all_sources() {
+        let registry = ScannerRegistry::with_builtin(Vec::new());
+        assert!(registry.scanners.len() >= 3);
+    }
+}
@@ -0,0 +1,273 @@
+//! 文件监听：监听开始菜单、`.desktop` 目录和用户扫描目录，变动后去抖再回调。
+//!
+//! 流程：
+//!
+//! ```text
+//! [notify 事件 (inotify / ReadDirectoryChangesW / FSEvents)]
+//!   -> 过滤掉 Access 类事件
+//!   -> mpsc 通道 -> 去抖线程
+//!      -> 静默 DEBOUNCE_MS 后把累计的路径一次性交给 on_change
+//! ```
+//!
+//! 尚不存在的根目录（如新账户首次按用户安装应用时才创建的 `~/.local/share/applications`）
+//! 先非递归监听最近的已存在祖先目录；每批变动后重新检查，根目录出现即改为递归监听，
+//! 并把它交给 on_change 触发一次重扫。
+//!
+//! [`IndexWatcher`] 被 drop 时底层 watcher 随之释放，通道断开，去抖线程自然退出。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, info, warn};
+
+/// 去抖窗口：最后一个事件之后静默这么久才触发回调（安装程序通常连续写很多文件）。
+pub const DEBOUNCE_MS: u64 = 1500;
+
+/// 文件监听句柄。持有期间监听生效。
+pub struct IndexWatcher {
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
+}
+
+impl IndexWatcher {
+    /// 递归监听 `roots`，变动去抖后以去重的、落在某个根目录下的路径列表调用 `on_change`。
+    ///
+    /// 单个根目录监听失败只记录日志；`on_change` 在独立线程中执行。
+    pub fn start<F>(roots: Vec<PathBuf>, on_change: F) -> Result<Self>
+    where
+        F: Fn(Vec<PathBuf>) + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<Vec<PathBuf>>();
+
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+                Ok(event) if is_relevant(&event.kind) => {
+                    let _ = tx.send(event.paths);
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "文件监听事件错误"),
+            })?;
+
+        let mut roots = WatchRoots::new(roots);
+        roots.arm(&mut |path, mode| watch(&mut watcher, path, mode));
+        info!(roots = roots.armed.len(), pending = roots.pending.len(), "文件监听已启动");
+
+        // 去抖线程只持有弱引用：句柄 drop 后 watcher 随之释放
+        let watcher = Arc::new(Mutex::new(watcher));
+        let weak = Arc::downgrade(&watcher);
+        thread::spawn(move || {
+            debounce_loop(rx, Duration::from_millis(DEBOUNCE_MS), |paths| {
+                if let Some(watcher) = weak.upgrade() {
+                    let mut watcher = watcher.lock().unwrap();
+                    roots.arm(&mut |path, mode| watch(&mut watcher, path, mode));
+                }
+                let paths = roots.relevant(paths);
+                if !paths.is_empty() {
+                    on_change(paths);
+                }
+            })
+        });
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+fn watch(watcher: &mut RecommendedWatcher, path: &Path, mode: RecursiveMode) -> bool {
+    match watcher.watch(path, mode) {
+        Ok(()) => {
+            debug!(?path, ?mode, "开始监听目录");
+            true
+        }
+        Err(e) => {
+            warn!(?path, error = %e, "监听目录失败，跳过");
+            false
+        }
+    }
+}
+
+/// 监听根目录的布防状态。
+struct WatchRoots {
+    roots: Vec<PathBuf>,
+    /// 已递归监听的根目录
+    armed: HashSet<PathBuf>,
+    /// 尚不存在的根目录 → 代为非递归监听的祖先目录
+    pending: HashMap<PathBuf, PathBuf>,
+}
+
+impl WatchRoots {
+    fn new(roots: Vec<PathBuf>) -> Self {
+        Self {
+            roots,
+            armed: HashSet::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// 递归监听已出现的根目录；仍不存在的根目录改为监听最近的已存在祖先目录
+    /// （已被某个根目录递归覆盖时无需再监听）。`watch` 返回是否成功。
+    fn arm(&mut self, watch: &mut dyn FnMut(&Path, RecursiveMode) -> bool) {
+        for root in &self.roots {
+            if self.armed.contains(root) || !root.is_dir() {
+                continue;
+            }
+            if watch(root, RecursiveMode::Recursive) {
+                self.armed.insert(root.clone());
+                self.pending.remove(root);
+            }
+        }
+        for root in &self.roots {
+            if self.armed.contains(root) {
+                continue;
+            }
+            let Some(parent) = root.ancestors().skip(1).find(|p| p.is_dir()) else {
+                continue;
+            };
+            if self.pending.get(root).map(PathBuf::as_path) == Some(parent) {
+                continue;
+            }
+            let covered = self.armed.iter().any(|r| parent.starts_with(r));
+            if covered || watch(parent, RecursiveMode::NonRecursive) {
+                self.pending.insert(root.clone(), parent.to_path_buf());
+            }
+        }
+    }
+
+    /// 只保留落在某个根目录下（含根目录本身）的路径；祖先目录里的其他变动与索引无关。
+    fn relevant(&self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        paths
+            .into_iter()
+            .filter(|p| self.roots.iter().any(|root| p.starts_with(root)))
+            .collect()
+    }
+}
+
+/// 只关心增删改；Access（打开/读取）事件过于频繁且与索引无关。
+fn is_relevant(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_) | EventKind::Any
+    )
+}
+
+/// 去抖循环：收到首个事件后持续累积，直到 `window` 内无新事件再回调一次。
+fn debounce_loop<F>(rx: Receiver<Vec<PathBuf>>, window: Duration, mut on_change: F)
+where
+    F: FnMut(Vec<PathBuf>),
+{
+    // recv 失败 = 发送端（watcher）已 drop，退出线程
+    while let Ok(first) = rx.recv() {
+        let mut pending: HashSet<PathBuf> = first.into_iter().collect();
+        let mut disconnected = false;
+
+        loop {
+            match rx.recv_timeout(window) {
+                Ok(paths) => pending.extend(paths),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        if !pending.is_empty() {
+            debug!(count = pending.len(), "文件变动去抖完成");
+            on_change(pending.into_iter().collect());
+        }
+        if disconnected {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn debounce_coalesces_burst_into_one_callback() {
+        let (tx, rx) = mpsc::channel();
+        let calls: Arc<Mutex<Vec<Vec<PathBuf>>>> = Arc::default();
+        let sink = calls.clone();
+
+        let handle = thread::spawn(move || {
+            debounce_loop(rx, Duration::from_millis(50), move |paths| {
+                sink.lock().unwrap().push(paths)
+            })
+        });
+
+        tx.send(vec![PathBuf::from("/a")]).unwrap();
+        tx.send(vec![PathBuf::from("/b"), PathBuf::from("/a")])
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+        tx.send(vec![PathBuf::from("/c")]).unwrap();
+        drop(tx);
+        handle.join().unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        let mut first = calls[0].clone();
+        first.sort();
+        assert_eq!(first, vec![PathBuf::from("/a"), PathBuf::from("/b")]);
+        assert_eq!(calls[1], vec![PathBuf::from("/c")]);
+    }
+
+    #[test]
+    fn missing_roots_are_armed_once_they_appear() {
+        let base = std::env::temp_dir().join(format!(
+            "sparknova_watch_test_{:?}_{:?}",
+            thread::current().id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let (existing, missing) = (base.join("menu"), base.join("share").join("applications"));
+        std::fs::create_dir_all(&existing).unwrap();
+
+        let mut roots = WatchRoots::new(vec![existing.clone(), missing.clone()]);
+        let arm = |roots: &mut WatchRoots| {
+            let mut calls = Vec::new();
+            roots.arm(&mut |path, mode| {
+                calls.push((path.to_path_buf(), mode));
+                true
+            });
+            calls
+        };
+
+        // 不存在的根目录：非递归监听最近的已存在祖先
+        assert_eq!(
+            arm(&mut roots),
+            vec![
+                (existing.clone(), RecursiveMode::Recursive),
+                (base.clone(), RecursiveMode::NonRecursive)
+            ]
+        );
+        assert!(arm(&mut roots).is_empty());
+
+        // 中间目录先出现：祖先下移；根目录出现后改为递归监听
+        std::fs::create_dir(base.join("share")).unwrap();
+        assert_eq!(arm(&mut roots), vec![(base.join("share"), RecursiveMode::NonRecursive)]);
+        std::fs::create_dir(&missing).unwrap();
+        assert_eq!(arm(&mut roots), vec![(missing.clone(), RecursiveMode::Recursive)]);
+        assert!(roots.pending.is_empty());
+
+        // 祖先目录中的无关变动不回调
+        let changed = vec![missing.join("app.desktop"), base.join("share").join("fonts")];
+        assert_eq!(roots.relevant(changed), vec![missing.join("app.desktop")]);
+        std::fs::remove_dir_all(base).ok();
+    }
+
+    #[test]
+    fn access_events_are_ignored() {
+        use notify::event::{AccessKind, CreateKind};
+        assert!(!is_relevant(&EventKind::Access(AccessKind::Any)));
+        assert!(is_relevant(&EventKind::Create(CreateKind::File)));
+    }
+}
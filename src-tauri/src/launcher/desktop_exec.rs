@@ -0,0 +1,152 @@
+//! `.desktop` 的 `Exec` 行：按 Desktop Entry 规范拆分参数并展开字段码。
+//!
+//! 拆分规则：空白分隔；双引号内的 `\"` `` \` `` `\$` `\\` 为转义，其余字符原样保留。
+//! 通用转义（`\s`、`\\` 等）已在解析 `.desktop` 时处理过，这里不再重复。
+//!
+//! 字段码：启动器不传文件/URL，`%f %F %u %U` 直接去掉；`%i` 展开为 `--icon <Icon>`，
+//! `%c` 为（本地化的）名称，`%k` 为 `.desktop` 文件路径，`%%` 为字面量 `%`；
+//! 已废弃的 `%d %D %n %N %v %m` 和无法识别的字段码一律去掉。
+
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+/// 展开字段码所需的条目信息。
+#[derive(Debug, Clone, Copy)]
+pub struct ExecContext<'a> {
+    /// `Icon` 键
+    pub icon: Option<&'a str>,
+    /// 显示名（`%c`）
+    pub name: &'a str,
+    /// `.desktop` 文件本身（`%k`）
+    pub desktop_file: &'a Path,
+}
+
+/// 拆分 `Exec` 并展开字段码，返回程序与参数（第一个元素为程序）。
+pub fn expand_exec(exec: &str, ctx: &ExecContext) -> Result<Vec<String>> {
+    let mut argv = Vec::new();
+    for token in split_exec(exec)? {
+        match token.as_str() {
+            "%i" => {
+                if let Some(icon) = ctx.icon.filter(|i| !i.is_empty()) {
+                    argv.push("--icon".to_string());
+                    argv.push(icon.to_string());
+                }
+            }
+            // 单独成参的文件/URL 列表、废弃与无法识别的字段码：整个参数去掉
+            t if is_dropped_code(t) => {}
+            _ => argv.push(expand_inline(&token, ctx)),
+        }
+    }
+    if argv.is_empty() {
+        bail!("Exec 为空");
+    }
+    Ok(argv)
+}
+
+fn is_dropped_code(token: &str) -> bool {
+    token.starts_with('%') && token.chars().count() == 2 && !matches!(token, "%%" | "%c" | "%k")
+}
+
+/// 展开嵌在参数中的字段码（如 `--name=%c`）。
+fn expand_inline(token: &str, ctx: &ExecContext) -> String {
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some('c') => out.push_str(ctx.name),
+            Some('k') => out.push_str(&ctx.desktop_file.to_string_lossy()),
+            Some(_) => {}
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+/// 按规范的引号规则拆分参数。
+fn split_exec(exec: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    // 当前参数；None 表示还没开始（用于区分空的 `""` 参数和参数间空白）
+    let mut current: Option<String> = None;
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => tokens.extend(current.take()),
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(e @ ('"' | '`' | '$' | '\\')) => arg.push(e),
+                            Some(other) => {
+                                arg.push('\\');
+                                arg.push(other);
+                            }
+                            None => bail!("Exec 引号未闭合: {}", exec),
+                        },
+                        Some(other) => arg.push(other),
+                        None => bail!("Exec 引号未闭合: {}", exec),
+                    }
+                }
+            }
+            other => current.get_or_insert_with(String::new).push(other),
+        }
+    }
+    tokens.extend(current);
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(exec: &str, icon: Option<&str>) -> Vec<String> {
+        let ctx = ExecContext {
+            icon,
+            name: "代码编辑器",
+            desktop_file: Path::new("/usr/share/applications/code.desktop"),
+        };
+        expand_exec(exec, &ctx).unwrap()
+    }
+
+    #[test]
+    fn quoted_arguments_and_escapes() {
+        assert_eq!(
+            split_exec(r#"  "/opt/My App/run" --title "say \"hi\" \$HOME" "" x "#).unwrap(),
+            vec!["/opt/My App/run", "--title", r#"say "hi" $HOME"#, "", "x"]
+        );
+        assert_eq!(split_exec(r#"a"b c"d"#).unwrap(), vec!["ab cd"]);
+        assert!(split_exec(r#"run "unterminated"#).is_err());
+    }
+
+    #[test]
+    fn field_codes_are_expanded_or_dropped() {
+        assert_eq!(expand("/usr/bin/code %F", Some("vscode")), vec!["/usr/bin/code"]);
+        assert_eq!(expand("app %u --new %U", None), vec!["app", "--new"]);
+        assert_eq!(
+            expand("app %i %c --desktop=%k 100%% %d %x", Some("app-icon")),
+            vec![
+                "app",
+                "--icon",
+                "app-icon",
+                "代码编辑器",
+                "--desktop=/usr/share/applications/code.desktop",
+                "100%",
+            ]
+        );
+        // 没有 Icon 时 %i 整体去掉
+        assert_eq!(expand("app %i", None), vec!["app"]);
+        assert!(expand_exec("%f", &ExecContext {
+            icon: None,
+            name: "",
+            desktop_file: Path::new("/a.desktop"),
+        })
+        .is_err());
+    }
+}
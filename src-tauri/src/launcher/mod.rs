@@ -0,0 +1,593 @@
+//! 启动层：按条目类型（[`LaunchKind`]）决定怎么启动，产出 [`LaunchCommand`] 交给
+//! [`ProcessSpawner`]。测试替换 spawner 即可验证各类条目的启动方式而不真正起进程。
+//!
+//! - `.desktop`：解析 `Exec` 并展开字段码，`Path` 为工作目录，`Terminal=true` 时包进终端；
+//!   `Type=Link` 交给系统默认程序打开 `URL`
+//! - AppImage、可执行文件（ELF 或带 shebang 且有执行权限）：直接运行，工作目录为所在目录
+//! - 脚本（`.sh` 等）：有执行权限时直接运行，否则交给 shebang 指定的解释器或 `sh`；
+//!   `.bat` / `.cmd` 走 `cmd /C`，`.ps1` 走 PowerShell
+//! - 目录、文档、网址：交给系统默认程序（xdg-open / open / explorer）
+//! - 用户自定义条目：命令可以是网址、已存在的路径或 `PATH` 中的程序，
+//!   附加保存的参数、工作目录与环境变量
+
+mod desktop_exec;
+mod spawner;
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+use tracing::debug;
+
+use crate::domain::UserEntry;
+use crate::indexer::{find_executable, parse_desktop_entry};
+use desktop_exec::{expand_exec, ExecContext};
+pub use spawner::{LaunchCommand, ProcessSpawner, SystemSpawner};
+
+/// 常见终端及其"执行后续命令"的参数，按探测优先级排列。
+const TERMINALS: &[(&str, &[&str])] = &[
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("xfce4-terminal", &["-x"]),
+    ("alacritty", &["-e"]),
+    ("kitty", &[]),
+    ("xterm", &["-e"]),
+];
+
+/// 可启动条目的类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchKind {
+    /// XDG `.desktop` 文件
+    DesktopEntry,
+    /// `.AppImage`
+    AppImage,
+    /// 按扩展名识别的脚本（`.sh` `.bash` `.zsh` `.bat` `.cmd` `.ps1`）
+    Script,
+    /// 可直接运行的程序
+    Executable,
+    /// 目录
+    Directory,
+    /// 其他文件，交给系统默认程序
+    Document,
+}
+
+impl LaunchKind {
+    /// 按路径判断条目类型；路径不存在时返回 None。
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        if meta.is_dir() {
+            return Some(Self::Directory);
+        }
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        Some(match ext.as_str() {
+            "desktop" => Self::DesktopEntry,
+            "appimage" => Self::AppImage,
+            "sh" | "bash" | "zsh" | "bat" | "cmd" | "ps1" => Self::Script,
+            "exe" | "com" if cfg!(windows) => Self::Executable,
+            // 执行位在 NTFS 挂载盘等处并不可靠，再用文件头确认
+            _ if is_executable(&meta) && has_executable_header(path) => Self::Executable,
+            _ => Self::Document,
+        })
+    }
+
+    /// 是否接受额外参数（交给系统默认程序打开的类型不接受）。
+    fn takes_args(self) -> bool {
+        !matches!(self, Self::Directory | Self::Document)
+    }
+}
+
+/// `Terminal=true` 时包裹命令的终端。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Terminal {
+    pub program: String,
+    /// 放在被执行命令之前的参数（如 `-e`）
+    pub exec_args: Vec<String>,
+}
+
+/// 探测可用的终端：`$TERMINAL` 优先，其次按 [`TERMINALS`] 顺序在 `PATH` 中查找。
+pub fn detect_terminal() -> Option<Terminal> {
+    let from_env = std::env::var("TERMINAL")
+        .ok()
+        .filter(|t| find_executable(t).is_some())
+        .map(|program| (program, vec!["-e".to_string()]));
+    let (program, exec_args) = from_env.or_else(|| {
+        TERMINALS.iter().find(|(name, _)| find_executable(name).is_some()).map(|(name, args)| {
+            (name.to_string(), args.iter().map(|a| a.to_string()).collect())
+        })
+    })?;
+    Some(Terminal { program, exec_args })
+}
+
+/// 进程内缓存的 [`detect_terminal`] 结果：只在首次遇到 `Terminal=true` 的条目时探测。
+fn system_terminal() -> Option<&'static Terminal> {
+    static TERMINAL: OnceLock<Option<Terminal>> = OnceLock::new();
+    TERMINAL.get_or_init(detect_terminal).as_ref()
+}
+
+/// 启动器。
+pub struct Launcher {
+    spawner: Box<dyn ProcessSpawner>,
+    /// `Terminal=true` 的条目用它启动；未设置时按需探测，None 表示没有可用终端
+    terminal: OnceLock<Option<Terminal>>,
+}
+
+impl Launcher {
+    /// 指定 spawner 与终端（None 表示没有可用终端）的启动器。
+    #[allow(dead_code)] // 目前只有测试注入 spawner
+    pub fn new(spawner: Box<dyn ProcessSpawner>, terminal: Option<Terminal>) -> Self {
+        Self { spawner, terminal: OnceLock::from(terminal) }
+    }
+
+    /// 真正启动进程的启动器；终端在需要时才探测。
+    pub fn system() -> Self {
+        Self { spawner: Box::new(SystemSpawner), terminal: OnceLock::new() }
+    }
+
+    fn terminal(&self) -> Option<&Terminal> {
+        self.terminal.get_or_init(|| system_terminal().cloned()).as_ref()
+    }
+
+    /// 启动索引中的条目（`.desktop`、程序、脚本、目录、文档……）。
+    pub fn launch_path(&self, path: &Path) -> Result<()> {
+        let kind = LaunchKind::of(path)
+            .with_context(|| format!("路径不存在: {}", path.display()))?;
+        let command = self.command_for(path, kind)?;
+        self.spawn(&command)
+    }
+
+    /// 按用户条目保存的命令、参数、工作目录与环境变量启动。
+    pub fn launch_user_entry(&self, entry: &UserEntry) -> Result<()> {
+        let command = self.command_for_user_entry(entry)?;
+        self.spawn(&command)
+    }
+
+    fn spawn(&self, command: &LaunchCommand) -> Result<()> {
+        debug!(program = %command.program, args = ?command.args, "启动进程");
+        self.spawner.spawn(command)
+    }
+
+    fn command_for(&self, path: &Path, kind: LaunchKind) -> Result<LaunchCommand> {
+        let path_str = path.to_string_lossy().into_owned();
+        let in_parent = |command: LaunchCommand| match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => command.working_dir(dir),
+            _ => command,
+        };
+        Ok(match kind {
+            LaunchKind::DesktopEntry => self.desktop_command(path)?,
+            LaunchKind::AppImage => {
+                if !fs::metadata(path).map(|m| is_executable(&m)).unwrap_or(false) {
+                    bail!("AppImage 没有执行权限（chmod +x）: {}", path.display());
+                }
+                in_parent(LaunchCommand::new(path_str))
+            }
+            LaunchKind::Script => in_parent(script_command(path)),
+            LaunchKind::Executable => in_parent(LaunchCommand::new(path_str)),
+            LaunchKind::Directory | LaunchKind::Document => system_open(&path_str),
+        })
+    }
+
+    /// `.desktop`：展开 `Exec`，按需包进终端；`Type=Link` 打开 `URL`。
+    fn desktop_command(&self, path: &Path) -> Result<LaunchCommand> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("读取 desktop 文件失败: {}", path.display()))?;
+        let entry = parse_desktop_entry(&content);
+        match entry.get("Type").map(str::trim) {
+            Some("Application") => {}
+            Some("Link") => {
+                let url = entry.get("URL").map(str::trim).filter(|u| !u.is_empty());
+                let url = url.with_context(|| format!("Link 缺少 URL: {}", path.display()))?;
+                return Ok(system_open(url));
+            }
+            other => bail!("不支持的 desktop 类型 {:?}: {}", other, path.display()),
+        }
+
+        let exec = entry
+            .get("Exec")
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .with_context(|| format!("缺少 Exec: {}", path.display()))?;
+        let ctx = ExecContext {
+            icon: entry.get("Icon").map(str::trim),
+            name: entry.display_name().unwrap_or_default(),
+            desktop_file: path,
+        };
+        let mut argv = expand_exec(exec, &ctx)?.into_iter();
+        let program = argv.next().unwrap_or_default();
+        let mut command = LaunchCommand::new(program).args(argv);
+
+        if entry.get_bool("Terminal") {
+            let Some(terminal) = self.terminal() else {
+                bail!("没有可用的终端，无法启动: {}", path.display());
+            };
+            let inner = std::mem::take(&mut command);
+            command = LaunchCommand::new(terminal.program.clone())
+                .args(terminal.exec_args.iter().cloned())
+                .arg(inner.program)
+                .args(inner.args);
+        }
+        if let Some(dir) = entry.get("Path").map(str::trim).filter(|d| !d.is_empty()) {
+            command = command.working_dir(dir);
+        }
+        Ok(command)
+    }
+
+    /// 用户条目：网址交给系统默认程序；含路径分隔符的命令视为路径（相对路径按条目的
+    /// 工作目录解析），已存在时按类型启动并附加参数；不含分隔符的命令名只在 `PATH` 中查找，
+    /// 不会被工作目录下的同名文件或目录截获。
+    fn command_for_user_entry(&self, entry: &UserEntry) -> Result<LaunchCommand> {
+        let target = expand_home(&entry.command);
+        let working_dir = entry.working_dir.as_deref().map(|d| PathBuf::from(expand_home(d)));
+        let args = entry.args.iter().map(|arg| expand_home(arg));
+
+        let mut command = if target.contains("://") {
+            system_open(&target)
+        } else if target.contains(['/', '\\']) {
+            let path = match &working_dir {
+                Some(dir) => dir.join(&target),
+                None => PathBuf::from(&target),
+            };
+            match LaunchKind::of(&path) {
+                Some(kind) if kind.takes_args() => self.command_for(&path, kind)?.args(args),
+                Some(kind) => self.command_for(&path, kind)?,
+                None => LaunchCommand::new(target).args(args),
+            }
+        } else {
+            match find_executable(&target) {
+                // PATH 中的 `.cmd`/`.bat`/`.ps1` 需经解释器启动
+                Some(path) if LaunchKind::of(&path) == Some(LaunchKind::Script) => {
+                    script_command(&path).args(args)
+                }
+                Some(path) => LaunchCommand::new(path.to_string_lossy()).args(args),
+                None => LaunchCommand::new(target).args(args),
+            }
+        };
+        if working_dir.is_some() {
+            command.working_dir = working_dir;
+        }
+        command.env.extend(entry.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(command)
+    }
+}
+
+/// 按扩展名选择脚本的运行方式。
+fn script_command(path: &Path) -> LaunchCommand {
+    let path_str = path.to_string_lossy().into_owned();
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match ext.as_str() {
+        "bat" | "cmd" => LaunchCommand::new("cmd").args(["/C", path_str.as_str()]),
+        "ps1" => LaunchCommand::new("powershell").args([
+            "-NoProfile",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-File",
+            path_str.as_str(),
+        ]),
+        _ if fs::metadata(path).map(|m| is_executable(&m)).unwrap_or(false) => {
+            LaunchCommand::new(path_str)
+        }
+        _ => match read_shebang(path) {
+            Some(mut argv) => {
+                let program = argv.remove(0);
+                LaunchCommand::new(program).args(argv).arg(path_str)
+            }
+            None => {
+                let shell = match ext.as_str() {
+                    "bash" | "zsh" => ext.as_str(),
+                    _ => "sh",
+                };
+                LaunchCommand::new(shell).arg(path_str)
+            }
+        },
+    }
+}
+
+/// 读取首行 `#!` 指定的解释器及其参数（如 `/usr/bin/env bash`）。
+fn read_shebang(path: &Path) -> Option<Vec<String>> {
+    let mut head = [0u8; 256];
+    let n = fs::File::open(path).and_then(|mut f| f.read(&mut head)).ok()?;
+    let text = String::from_utf8_lossy(&head[..n]);
+    let line = text.strip_prefix("#!")?.lines().next()?;
+    let argv: Vec<String> = line.split_whitespace().map(String::from).collect();
+    (!argv.is_empty()).then_some(argv)
+}
+
+/// 文件头是 ELF 或 `#!`（可直接交给内核执行）。
+fn has_executable_header(path: &Path) -> bool {
+    let mut head = [0u8; 4];
+    let Ok(n) = fs::File::open(path).and_then(|mut f| f.read(&mut head)) else {
+        return false;
+    };
+    head[..n].starts_with(b"\x7fELF") || head[..n].starts_with(b"#!")
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.is_file() && meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &fs::Metadata) -> bool {
+    false
+}
+
+/// 用系统默认程序打开 `target`（目录、文档、网址）。
+fn system_open(target: &str) -> LaunchCommand {
+    let opener = if cfg!(windows) {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    LaunchCommand::new(opener).arg(target)
+}
+
+/// 把开头的 `~`（单独或后接路径分隔符）展开为用户主目录；取不到主目录时原样返回。
+fn expand_home(text: &str) -> String {
+    let rest = match text.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return text.to_string(),
+    };
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match home {
+        Some(home) => format!("{}{}", home.to_string_lossy(), rest),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+
+    /// 只记录命令、不真正启动的 spawner。
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<LaunchCommand>>>);
+
+    impl ProcessSpawner for Recorder {
+        fn spawn(&self, command: &LaunchCommand) -> Result<()> {
+            self.0.lock().unwrap().push(command.clone());
+            Ok(())
+        }
+    }
+
+    impl Recorder {
+        fn last(&self) -> LaunchCommand {
+            self.0.lock().unwrap().last().cloned().unwrap()
+        }
+    }
+
+    /// 测试用临时目录，离开作用域时删除。
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(tag: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "sparknova_launcher_test_{}_{:?}",
+                tag,
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos()
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn launcher(terminal: Option<Terminal>) -> (Launcher, Recorder) {
+        let recorder = Recorder::default();
+        (Launcher::new(Box::new(recorder.clone()), terminal), recorder)
+    }
+
+    fn xterm() -> Option<Terminal> {
+        Some(Terminal { program: "xterm".into(), exec_args: vec!["-e".into()] })
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn user_entry(command: &str, args: &[&str], working_dir: &Path) -> UserEntry {
+        UserEntry {
+            id: 1,
+            name: "Entry".into(),
+            command: command.into(),
+            args: strings(args),
+            working_dir: Some(working_dir.to_string_lossy().into_owned()),
+            env: BTreeMap::from([("MODE".to_string(), "dev".to_string())]),
+            icon: None,
+            keywords: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn windows_scripts_use_their_interpreter() {
+        let bat = Path::new("tools").join("build.BAT");
+        let bat_str = bat.to_string_lossy().into_owned();
+        assert_eq!(script_command(&bat), LaunchCommand::new("cmd").args(["/C", &bat_str]));
+
+        let ps1 = Path::new("tools").join("setup.ps1");
+        let ps1_str = ps1.to_string_lossy().into_owned();
+        assert_eq!(
+            script_command(&ps1),
+            LaunchCommand::new("powershell").args([
+                "-NoProfile",
+                "-ExecutionPolicy",
+                "Bypass",
+                "-File",
+                &ps1_str,
+            ])
+        );
+    }
+
+    #[test]
+    fn system_open_uses_platform_opener() {
+        let expected = if cfg!(windows) {
+            "explorer"
+        } else if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        assert_eq!(
+            system_open("https://example.com"),
+            LaunchCommand::new(expected).arg("https://example.com")
+        );
+    }
+
+    #[test]
+    fn desktop_entries_expand_exec_and_use_terminal() {
+        let dir = TempDir::new("desktop");
+        let file = dir.path().join("top.desktop");
+        fs::write(
+            &file,
+            "[Desktop Entry]\nType=Application\nName=Top\nName[zh_CN]=进程\nIcon=top\n\
+             Exec=\"/opt/My Tools/top\" --title=%c %i %U\nPath=/srv\nTerminal=true\n",
+        )
+        .unwrap();
+
+        let (l, rec) = launcher(xterm());
+        l.launch_path(&file).unwrap();
+        let cmd = rec.last();
+        assert_eq!(cmd.program, "xterm");
+        assert_eq!(
+            cmd.args,
+            strings(&["-e", "/opt/My Tools/top", "--title=进程", "--icon", "top"])
+        );
+        assert_eq!(cmd.working_dir, Some(PathBuf::from("/srv")));
+
+        // 没有终端时 Terminal=true 的条目报错，不静默后台运行
+        let (l, _) = launcher(None);
+        assert!(l.launch_path(&file).is_err());
+
+        let link = dir.path().join("site.desktop");
+        fs::write(&link, "[Desktop Entry]\nType=Link\nName=Site\nURL=https://example.com\n")
+            .unwrap();
+        let (l, rec) = launcher(None);
+        l.launch_path(&link).unwrap();
+        assert_eq!(rec.last(), system_open("https://example.com"));
+    }
+
+    #[test]
+    fn user_entry_urls_and_missing_paths() {
+        let dir = TempDir::new("user_url");
+        let (l, rec) = launcher(None);
+
+        // 网址交给系统默认程序，忽略参数
+        let url = user_entry("https://example.com/docs", &["x"], dir.path());
+        l.launch_user_entry(&url).unwrap();
+        let cmd = rec.last();
+        assert_eq!(cmd.program, system_open("").program);
+        assert_eq!(cmd.args, strings(&["https://example.com/docs"]));
+        assert_eq!(cmd.env, vec![("MODE".to_string(), "dev".to_string())]);
+
+        // 裸文件名不按工作目录解析
+        fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        l.launch_user_entry(&user_entry("notes.txt", &[], dir.path())).unwrap();
+        assert_eq!(rec.last().program, "notes.txt");
+        assert_eq!(rec.last().working_dir.as_deref(), Some(dir.path()));
+    }
+
+    #[test]
+    fn home_is_expanded_only_at_start() {
+        assert_eq!(expand_home("~user/x"), "~user/x");
+        assert_eq!(expand_home("a/~/b"), "a/~/b");
+    }
+
+    /// 执行位与 ELF 文件头只在 Unix 上有意义。
+    #[cfg(unix)]
+    mod unix {
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+
+        fn write(path: &Path, content: &[u8], mode: u32) -> String {
+            fs::write(path, content).unwrap();
+            fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+            path.to_string_lossy().into_owned()
+        }
+
+        #[test]
+        fn files_launch_by_kind() {
+            let tmp = TempDir::new("kinds");
+            let dir = tmp.path();
+            let (l, rec) = launcher(None);
+            let launch = |path: &str| -> LaunchCommand {
+                l.launch_path(Path::new(path)).unwrap();
+                rec.last()
+            };
+            let dir_str = dir.to_string_lossy().into_owned();
+
+            let binary = write(&dir.join("tool"), b"\x7fELF\x02\x01", 0o755);
+            assert_eq!(launch(&binary), LaunchCommand::new(&binary).working_dir(dir));
+
+            let image = write(&dir.join("App.AppImage"), b"\x7fELF", 0o755);
+            assert_eq!(launch(&image).program, image);
+            let locked = write(&dir.join("Locked.AppImage"), b"\x7fELF", 0o644);
+            assert!(l.launch_path(Path::new(&locked)).is_err());
+
+            let runnable = write(&dir.join("run.sh"), b"#!/bin/sh\necho hi\n", 0o755);
+            assert_eq!(launch(&runnable).program, runnable);
+            let env_script = write(&dir.join("build.sh"), b"#!/usr/bin/env bash\nmake\n", 0o644);
+            assert_eq!(
+                launch(&env_script),
+                LaunchCommand::new("/usr/bin/env").args(["bash", &env_script]).working_dir(dir)
+            );
+            let plain = write(&dir.join("plain.sh"), b"echo hi\n", 0o644);
+            assert_eq!(launch(&plain).program, "sh");
+            assert_eq!(launch(&plain).args, vec![plain.clone()]);
+
+            // 有执行位但不是程序（如 NTFS 上的文档）按文档打开
+            let doc = write(&dir.join("report.pdf"), b"%PDF-1.7", 0o755);
+            assert_eq!(launch(&doc), system_open(&doc));
+            assert_eq!(launch(&dir_str), system_open(&dir_str));
+
+            assert!(l.launch_path(&dir.join("missing")).is_err());
+        }
+
+        #[test]
+        fn user_entries_apply_args_dir_and_env() {
+            let tmp = TempDir::new("user");
+            let dir = tmp.path();
+            let (l, rec) = launcher(None);
+
+            // 不含分隔符的命令名只在 PATH 中查找：工作目录下同名的目录不会被打开
+            fs::create_dir(dir.join("sh")).unwrap();
+            l.launch_user_entry(&user_entry("sh", &["-c", "echo 'a b'"], dir)).unwrap();
+            let cmd = rec.last();
+            assert!(cmd.program.ends_with("/sh"), "{}", cmd.program);
+            assert_eq!(cmd.args, strings(&["-c", "echo 'a b'"]));
+            assert_eq!(cmd.working_dir.as_deref(), Some(dir));
+            assert_eq!(cmd.env, vec![("MODE".to_string(), "dev".to_string())]);
+
+            // 含分隔符的相对路径按工作目录解析，脚本附加参数
+            let script = write(&dir.join("deploy.sh"), b"#!/bin/sh\n", 0o755);
+            l.launch_user_entry(&user_entry("./deploy.sh", &["--prod"], dir)).unwrap();
+            assert_eq!(Path::new(&rec.last().program), Path::new(&script));
+            assert_eq!(rec.last().args, strings(&["--prod"]));
+        }
+    }
+}
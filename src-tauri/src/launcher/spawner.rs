@@ -0,0 +1,71 @@
+//! 进程启动抽象：启动层只产出 [`LaunchCommand`]，真正的 spawn 交给 [`ProcessSpawner`]，
+//! 测试中替换为记录命令的实现即可验证各类条目的启动方式。
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// 待启动的进程。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchCommand {
+    /// 程序路径或命令名（命令名由系统在 `PATH` 中查找）
+    pub program: String,
+    /// 参数，逐个原样传给进程（不经 shell 拆分）
+    pub args: Vec<String>,
+    /// 工作目录；None 时沿用启动器的工作目录
+    pub working_dir: Option<PathBuf>,
+    /// 额外的环境变量
+    pub env: Vec<(String, String)>,
+}
+
+impl LaunchCommand {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+}
+
+/// 进程启动器。
+pub trait ProcessSpawner: Send + Sync {
+    /// 启动进程，不等待其退出。
+    fn spawn(&self, command: &LaunchCommand) -> Result<()>;
+}
+
+/// 用 [`std::process::Command`] 真正启动进程。
+pub struct SystemSpawner;
+
+impl ProcessSpawner for SystemSpawner {
+    fn spawn(&self, command: &LaunchCommand) -> Result<()> {
+        let mut cmd = Command::new(&command.program);
+        cmd.args(&command.args);
+        if let Some(dir) = &command.working_dir {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(command.env.iter().map(|(k, v)| (k, v)));
+        cmd.spawn()
+            .with_context(|| format!("启动进程失败: {}", command.program))?;
+        Ok(())
+    }
+}
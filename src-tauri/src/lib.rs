@@ -34,14 +34,22 @@ mod window;
 mod commands;
 // Re-export Tauri commands
 pub use commands::activate::activate;
+pub use commands::aliases::{list_aliases, add_alias, remove_alias, set_aliases};
 pub use commands::config::{get_config, save_config};
+pub use commands::hidden::{list_hidden, hide_entry, hide_pattern, unhide};
+pub use commands::home::{home, pin_entry, unpin_entry, reorder_pins};
 pub use commands::notes::{list_notes, create_note, delete_note};
-pub use commands::search::{query, hide_window, SearchState};
+pub use commands::search::{query, explain_query, hide_window, SearchState};
+pub use commands::user_entries::{
+    list_user_entries, create_user_entry, update_user_entry, delete_user_entry,
+};
 
 mod paths;
 mod domain;
 mod search;
+mod provider;
 mod indexer;
+mod launcher;
 mod storage;
 mod config;
 mod tray;
@@ -60,8 +68,26 @@ pub fn run() {
         .plugin(tauri_plugin_opener::Builder::default().build())
         .invoke_handler(tauri::generate_handler![
             open_or_focus_main_window,
+            commands::activate::activate,
             commands::search::query,
-            commands::search::hide_window
+            commands::search::explain_query,
+            commands::search::hide_window,
+            commands::home::home,
+            commands::home::pin_entry,
+            commands::home::unpin_entry,
+            commands::home::reorder_pins,
+            commands::aliases::list_aliases,
+            commands::aliases::add_alias,
+            commands::aliases::remove_alias,
+            commands::aliases::set_aliases,
+            commands::hidden::list_hidden,
+            commands::hidden::hide_entry,
+            commands::hidden::hide_pattern,
+            commands::hidden::unhide,
+            commands::user_entries::list_user_entries,
+            commands::user_entries::create_user_entry,
+            commands::user_entries::update_user_entry,
+            commands::user_entries::delete_user_entry
         ])
         .setup(|app: &mut App| {
             let _ = fmt()
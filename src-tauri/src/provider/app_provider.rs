@@ -0,0 +1,147 @@
+//! 应用结果提供者：搜索引擎文本匹配 + 使用频率 / 选择学习重排。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use anyhow::Result;
+
+use super::result_provider::ResultProvider;
+use crate::domain::{AppEntry, ResultAction, ResultKind, SearchResult};
+use crate::search::{ParsedQuery, QueryScope, ScoringProfile, SearchEngine};
+use crate::storage::Selections;
+
+/// 参与频率重排的候选数：文本排名稍靠后但常用的应用也有机会进入最终结果。
+pub const RERANK_POOL: usize = 32;
+
+/// 应用提供者。共享 `SearchState` 的引擎与缓存，引擎加载完成前返回空结果。
+pub struct AppProvider {
+    engine: Arc<RwLock<Option<Arc<SearchEngine>>>>,
+    /// 应用路径 → 最近的启动时间戳（秒，升序）
+    frequency_cache: Arc<RwLock<HashMap<String, Vec<u64>>>>,
+    /// query 前缀 → 该前缀下选过的 (应用路径, 次数)
+    selection_cache: Arc<RwLock<HashMap<String, Selections>>>,
+    /// 使用频率衰减的半衰期（秒）
+    half_life: Arc<AtomicU64>,
+}
+
+impl AppProvider {
+    pub fn new(
+        engine: Arc<RwLock<Option<Arc<SearchEngine>>>>,
+        frequency_cache: Arc<RwLock<HashMap<String, Vec<u64>>>>,
+        selection_cache: Arc<RwLock<HashMap<String, Selections>>>,
+        half_life: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            engine,
+            frequency_cache,
+            selection_cache,
+            half_life,
+        }
+    }
+}
+
+impl ResultProvider for AppProvider {
+    fn name(&self) -> &'static str {
+        "apps"
+    }
+
+    fn scopes(&self) -> &'static [QueryScope] {
+        &[QueryScope::App]
+    }
+
+    /// 默认配置下名称命中的基础分：只有拼音模糊、序列比对等弱匹配时不会被放大成满分。
+    fn score_floor(&self) -> f64 {
+        let profile = ScoringProfile::default();
+        (profile.base_score + profile.name_match_bonus) as f64
+    }
+
+    /// 匹配 `query.text`；`query.sources` 非空时只返回这些来源的应用。
+    fn query(&self, query: &ParsedQuery, limit: usize) -> Result<Vec<SearchResult>> {
+        let engine = self.engine.read().unwrap();
+        let Some(ref eng) = *engine else {
+            return Ok(Vec::new());
+        };
+        let freq_cache = self.frequency_cache.read().unwrap();
+        let selection_cache = self.selection_cache.read().unwrap();
+
+        let hits = eng.search_hits_from(&query.text, RERANK_POOL.max(limit), &query.sources);
+        let learned = crate::search::learned_selections(&selection_cache, &query.text);
+        let ranked = crate::search::rerank_explained(
+            &hits,
+            &freq_cache,
+            learned,
+            crate::storage::IndexCache::current_timestamp(),
+            self.half_life.load(Ordering::Relaxed),
+        );
+
+        Ok(ranked
+            .into_iter()
+            .take(limit)
+            .map(|(i, boost)| {
+                let hit = &hits[i];
+                app_result(&hit.entry, boost.final_score, hit.highlights.clone())
+            })
+            .collect())
+    }
+}
+
+/// 把应用条目包装成统一的搜索结果（标识与副标题为路径，默认动作为打开）。
+pub fn app_result(entry: &AppEntry, score: f64, highlights: Vec<(usize, usize)>) -> SearchResult {
+    let path = entry.path.to_string_lossy().into_owned();
+    SearchResult {
+        id: path.clone(),
+        title: entry.name.clone(),
+        subtitle: path,
+        icon: entry.icon.clone(),
+        kind: ResultKind::App,
+        score,
+        actions: vec![ResultAction {
+            id: "open".into(),
+            label: "打开".into(),
+        }],
+        highlights,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AppSource;
+    use std::path::PathBuf;
+
+    fn app(name: &str, path: &str) -> AppEntry {
+        AppEntry {
+            name: name.into(),
+            path: PathBuf::from(path),
+            source: AppSource::StartMenu,
+            icon: None,
+            keywords: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn apps_are_reranked_by_usage() {
+        let engine = SearchEngine::new(vec![app("Chrome", "/chrome"), app("Calculator", "/calc")]);
+        let engine = Arc::new(RwLock::new(Some(Arc::new(engine))));
+        let selections = HashMap::from([("c".to_string(), vec![("/calc".to_string(), 3)])]);
+        let provider = AppProvider::new(
+            engine.clone(),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(selections)),
+            Arc::new(AtomicU64::new(crate::search::DEFAULT_HALF_LIFE_SECS)),
+        );
+
+        let parse = |q: &str| crate::search::QueryParser::default().parse(q);
+        let results = provider.query(&parse("app:c"), 8).unwrap();
+        assert_eq!(results[0].id, "/calc");
+        assert_eq!(results[0].kind, ResultKind::App);
+        assert_eq!(results[0].highlights, vec![(0, 1)]);
+        assert_eq!(provider.query(&parse("c"), 1).unwrap().len(), 1);
+        assert!(provider.query(&parse("c source:dir"), 8).unwrap().is_empty());
+
+        // 引擎尚未加载
+        *engine.write().unwrap() = None;
+        assert!(provider.query(&parse("c"), 8).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! 空 query 首页：固定的应用、最近使用、常用三栏。
+//!
+//! 搜索引擎对空 query 不返回结果，打开启动器时改由 `home` 命令展示这三栏。
+//! 每个应用只出现在一栏（固定 > 最近 > 常用）；已不在索引中或被隐藏的应用跳过，
+//! 固定记录本身保留，应用重新安装或取消隐藏后恢复显示。
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::app_provider::app_result;
+use crate::domain::{AppEntry, SearchResult};
+use crate::search::SearchEngine;
+
+/// 首页各栏的结果。
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HomeSections {
+    /// 用户固定的应用，按用户排定的顺序，不受条数限制
+    pub pinned: Vec<SearchResult>,
+    /// 最近启动的应用，越近越靠前（score 为最近一次启动的时间戳）
+    pub recent: Vec<SearchResult>,
+    /// frecency 最高的应用（score 为 frecency）
+    pub frequent: Vec<SearchResult>,
+}
+
+/// 组装首页三栏；`recent` / `frequent` 各最多 `limit` 条。
+///
+/// * `pins` — 固定的应用路径（按显示顺序）
+/// * `launches` — 应用路径 → 启动时间戳
+/// * `now` / `half_life_secs` — 见 [`crate::search::most_frecent`]
+pub fn home_sections(
+    engine: &SearchEngine,
+    pins: &[String],
+    launches: &HashMap<String, Vec<u64>>,
+    now: u64,
+    half_life_secs: u64,
+    limit: usize,
+) -> HomeSections {
+    let pinned = pins
+        .iter()
+        .enumerate()
+        .map(|(i, path)| (path.as_str(), (pins.len() - i) as f64))
+        .collect();
+    let recent = crate::search::most_recent(launches)
+        .into_iter()
+        .map(|(path, at)| (path, at as f64))
+        .collect();
+    let frequent = crate::search::most_frecent(launches, now, half_life_secs);
+
+    let paths: Vec<&Path> = pins
+        .iter()
+        .map(String::as_str)
+        .chain(launches.keys().map(String::as_str))
+        .map(Path::new)
+        .collect();
+    let entries = engine.get_visible(&paths);
+
+    let mut shown = HashSet::new();
+    HomeSections {
+        pinned: resolve(&entries, &mut shown, pinned, usize::MAX),
+        recent: resolve(&entries, &mut shown, recent, limit),
+        frequent: resolve(&entries, &mut shown, frequent, limit),
+    }
+}
+
+/// 按顺序取出 `entries`（索引中存在且未被隐藏的应用）里尚未在前面的栏出现过的应用，
+/// 最多 `limit` 条。
+fn resolve<'a>(
+    entries: &HashMap<&Path, AppEntry>,
+    shown: &mut HashSet<&'a str>,
+    candidates: Vec<(&'a str, f64)>,
+    limit: usize,
+) -> Vec<SearchResult> {
+    candidates
+        .into_iter()
+        .filter_map(|(path, score)| {
+            if shown.contains(path) {
+                return None;
+            }
+            let entry = entries.get(Path::new(path))?;
+            shown.insert(path);
+            Some(app_result(entry, score, Vec::new()))
+        })
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AppSource;
+    use std::path::PathBuf;
+
+    const DAY: u64 = 24 * 3600;
+    const NOW: u64 = 1_000 * DAY;
+
+    fn app(name: &str) -> AppEntry {
+        AppEntry {
+            name: name.into(),
+            path: PathBuf::from(format!("/{}", name)),
+            source: AppSource::StartMenu,
+            icon: None,
+            keywords: Vec::new(),
+        }
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[test]
+    fn sections_do_not_repeat_entries() {
+        let engine = SearchEngine::new(["a", "b", "c", "d", "e"].map(app).to_vec());
+        let pins = vec!["/c".to_string(), "/gone".to_string(), "/a".to_string()];
+        let launches = HashMap::from([
+            ("/a".to_string(), vec![NOW]),
+            ("/b".to_string(), vec![NOW - 2 * DAY; 10]),
+            ("/d".to_string(), vec![NOW - DAY]),
+            ("/e".to_string(), vec![NOW - 5 * DAY; 3]),
+            ("/uninstalled".to_string(), vec![NOW - 60]),
+        ]);
+
+        let home = home_sections(&engine, &pins, &launches, NOW, 14 * DAY, 2);
+        assert_eq!(ids(&home.pinned), vec!["/c", "/a"]);
+        assert_eq!(ids(&home.recent), vec!["/d", "/b"]);
+        assert_eq!(ids(&home.frequent), vec!["/e"]);
+        assert_eq!(home.pinned[0].title, "c");
+    }
+}
@@ -0,0 +1,16 @@
+//! 结果提供者层：把各类可搜索对象（应用、笔记、计算器、文件……）统一成
+//! [`crate::domain::SearchResult`]，由 [`ResultPipeline`] 合并后交给 `query` 命令。
+//!
+//! - `result_provider` —— [`ResultProvider`] trait
+//! - `pipeline`        —— 注册表、配额与按归一化分数的交错合并
+//! - `app_provider`    —— 应用搜索（搜索引擎 + 频率重排），第一个提供者
+//! - `home`            —— 空 query 首页：固定、最近使用、常用
+
+mod app_provider;
+mod home;
+mod pipeline;
+mod result_provider;
+
+pub use app_provider::{AppProvider, RERANK_POOL};
+pub use home::{home_sections, HomeSections};
+pub use pipeline::ResultPipeline;
@@ -0,0 +1,270 @@
+//! 结果管线：按注册顺序查询所有 [`ResultProvider`]，按配额截断后按归一化分数交错合并。
+//!
+//! query 限定了范围（`app:`、`note:` 等）时只查询负责该范围的提供者。
+//! 单个提供者失败或 panic 只记录日志，不影响其他提供者。
+//!
+//! 归一化：各提供者的分数量纲不同，合并前除以 `max(该批最高分, 提供者分数下限)`
+//! 再乘以提供者权重，映射到 `[0, weight]`；只有弱结果的批次因此不会与别家的最好结果并列。
+//! 交错合并时每次取各提供者队首中归一化分数最高的一条（同分取先注册的），
+//! 因此同一提供者内部的顺序保持不变。
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use tracing::{debug, warn};
+
+use super::result_provider::ResultProvider;
+use crate::domain::SearchResult;
+use crate::search::ParsedQuery;
+
+/// 已注册的提供者及其配额。
+struct Registered {
+    provider: Box<dyn ResultProvider>,
+    /// 每次查询最多贡献几条结果
+    quota: usize,
+}
+
+/// 一个提供者本次查询的结果。
+pub struct Batch {
+    pub results: Vec<SearchResult>,
+    /// 最多取前几条
+    pub quota: usize,
+    /// 归一化后的最高分（见 [`ResultProvider::weight`]）
+    pub weight: f64,
+    /// 归一化的分母下限（见 [`ResultProvider::score_floor`]）
+    pub floor: f64,
+}
+
+/// 结果提供者注册表。
+#[derive(Default)]
+pub struct ResultPipeline {
+    providers: Vec<Registered>,
+}
+
+impl ResultPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册一个提供者；`quota` 为它每次查询最多贡献的结果数。
+    pub fn register(&mut self, provider: Box<dyn ResultProvider>, quota: usize) {
+        self.providers.push(Registered { provider, quota });
+    }
+
+    /// 查询范围内的所有提供者并合并，返回前 `limit` 条。
+    pub fn query(&self, query: &ParsedQuery, limit: usize) -> Vec<SearchResult> {
+        let batches = self
+            .providers
+            .iter()
+            .filter(|r| query.targets(r.provider.scopes()))
+            .filter_map(|r| {
+                let quota = r.quota.min(limit);
+                let results = run_provider(r.provider.as_ref(), query, quota)?;
+                Some(Batch {
+                    results,
+                    quota,
+                    weight: r.provider.weight(),
+                    floor: r.provider.score_floor(),
+                })
+            })
+            .collect();
+        merge(batches, limit)
+    }
+}
+
+/// 执行单个提供者；catch_unwind 防止单个提供者 panic 导致整次查询失败。
+fn run_provider(
+    provider: &dyn ResultProvider,
+    query: &ParsedQuery,
+    limit: usize,
+) -> Option<Vec<SearchResult>> {
+    let name = provider.name();
+    match catch_unwind(AssertUnwindSafe(|| provider.query(query, limit))) {
+        Ok(Ok(results)) => {
+            debug!(provider = name, count = results.len(), "提供者查询完成");
+            Some(results)
+        }
+        Ok(Err(e)) => {
+            warn!(provider = name, error = ?e, "提供者查询失败");
+            None
+        }
+        Err(e) => {
+            warn!(provider = name, error = ?e, "提供者查询 panic");
+            None
+        }
+    }
+}
+
+/// 按配额截断各批结果，再按归一化分数交错合并为前 `limit` 条。
+pub fn merge(batches: Vec<Batch>, limit: usize) -> Vec<SearchResult> {
+    let mut queues: Vec<(f64, std::vec::IntoIter<SearchResult>)> = batches
+        .into_iter()
+        .map(|batch| {
+            let mut results = batch.results;
+            results.truncate(batch.quota);
+            let max = results.iter().map(|r| r.score).fold(batch.floor, f64::max);
+            let scale = if max > 0.0 { batch.weight / max } else { 0.0 };
+            (scale, results.into_iter())
+        })
+        .collect();
+
+    let mut merged = Vec::new();
+    while merged.len() < limit {
+        let best = queues
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (scale, queue))| {
+                queue.as_slice().first().map(|r| (i, r.score * scale))
+            })
+            .fold(None, |best: Option<(usize, f64)>, (i, score)| match best {
+                Some((_, b)) if b >= score => best,
+                _ => Some((i, score)),
+            });
+        let Some((i, _)) = best else {
+            break;
+        };
+        merged.extend(queues[i].1.next());
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ResultKind;
+    use crate::search::{QueryParser, QueryScope};
+
+    fn result(id: &str, score: f64) -> SearchResult {
+        SearchResult {
+            id: id.into(),
+            title: id.into(),
+            subtitle: String::new(),
+            icon: None,
+            kind: ResultKind::App,
+            score,
+            actions: Vec::new(),
+            highlights: Vec::new(),
+        }
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    struct FakeProvider {
+        results: Vec<(&'static str, f64)>,
+        fail: bool,
+        scopes: &'static [QueryScope],
+    }
+
+    impl FakeProvider {
+        fn new(results: Vec<(&'static str, f64)>) -> Self {
+            Self { results, fail: false, scopes: &[QueryScope::App] }
+        }
+    }
+
+    impl ResultProvider for FakeProvider {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn scopes(&self) -> &'static [QueryScope] {
+            self.scopes
+        }
+
+        fn query(&self, _query: &ParsedQuery, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+            if self.fail {
+                anyhow::bail!("boom");
+            }
+            Ok(self.results.iter().take(limit).map(|&(id, s)| result(id, s)).collect())
+        }
+    }
+
+    #[test]
+    fn merge_interleaves_by_normalized_score() {
+        // 应用分数在千量级、笔记在 0–1：归一化后交错
+        let apps = Batch {
+            results: vec![result("a1", 1000.0), result("a2", 400.0), result("a3", 100.0)],
+            quota: 8,
+            weight: 1.0,
+            floor: 0.0,
+        };
+        let notes = Batch {
+            results: vec![result("n1", 0.8), result("n2", 0.6)],
+            quota: 8,
+            weight: 1.0,
+            floor: 0.0,
+        };
+        // a1=1.0 n1=1.0 n2=0.75 a2=0.4 a3=0.1；同分取先注册的
+        assert_eq!(ids(&merge(vec![apps, notes], 10)), vec!["a1", "n1", "n2", "a2", "a3"]);
+    }
+
+    #[test]
+    fn merge_respects_quota_weight_and_limit() {
+        let apps = Batch {
+            results: vec![result("a1", 10.0), result("a2", 9.0), result("a3", 8.0)],
+            quota: 2,
+            weight: 1.0,
+            floor: 0.0,
+        };
+        let files = Batch {
+            results: vec![result("f1", 5.0)],
+            quota: 8,
+            weight: 0.5,
+            floor: 0.0,
+        };
+        assert_eq!(ids(&merge(vec![apps, files], 10)), vec!["a1", "a2", "f1"]);
+
+        let apps = Batch {
+            results: vec![result("a1", 10.0), result("a2", 9.0)],
+            quota: 8,
+            weight: 1.0,
+            floor: 0.0,
+        };
+        assert_eq!(ids(&merge(vec![apps], 1)), vec!["a1"]);
+    }
+
+    #[test]
+    fn score_floor_keeps_weak_batches_weak() {
+        let apps = Batch {
+            results: vec![result("a1", 1000.0), result("a2", 500.0)],
+            quota: 8,
+            weight: 1.0,
+            floor: 1000.0,
+        };
+        // 唯一的结果很弱：按下限缩放为 0.1，而不是放大到 1.0 与 a1 并列
+        let weak = Batch {
+            results: vec![result("w1", 100.0)],
+            quota: 8,
+            weight: 1.0,
+            floor: 1000.0,
+        };
+        assert_eq!(ids(&merge(vec![weak, apps], 10)), vec!["a1", "a2", "w1"]);
+    }
+
+    #[test]
+    fn failed_provider_does_not_break_pipeline() {
+        let mut pipeline = ResultPipeline::new();
+        pipeline.register(Box::new(FakeProvider { fail: true, ..FakeProvider::new(vec![]) }), 8);
+        pipeline.register(Box::new(FakeProvider::new(vec![("a", 2.0), ("b", 1.0)])), 1);
+        let query = QueryParser::default().parse("q");
+        assert_eq!(ids(&pipeline.query(&query, 8)), vec!["a"]);
+    }
+
+    #[test]
+    fn scope_routes_to_matching_providers() {
+        let mut pipeline = ResultPipeline::new();
+        pipeline.register(Box::new(FakeProvider::new(vec![("app", 1.0)])), 8);
+        pipeline.register(
+            Box::new(FakeProvider {
+                scopes: &[QueryScope::Note, QueryScope::Todo],
+                ..FakeProvider::new(vec![("note", 1.0)])
+            }),
+            8,
+        );
+        let parser = QueryParser::default();
+        assert_eq!(ids(&pipeline.query(&parser.parse("x"), 8)), vec!["app", "note"]);
+        assert_eq!(ids(&pipeline.query(&parser.parse("todo: x"), 8)), vec!["note"]);
+        assert_eq!(ids(&pipeline.query(&parser.parse("app:x"), 8)), vec!["app"]);
+        assert!(pipeline.query(&parser.parse("file:x"), 8).is_empty());
+    }
+}
@@ -0,0 +1,34 @@
+//! 结果提供者抽象：应用、笔记、计算器、文件等都实现同一个 [`ResultProvider`] trait，
+//! 产出统一的 [`SearchResult`]。
+//!
+//! 新增提供者只需实现 trait 并在 [`super::ResultPipeline`] 中注册，
+//! 调用方（`commands::search`）无需改动。
+
+use anyhow::Result;
+
+use crate::domain::SearchResult;
+use crate::search::{ParsedQuery, QueryScope};
+
+/// 搜索结果提供者。
+pub trait ResultProvider: Send + Sync {
+    /// 提供者名（日志用），如 `"apps"`。
+    fn name(&self) -> &'static str;
+
+    /// 负责的搜索范围；query 限定了其他范围时不调用本提供者。
+    fn scopes(&self) -> &'static [QueryScope];
+
+    /// 返回按相关性降序排列的结果，最多 `limit` 条。匹配 `query.text`，
+    /// 并按提供者自身的语义应用过滤条件。
+    fn query(&self, query: &ParsedQuery, limit: usize) -> Result<Vec<SearchResult>>;
+
+    /// 合并时的权重：本提供者最好的结果归一化后的分数。默认 1.0。
+    fn weight(&self) -> f64 {
+        1.0
+    }
+
+    /// 归一化的分母下限：本批最高分低于它时按它缩放，只有弱结果的批次不会被放大到满分。
+    /// 默认 0（按本批最高分归一化）。
+    fn score_floor(&self) -> f64 {
+        0.0
+    }
+}
@@ -1,13 +1,43 @@
 //! 搜索引擎核心。v0.1 实现：自定义模糊匹配 + 拼音支持。
 //!
 //! 预计算 lowercase + 拼音字段 + 字符向量，支持中英文混合搜索。
+//! 名称与 query 先经 [`normalize::fold`] 折叠（繁→简、全角→半角、去变音符号）再小写。
+//! 名称的拼音字段可由索引缓存提供（[`SearchEngine::with_pinyin`]），冷启动时免去逐条转换；
+//! 关键词与别名的拼音有意不缓存，见 `term_forms`。
+//!
+//! 打分前先用字符位图剪枝（[`may_match`]）：每个条目预存名称/全拼/首字母的位图，
+//! 与 query 位图做几次位运算即可排除不可能命中的条目。剪枝条件是各打分路径的必要条件，
+//! 结果与逐条打分完全一致。
+//!
+//! 启用双拼方案（[`SearchEngine::set_double_pinyin`]）后，英文 query 还会解码为候选全拼，
+//! 与条目的全拼变体匹配。启用模糊音（[`SearchEngine::set_fuzzy_pinyin`]）后，
+//! 条目拼音按规则预先归一化，精确拼音未命中时再用归一后的 query 匹配。
+//!
+//! 用户别名（[`SearchEngine::set_aliases`]，如 Photoshop → `ps`）连同别名的拼音一起匹配，
+//! query 与别名完全一致时给最高档 bonus，不受其他路径的模糊打分影响。
+//! 条目自带的关键词（`.desktop` 的 `Keywords` 与未翻译的 `Name`、用户条目的关键词）
+//! 同样连同拼音参与匹配，bonus 低于名称与别名，也没有完全一致的置顶档。
+//!
+//! 被隐藏规则（[`SearchEngine::set_hidden`]）命中的条目留在索引里，但不参与任何查询。
+//!
+//! 各路径的 bonus 与比对参数取自 [`ScoringProfile`]（[`SearchEngine::set_scoring_profile`]），
+//! 每次查询开始时快照一份，替换配置不需要重建索引。
 
-use std::path::Path;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::RwLock;
 
-use crate::domain::AppEntry;
+use crate::domain::{AppEntry, AppSource};
 use crate::search::matcher;
-use crate::search::pinyin::{to_initials, to_pinyin};
+use crate::search::normalize;
+use crate::search::pinyin::{
+    decode_double_pinyin, full_owners, fuzzy_normalize, initials_owners, to_pinyin,
+    DoublePinyinScheme, FuzzyRule, PinyinFields,
+};
+use crate::search::hidden::HiddenFilter;
+use crate::search::profile::ScoringProfile;
 
 /// 搜索引擎：持有内存索引，暴露 `search` API。
 ///
@@ -15,6 +45,26 @@ use crate::search::pinyin::{to_initials, to_pinyin};
 pub struct SearchEngine {
     /// (原始条目, 预计算的搜索字段)
     apps: RwLock<Vec<(AppEntry, SearchFields)>>,
+    /// 查询相关的用户设置（双拼、模糊音）
+    options: RwLock<SearchOptions>,
+    /// 用户别名：应用路径 → 别名（新增条目按它计算别名字段）
+    aliases: RwLock<HashMap<PathBuf, Vec<String>>>,
+    /// 隐藏规则（新增条目按它计算隐藏标记）
+    hidden: RwLock<HiddenFilter>,
+    /// 模糊音规则、别名或隐藏规则每替换一次加一（持 `apps` 写锁时递增），
+    /// 新增条目据此发现锁外算好的字段已过期
+    fields_generation: AtomicU64,
+}
+
+/// 查询相关的用户设置。
+#[derive(Debug, Clone, Default)]
+struct SearchOptions {
+    /// 双拼方案；None 表示不解码双拼
+    double_pinyin: Option<DoublePinyinScheme>,
+    /// 启用的模糊音规则；为空表示关闭模糊音
+    fuzzy_pinyin: Vec<FuzzyRule>,
+    /// 打分权重与阈值
+    profile: ScoringProfile,
 }
 
 /// 预计算的搜索字段。
@@ -23,39 +73,344 @@ struct SearchFields {
     lowercase_name: String,
     /// 小写名称的字符向量（避免搜索时重复分配）
     lowercase_chars: Vec<char>,
-    /// 拼音全拼的字符向量
-    pinyin_full_chars: Vec<char>,
-    /// 拼音首字母的字符向量
-    pinyin_initials_chars: Vec<char>,
+    /// 拼音全拼各读音变体的字符向量（多音字）
+    pinyin_full_chars: Vec<Vec<char>>,
+    /// 拼音首字母各读音变体的字符向量
+    pinyin_initials_chars: Vec<Vec<char>>,
+    /// 与 `lowercase_chars` 对齐的词首标记（按原始大小写计算，见 [`matcher::word_boundaries`]）
+    boundaries: Vec<bool>,
+    /// 按模糊音规则归一化的全拼 / 首字母变体（未启用模糊音时为空）
+    fuzzy_full_chars: Vec<Vec<char>>,
+    fuzzy_initials_chars: Vec<Vec<char>>,
+    /// 小写名称 / 全拼 / 首字母的字符位图（剪枝用；拼音为各变体的并集）
+    name_mask: u64,
+    pinyin_full_mask: u64,
+    pinyin_initials_mask: u64,
+    /// 模糊音全拼与首字母变体的字符位图并集
+    fuzzy_mask: u64,
+    /// 用户别名的各种写法：小写别名及其全拼 / 首字母变体（去重）
+    alias_chars: Vec<Vec<char>>,
+    /// 别名各写法的字符位图并集
+    alias_mask: u64,
+    /// 条目关键词（[`AppEntry::keywords`]）的各种写法，构造同别名
+    keyword_chars: Vec<Vec<char>>,
+    /// 关键词各写法的字符位图并集
+    keyword_mask: u64,
+    /// 是否被隐藏规则命中（命中则不参与查询）
+    hidden: bool,
 }
 
 /// 从 AppEntry 预计算搜索字段。
-fn compute_fields(app: &AppEntry) -> SearchFields {
-    let lowercase_name = app.name.to_lowercase();
-    let pinyin_full = to_pinyin(&app.name);
-    let pinyin_initials = to_initials(&app.name);
+fn compute_fields(app: &AppEntry, fuzzy: &[FuzzyRule]) -> SearchFields {
+    fields_with_pinyin(app, &PinyinFields::compute(&app.name), fuzzy)
+}
+
+/// 用已有的拼音字段构造搜索字段（只剩小写化和字符拆分）。
+fn fields_with_pinyin(app: &AppEntry, pinyin: &PinyinFields, fuzzy: &[FuzzyRule]) -> SearchFields {
+    let folded = normalize::fold(&app.name);
+    let lowercase_name = folded.to_lowercase();
     let lowercase_chars: Vec<char> = lowercase_name.chars().collect();
-    let pinyin_full_chars: Vec<char> = pinyin_full.chars().collect();
-    let pinyin_initials_chars: Vec<char> = pinyin_initials.chars().collect();
-    SearchFields {
+    let to_chars = |variants: &[String]| -> Vec<Vec<char>> {
+        variants.iter().map(|v| v.chars().collect()).collect()
+    };
+    let union_mask = |variants: &[Vec<char>]| {
+        variants.iter().fold(0, |mask, v| mask | matcher::char_mask(v))
+    };
+    let pinyin_full_chars = to_chars(&pinyin.full);
+    let pinyin_initials_chars = to_chars(&pinyin.initials);
+    let keyword_chars = term_forms(&app.keywords);
+    let mut fields = SearchFields {
+        boundaries: lowercase_boundaries(&folded),
+        name_mask: matcher::char_mask(&lowercase_chars),
+        pinyin_full_mask: union_mask(&pinyin_full_chars),
+        pinyin_initials_mask: union_mask(&pinyin_initials_chars),
         lowercase_name,
         lowercase_chars,
         pinyin_full_chars,
         pinyin_initials_chars,
+        fuzzy_full_chars: Vec::new(),
+        fuzzy_initials_chars: Vec::new(),
+        fuzzy_mask: 0,
+        alias_chars: Vec::new(),
+        alias_mask: 0,
+        keyword_mask: union_mask(&keyword_chars),
+        keyword_chars,
+        hidden: false,
+    };
+    apply_fuzzy(&mut fields, fuzzy);
+    fields
+}
+
+/// 按模糊音规则（重新）计算归一化的拼音变体；规则为空时清空。
+fn apply_fuzzy(fields: &mut SearchFields, rules: &[FuzzyRule]) {
+    let normalize = |variants: &[Vec<char>]| -> Vec<Vec<char>> {
+        if rules.is_empty() {
+            return Vec::new();
+        }
+        variants.iter().map(|v| fuzzy_normalize(v, rules).0).collect()
+    };
+    fields.fuzzy_full_chars = normalize(&fields.pinyin_full_chars);
+    fields.fuzzy_initials_chars = normalize(&fields.pinyin_initials_chars);
+    fields.fuzzy_mask = fields
+        .fuzzy_full_chars
+        .iter()
+        .chain(&fields.fuzzy_initials_chars)
+        .fold(0, |mask, v| mask | matcher::char_mask(v));
+}
+
+/// 按用户别名（重新）计算别名字段。
+fn apply_aliases(fields: &mut SearchFields, aliases: &[String]) {
+    let forms = term_forms(aliases);
+    fields.alias_mask = forms.iter().fold(0, |mask, v| mask | matcher::char_mask(v));
+    fields.alias_chars = forms;
+}
+
+/// 别名 / 关键词的各种写法：折叠小写后的文本，含中文时再加全拼 / 首字母变体（去重）。
+///
+/// 这里的拼音不进索引缓存（缓存只存名称拼音），每次构造字段时重新转换：关键词、别名通常
+/// 只有几个且多为 ASCII，别名又来自 SQLite、可随时修改，缓存它们得不偿失。
+fn term_forms(terms: &[String]) -> Vec<Vec<char>> {
+    let mut forms: Vec<Vec<char>> = Vec::new();
+    let mut push = |form: String| {
+        let chars: Vec<char> = form.chars().collect();
+        if !forms.contains(&chars) {
+            forms.push(chars);
+        }
+    };
+    for term in terms {
+        let lowercase = normalize::fold(term.trim()).to_lowercase();
+        if lowercase.is_empty() {
+            continue;
+        }
+        if lowercase.is_ascii() {
+            push(lowercase);
+            continue;
+        }
+        let pinyin = PinyinFields::compute(&lowercase);
+        std::iter::once(lowercase).chain(pinyin.full).chain(pinyin.initials).for_each(&mut push);
+    }
+    forms
+}
+
+/// 原名的词首标记展开到小写字符上（一个字符小写后变成多个时，只有第一个继承标记）。
+fn lowercase_boundaries(name: &str) -> Vec<bool> {
+    let chars: Vec<char> = name.chars().collect();
+    matcher::word_boundaries(&chars)
+        .into_iter()
+        .zip(&chars)
+        .flat_map(|(boundary, c)| {
+            c.to_lowercase().enumerate().map(move |(k, _)| boundary && k == 0)
+        })
+        .collect()
+}
+
+/// query 侧的字符位图。
+struct QueryMasks {
+    /// query 全部字符
+    all: u64,
+    /// 逐位置的字符位（Smith-Waterman 上限估计用）
+    per_char: Vec<u64>,
+    /// 中文 query 转拼音后的 (全部字符, 逐位置字符位)
+    pinyin: Option<(u64, Vec<u64>)>,
+    /// 双拼解码出的各候选全拼的字符位图
+    double_pinyin: Vec<u64>,
+    /// 模糊音归一化后的 query 字符位图
+    fuzzy: Option<u64>,
+}
+
+impl QueryMasks {
+    fn new(
+        query_chars: &[char],
+        query_pinyin_chars: Option<&[char]>,
+        double_pinyin: &[Vec<char>],
+        fuzzy_chars: Option<&[char]>,
+    ) -> Self {
+        let per_position = |chars: &[char]| -> (u64, Vec<u64>) {
+            (matcher::char_mask(chars), chars.iter().map(|&c| matcher::char_bit(c)).collect())
+        };
+        let (all, per_char) = per_position(query_chars);
+        Self {
+            all,
+            per_char,
+            pinyin: query_pinyin_chars.map(per_position),
+            double_pinyin: double_pinyin.iter().map(|c| matcher::char_mask(c)).collect(),
+            fuzzy: fuzzy_chars.map(matcher::char_mask),
+        }
     }
 }
 
+/// 剪枝：条目是否**可能**被 [`calculate_match_score`] 命中。
+///
+/// - 子序列匹配（名称/别名/全拼/首字母）要求 query 的每个字符都出现在对应字段中
+/// - 双拼匹配要求某个候选全拼的字符全部出现在全拼中
+/// - 模糊音匹配要求归一后的 query 字符全部出现在归一后的拼音中
+/// - 反向拼音匹配要求 query 拼音全部出现在名称中，或满足跨语言比对的得分上限
+/// - 通用 Smith-Waterman 要求名称中出现的 query 字符足够多（见 [`matcher::similarity_possible`]）
+fn may_match(query: &QueryMasks, fields: &SearchFields, profile: &ScoringProfile) -> bool {
+    let covers = |all: u64, mask: u64| all & mask == all;
+    let present = |per_char: &[u64]| per_char.iter().filter(|&&b| b & fields.name_mask != 0).count();
+
+    if covers(query.all, fields.name_mask)
+        || covers(query.all, fields.alias_mask)
+        || covers(query.all, fields.keyword_mask)
+        || covers(query.all, fields.pinyin_full_mask)
+        || covers(query.all, fields.pinyin_initials_mask)
+    {
+        return true;
+    }
+    if query.double_pinyin.iter().any(|&all| covers(all, fields.pinyin_full_mask)) {
+        return true;
+    }
+    if query.fuzzy.is_some_and(|all| covers(all, fields.fuzzy_mask)) {
+        return true;
+    }
+    if let Some((all, ref per_char)) = query.pinyin {
+        if covers(all, fields.name_mask)
+            || matcher::similarity_possible(
+                present(per_char),
+                per_char.len(),
+                profile.cross_lang_threshold,
+                profile,
+            )
+        {
+            return true;
+        }
+    }
+    matcher::similarity_possible(
+        present(&query.per_char),
+        query.per_char.len(),
+        profile.general_sim_threshold,
+        profile,
+    )
+}
+
 impl SearchEngine {
     pub fn new(apps: Vec<AppEntry>) -> Self {
         let apps = apps
             .into_iter()
             .map(|app| {
-                let fields = compute_fields(&app);
+                let fields = compute_fields(&app, &[]);
                 (app, fields)
             })
             .collect();
         Self {
             apps: RwLock::new(apps),
+            options: RwLock::new(SearchOptions::default()),
+            aliases: RwLock::new(HashMap::new()),
+            hidden: RwLock::new(HiddenFilter::default()),
+            fields_generation: AtomicU64::new(0),
+        }
+    }
+
+    /// 用缓存的拼音字段构建索引；版本过期的拼音按当前规则重新计算。
+    pub fn with_pinyin(apps: Vec<(AppEntry, PinyinFields)>) -> Self {
+        let apps = apps
+            .into_iter()
+            .map(|(app, pinyin)| {
+                let fields = if pinyin.is_current() {
+                    fields_with_pinyin(&app, &pinyin, &[])
+                } else {
+                    compute_fields(&app, &[])
+                };
+                (app, fields)
+            })
+            .collect();
+        Self {
+            apps: RwLock::new(apps),
+            options: RwLock::new(SearchOptions::default()),
+            aliases: RwLock::new(HashMap::new()),
+            hidden: RwLock::new(HiddenFilter::default()),
+            fields_generation: AtomicU64::new(0),
+        }
+    }
+
+    /// 设置双拼方案（None 关闭）。对之后的查询生效。
+    pub fn set_double_pinyin(&self, scheme: Option<DoublePinyinScheme>) {
+        self.options.write().unwrap().double_pinyin = scheme;
+    }
+
+    /// 替换打分配置。只影响打分，对之后的查询立即生效，不重建索引。
+    pub fn set_scoring_profile(&self, profile: ScoringProfile) {
+        self.options.write().unwrap().profile = profile;
+    }
+
+    /// 设置模糊音规则（空表示关闭），并按新规则重算全部条目的归一化拼音。
+    pub fn set_fuzzy_pinyin(&self, rules: Vec<FuzzyRule>) {
+        let mut options = self.options.write().unwrap();
+        if options.fuzzy_pinyin == rules {
+            return;
+        }
+        let mut apps = self.apps.write().unwrap();
+        for (_, fields) in apps.iter_mut() {
+            apply_fuzzy(fields, &rules);
+        }
+        options.fuzzy_pinyin = rules;
+        self.fields_generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// 替换用户别名（应用路径 → 别名），并重算全部条目的别名字段。对之后的查询生效。
+    pub fn set_aliases(&self, aliases: HashMap<PathBuf, Vec<String>>) {
+        // 先替换映射再改条目，与新增条目时“先读映射、后写条目”的加锁顺序一致
+        *self.aliases.write().unwrap() = aliases.clone();
+        let mut apps = self.apps.write().unwrap();
+        for (app, fields) in apps.iter_mut() {
+            apply_aliases(fields, aliases.get(&app.path).map(Vec::as_slice).unwrap_or_default());
+        }
+        self.fields_generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// 替换隐藏规则，并重算全部条目的隐藏标记。对之后的查询生效。
+    pub fn set_hidden(&self, filter: HiddenFilter) {
+        // 加锁顺序同 set_aliases
+        *self.hidden.write().unwrap() = filter.clone();
+        let mut apps = self.apps.write().unwrap();
+        for (app, fields) in apps.iter_mut() {
+            fields.hidden = filter.hides(app);
+        }
+        self.fields_generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// 条目是否被当前隐藏规则命中。
+    pub fn is_hidden(&self, entry: &AppEntry) -> bool {
+        self.hidden.read().unwrap().hides(entry)
+    }
+
+    /// 按当前模糊音规则、用户别名与隐藏规则计算新增条目的搜索字段。
+    fn fields_for(&self, entry: &AppEntry, fuzzy: &[FuzzyRule]) -> SearchFields {
+        let mut fields = compute_fields(entry, fuzzy);
+        if let Some(aliases) = self.aliases.read().unwrap().get(&entry.path) {
+            apply_aliases(&mut fields, aliases);
+        }
+        fields.hidden = self.is_hidden(entry);
+        fields
+    }
+
+    /// 当前模糊音规则（新增条目按它计算搜索字段）。
+    fn fuzzy_rules(&self) -> Vec<FuzzyRule> {
+        self.options.read().unwrap().fuzzy_pinyin.clone()
+    }
+
+    /// 在持写锁之外为 `entries` 计算搜索字段，再持 `apps` 写锁交给 `write` 落入索引。
+    ///
+    /// 计算期间模糊音规则、别名或隐藏规则若被替换（[`Self::fields_generation`] 变化），
+    /// 放开写锁按新规则重算，避免新条目沿用旧字段直到下次全量重建。
+    /// 不在写锁内重算：设置方先持 `options` 再持 `apps`，反过来加锁会死锁。
+    fn write_with_fields<R>(
+        &self,
+        entries: &[AppEntry],
+        write: impl FnOnce(&mut Vec<(AppEntry, SearchFields)>, Vec<(AppEntry, SearchFields)>) -> R,
+    ) -> R {
+        loop {
+            let generation = self.fields_generation.load(Ordering::Acquire);
+            let fuzzy = self.fuzzy_rules();
+            let prepared = entries
+                .iter()
+                .map(|entry| (entry.clone(), self.fields_for(entry, &fuzzy)))
+                .collect();
+            let mut apps = self.apps.write().unwrap();
+            if self.fields_generation.load(Ordering::Acquire) == generation {
+                return write(&mut apps, prepared);
+            }
         }
     }
 
@@ -72,8 +427,65 @@ impl SearchEngine {
 
     /// 运行时动态添加条目（Layer 3 "用过即学"）。
     pub fn add(&self, entry: AppEntry) {
-        let fields = compute_fields(&entry);
-        self.apps.write().unwrap().push((entry, fields));
+        self.write_with_fields(&[entry], |apps, prepared| apps.extend(prepared));
+    }
+
+    /// 按路径插入或替换条目（名称变化时重新计算搜索字段）。
+    pub fn upsert(&self, entry: AppEntry) {
+        self.apply_delta(&[entry], &[]);
+    }
+
+    /// 按路径移除条目，返回是否确有移除。
+    pub fn remove_path(&self, path: &Path) -> bool {
+        let mut apps = self.apps.write().unwrap();
+        let before = apps.len();
+        apps.retain(|(app, _)| app.path != path);
+        apps.len() != before
+    }
+
+    /// 批量应用增量变更（文件监听 / 对账产出的 upsert + remove）。
+    ///
+    /// 只为变动的条目计算搜索字段，且在持写锁之前完成；未变动的条目原样保留。
+    pub fn apply_delta(&self, upserts: &[AppEntry], removed: &[PathBuf]) {
+        self.write_with_fields(upserts, |apps, prepared| {
+            Self::merge_delta(apps, prepared, removed)
+        });
+    }
+
+    fn merge_delta(
+        apps: &mut Vec<(AppEntry, SearchFields)>,
+        prepared: Vec<(AppEntry, SearchFields)>,
+        removed: &[PathBuf],
+    ) {
+        if !removed.is_empty() {
+            let removed: HashSet<&Path> = removed.iter().map(PathBuf::as_path).collect();
+            apps.retain(|(app, _)| !removed.contains(app.path.as_path()));
+        }
+
+        let mut slots: HashMap<PathBuf, usize> = apps
+            .iter()
+            .enumerate()
+            .map(|(i, (app, _))| (app.path.clone(), i))
+            .collect();
+        for (entry, fields) in prepared {
+            match slots.get(&entry.path) {
+                Some(&i) => apps[i] = (entry, fields),
+                None => {
+                    slots.insert(entry.path.clone(), apps.len());
+                    apps.push((entry, fields));
+                }
+            }
+        }
+    }
+
+    /// 整体替换某个来源的全部条目（用户自定义条目增删改后同步用），其他来源不受影响。
+    pub fn replace_source(&self, source: AppSource, entries: &[AppEntry]) {
+        let entries: Vec<AppEntry> =
+            entries.iter().filter(|entry| entry.source == source).cloned().collect();
+        self.write_with_fields(&entries, |apps, prepared| {
+            apps.retain(|(app, _)| app.source != source);
+            apps.extend(prepared);
+        });
     }
 
     /// 检查索引中是否已有指定路径的条目。
@@ -81,23 +493,197 @@ impl SearchEngine {
         self.apps.read().unwrap().iter().any(|(app, _)| app.path == path)
     }
 
+    /// 按路径查找条目。
+    pub fn get(&self, path: &Path) -> Option<AppEntry> {
+        self.apps
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(app, _)| app.path == path)
+            .map(|(app, _)| app.clone())
+    }
+
+    /// 一次遍历批量查找：返回 `paths` 中在索引里且未被隐藏（按缓存的隐藏标记）的条目。
+    pub fn get_visible<'a>(&self, paths: &[&'a Path]) -> HashMap<&'a Path, AppEntry> {
+        let wanted: HashSet<&'a Path> = paths.iter().copied().collect();
+        self.apps
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, fields)| !fields.hidden)
+            .filter_map(|(app, _)| Some((*wanted.get(app.path.as_path())?, app.clone())))
+            .collect()
+    }
+
     /// 按 query 匹配应用名，返回前 `limit` 条结果。
     ///
     /// 匹配策略：
     /// - 空 query / 纯空白 → 空列表
-    /// - 模糊匹配（名称、拼音全拼、拼音首字母）
+    /// - 模糊匹配（名称、拼音全拼、拼音首字母；启用时含双拼解码、模糊音）
+    /// - 多个词（空白分隔）：与顺序无关，每个词各自匹配且必须全部命中，分数取平均
+    /// - 被隐藏规则命中的条目不返回
     /// - 按匹配质量排序
+    #[allow(dead_code)] // query 命令走 search_hits；保留给不需要高亮的调用方
     pub fn search(&self, query: &str, limit: usize) -> Vec<AppEntry> {
-        let q = query.trim().to_lowercase();
-        if q.is_empty() {
+        let Some(query) = self.prepare(query) else {
             return Vec::new();
+        };
+        let apps = self.apps.read().unwrap();
+        self.rank(&apps, &query, limit, &[], true)
+            .into_iter()
+            .map(|(i, _)| apps[i].0.clone())
+            .collect()
+    }
+
+    /// 同 [`Self::search`]，并附带每条结果在显示名上的高亮区间。
+    pub fn search_hits(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        self.search_hits_from(query, limit, &[])
+    }
+
+    /// 同 [`Self::search_hits`]，只搜索来自 `sources` 的条目（为空表示不限来源）。
+    pub fn search_hits_from(
+        &self,
+        query: &str,
+        limit: usize,
+        sources: &[AppSource],
+    ) -> Vec<SearchHit> {
+        let Some(query) = self.prepare(query) else {
+            return Vec::new();
+        };
+        let apps = self.apps.read().unwrap();
+        self.rank(&apps, &query, limit, sources, true)
+            .into_iter()
+            .map(|(i, score)| {
+                let (app, fields) = &apps[i];
+                SearchHit {
+                    entry: app.clone(),
+                    score,
+                    highlights: highlight_ranges(&query, app, fields),
+                }
+            })
+            .collect()
+    }
+
+    /// 同 [`Self::search_hits`]，并附带每条结果所有命中的匹配路径及其得分（调参/排查用）。
+    ///
+    /// 多词 query 按词分别列出；结果分数为各词最终路径得分的平均。
+    /// 整个 query 与别名完全一致时，该别名命中列在最前并作为采用的路径。
+    pub fn explain(
+        &self,
+        query: &str,
+        limit: usize,
+        sources: &[AppSource],
+    ) -> Vec<(SearchHit, Vec<StrategyScore>)> {
+        let Some(query) = self.prepare(query) else {
+            return Vec::new();
+        };
+        let apps = self.apps.read().unwrap();
+        self.rank(&apps, &query, limit, sources, true)
+            .into_iter()
+            .map(|(i, score)| {
+                let (app, fields) = &apps[i];
+                let strategies = query.explain(fields);
+                let hit = SearchHit {
+                    entry: app.clone(),
+                    score,
+                    highlights: highlight_ranges(&query, app, fields),
+                };
+                (hit, strategies)
+            })
+            .collect()
+    }
+
+    fn prepare(&self, query: &str) -> Option<PreparedQuery> {
+        PreparedQuery::new(query, &self.options.read().unwrap())
+    }
+
+    /// 打分并排序，返回前 `limit` 条的 (下标, 分数)。`sources` 非空时只考虑这些来源的条目；
+    /// 隐藏的条目始终跳过。
+    fn rank(
+        &self,
+        apps: &[(AppEntry, SearchFields)],
+        query: &PreparedQuery,
+        limit: usize,
+        sources: &[AppSource],
+        prefilter: bool,
+    ) -> Vec<(usize, u32)> {
+        // 只收集 (下标, 分数)，排序截断后再克隆命中的条目
+        let mut matches: Vec<(usize, u32)> = apps
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, fields))| !fields.hidden)
+            .filter(|(_, (app, _))| sources.is_empty() || sources.contains(&app.source))
+            .filter(|(_, (_, fields))| !prefilter || query.may_match(fields))
+            .filter_map(|(i, (_, fields))| query.score(fields).map(|score| (i, score)))
+            .collect();
+
+        // 按匹配分数降序排列，同分保持索引顺序
+        matches.sort_unstable_by_key(|&(i, score)| (std::cmp::Reverse(score), i));
+
+        matches.truncate(limit);
+        matches
+    }
+}
+
+/// 一条带高亮的搜索结果。
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub entry: AppEntry,
+    /// 文本匹配分数（越高越好），供排序器与使用频率组合
+    pub score: u32,
+    /// 显示名上的命中区间（按字符计，左闭右开，升序不重叠）。
+    /// 拼音命中会映射回对应的汉字；序列比对命中没有可靠的对齐，返回空。
+    pub highlights: Vec<(usize, usize)>,
+}
+
+/// 预处理后的 query：小写字符、中文 query 的拼音、剪枝位图。
+///
+/// 含多个空白分隔的词时，`tokens` 为各词各自的预处理结果，打分只看 `tokens`。
+struct PreparedQuery {
+    chars: Vec<char>,
+    pinyin: Option<String>,
+    pinyin_chars: Option<Vec<char>>,
+    /// 双拼解码出的候选全拼
+    double_pinyin_chars: Vec<Vec<char>>,
+    /// 模糊音归一化后的 query（未启用模糊音时为 None）
+    fuzzy_chars: Option<Vec<char>>,
+    /// 归一化所用的模糊音规则（高亮映射时重算条目的归一化下标）
+    fuzzy_rules: Vec<FuzzyRule>,
+    /// 查询开始时的打分配置快照
+    profile: ScoringProfile,
+    masks: QueryMasks,
+    tokens: Vec<PreparedQuery>,
+    /// 名称匹配是否按词首对齐（多词 query 中的单个词）
+    boundary_aware: bool,
+    /// 名称对齐 DP 的缓冲区，整次查询复用
+    scratch: RefCell<matcher::AlignScratch>,
+}
+
+impl PreparedQuery {
+    /// 空 query / 纯空白返回 None。
+    fn new(query: &str, options: &SearchOptions) -> Option<Self> {
+        let q = normalize::fold(query.trim()).to_lowercase();
+        if q.is_empty() {
+            return None;
+        }
+
+        let mut prepared = Self::single(&q, false, options);
+        let words: Vec<&str> = q.split_whitespace().collect();
+        if words.len() > 1 {
+            prepared.tokens = words
+                .into_iter()
+                .map(|w| Self::single(w, true, options))
+                .collect();
         }
+        Some(prepared)
+    }
 
-        let query_chars: Vec<char> = q.chars().collect();
+    fn single(q: &str, boundary_aware: bool, options: &SearchOptions) -> Self {
+        let chars: Vec<char> = q.chars().collect();
 
         // 中文查询 → 提前转拼音，避免在循环内重复转换
-        let (query_pinyin, query_pinyin_chars) = if matcher::contains_chinese(&q) {
-            let pinyin = to_pinyin(&q);
+        let (pinyin, pinyin_chars) = if matcher::contains_chinese(q) {
+            let pinyin = to_pinyin(q);
             if pinyin != q {
                 let chars: Vec<char> = pinyin.chars().collect();
                 (Some(pinyin), Some(chars))
@@ -108,98 +694,460 @@ impl SearchEngine {
             (None, None)
         };
 
-        let apps = self.apps.read().unwrap();
-        let mut matches: Vec<(AppEntry, u32)> = apps
+        let double_pinyin_chars: Vec<Vec<char>> = options
+            .double_pinyin
+            .map(|scheme| decode_double_pinyin(q, scheme))
+            .unwrap_or_default()
             .iter()
-            .filter_map(|(app, fields)| {
-                calculate_match_score(
-                    &query_chars,
-                    query_pinyin.as_deref(),
-                    query_pinyin_chars.as_deref(),
-                    fields,
-                )
-                .map(|score| (app.clone(), score))
-            })
+            .map(|candidate| candidate.chars().collect())
             .collect();
-        drop(apps);
 
-        // 按匹配分数降序排列
-        matches.sort_by_key(|b| std::cmp::Reverse(b.1));
+        let fuzzy_rules = options.fuzzy_pinyin.clone();
+        let fuzzy_chars =
+            (!fuzzy_rules.is_empty()).then(|| fuzzy_normalize(&chars, &fuzzy_rules).0);
 
-        matches
-            .into_iter()
-            .take(limit)
-            .map(|(app, _)| app)
+        let masks = QueryMasks::new(
+            &chars,
+            pinyin_chars.as_deref(),
+            &double_pinyin_chars,
+            fuzzy_chars.as_deref(),
+        );
+        Self {
+            chars,
+            pinyin,
+            pinyin_chars,
+            double_pinyin_chars,
+            fuzzy_chars,
+            fuzzy_rules,
+            profile: options.profile,
+            masks,
+            tokens: Vec::new(),
+            boundary_aware,
+            scratch: RefCell::default(),
+        }
+    }
+
+    /// 剪枝：多词时每个词都必须可能命中。
+    fn may_match(&self, fields: &SearchFields) -> bool {
+        if self.tokens.is_empty() {
+            may_match(&self.masks, fields, &self.profile)
+        } else {
+            self.tokens.iter().all(|t| may_match(&t.masks, fields, &t.profile))
+        }
+    }
+
+    /// 条目命中的全部匹配路径，与 [`Self::score`] 的判定顺序一致。
+    fn explain(&self, fields: &SearchFields) -> Vec<StrategyScore> {
+        if self.tokens.is_empty() {
+            return explain_token(self, fields);
+        }
+        let per_token = self.tokens.iter().flat_map(|t| explain_token(t, fields));
+        let Some(raw) = exact_alias_match(&self.chars, fields, &self.profile) else {
+            return per_token.collect();
+        };
+        // 整体别名命中决定得分，各词自身的路径只作参考
+        let whole = StrategyScore {
+            token: self.chars.iter().collect(),
+            strategy: MatchKind::AliasExact.label(),
+            raw,
+            bonus: self.profile.alias_exact_bonus,
+            chosen: true,
+        };
+        std::iter::once(whole)
+            .chain(per_token.map(|s| StrategyScore { chosen: false, ..s }))
             .collect()
     }
+
+    /// 条目得分：单词直接打分；多词要求每个词都命中，取平均分（与单词得分同一量级）。
+    /// 多词 query 整体等于某个别名（如 `vs code`）时按别名精确命中计分。
+    fn score(&self, fields: &SearchFields) -> Option<u32> {
+        if self.tokens.is_empty() {
+            return calculate_match_score(self, fields).map(|(score, _)| score);
+        }
+        if let Some(score) = exact_alias_match(&self.chars, fields, &self.profile) {
+            return Some(score + self.profile.alias_exact_bonus);
+        }
+        let mut total = 0;
+        for token in &self.tokens {
+            total += calculate_match_score(token, fields)?.0;
+        }
+        Some(total / self.tokens.len() as u32)
+    }
+}
+
+/// 最高分来自哪条匹配路径，决定高亮如何映射回显示名。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchKind {
+    Name,
+    /// 命中的全拼变体下标
+    PinyinFull(usize),
+    /// 命中的首字母变体下标
+    PinyinInitials(usize),
+    /// query 与某个别名写法完全一致
+    AliasExact,
+    /// 别名写法的子序列匹配
+    Alias,
+    /// 条目关键词写法的子序列匹配
+    Keyword,
+    /// (双拼候选下标, 命中的全拼变体下标)
+    DoublePinyin(usize, usize),
+    /// 命中的模糊音全拼变体下标
+    FuzzyFull(usize),
+    /// 命中的模糊音首字母变体下标
+    FuzzyInitials(usize),
+    ReversePinyin,
+    Contains,
+    /// 中文 query 的拼音与名称做跨语言序列比对
+    CrossLangSimilarity,
+    Similarity,
+}
+
+impl MatchKind {
+    /// 打分明细中显示的路径名。
+    fn label(self) -> &'static str {
+        match self {
+            MatchKind::Name => "name",
+            MatchKind::PinyinFull(_) => "pinyin_full",
+            MatchKind::PinyinInitials(_) => "pinyin_initials",
+            MatchKind::AliasExact => "alias_exact",
+            MatchKind::Alias => "alias",
+            MatchKind::Keyword => "keyword",
+            MatchKind::DoublePinyin(..) => "double_pinyin",
+            MatchKind::FuzzyFull(_) => "fuzzy_pinyin_full",
+            MatchKind::FuzzyInitials(_) => "fuzzy_pinyin_initials",
+            MatchKind::ReversePinyin => "reverse_pinyin",
+            MatchKind::Contains => "contains",
+            MatchKind::CrossLangSimilarity => "cross_lang_similarity",
+            MatchKind::Similarity => "similarity",
+        }
+    }
+}
+
+/// 一条命中的匹配路径的得分明细（[`SearchEngine::explain`] 用）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyScore {
+    /// 该路径匹配的 query 词（单词 query 即整个 query）
+    pub token: String,
+    /// 匹配路径名，如 `name`、`pinyin_initials`、`similarity`
+    pub strategy: &'static str,
+    /// 匹配原语给出的原始分
+    pub raw: u32,
+    /// 该路径的固定 bonus（见 [`ScoringProfile`] 中的 `*_bonus`）
+    pub bonus: u32,
+    /// 是否为该词最终采用的路径（最高分，同分取先命中的）
+    pub chosen: bool,
+}
+
+/// 当前最高分及其路径；`trace` 为 Some 时记录每条命中的路径。
+struct BestMatch {
+    score: u32,
+    kind: MatchKind,
+    trace: Option<Vec<(MatchKind, u32, u32)>>,
+    /// 最高分路径在 `trace` 中的下标
+    chosen: usize,
+}
+
+impl BestMatch {
+    /// 严格更高才替换，同分保留先出现的路径。
+    fn offer(&mut self, raw: u32, bonus: u32, kind: MatchKind) {
+        let score = raw + bonus;
+        if let Some(ref mut trace) = self.trace {
+            trace.push((kind, raw, bonus));
+            if score > self.score {
+                self.chosen = trace.len() - 1;
+            }
+        }
+        if score > self.score {
+            self.score = score;
+            self.kind = kind;
+        }
+    }
 }
 
 /// 计算匹配分数（委托到 matcher 模块的算法原语）。
-/// 返回 Some(score)，分数越高表示匹配越好。
+/// 返回 Some((score, kind))，分数越高表示匹配越好。
 fn calculate_match_score(
-    query_chars: &[char],
-    query_pinyin: Option<&str>,
-    query_pinyin_chars: Option<&[char]>,
+    query: &PreparedQuery,
     fields: &SearchFields,
-) -> Option<u32> {
+) -> Option<(u32, MatchKind)> {
+    let best = evaluate(query, fields, false);
+    (best.score > 0).then_some((best.score, best.kind))
+}
+
+/// 单个词命中的全部匹配路径（未命中时为空）。
+fn explain_token(query: &PreparedQuery, fields: &SearchFields) -> Vec<StrategyScore> {
+    let best = evaluate(query, fields, true);
+    let token: String = query.chars.iter().collect();
+    best.trace
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(i, (kind, raw, bonus))| StrategyScore {
+            token: token.clone(),
+            strategy: kind.label(),
+            raw,
+            bonus,
+            chosen: i == best.chosen,
+        })
+        .collect()
+}
+
+/// 依次尝试各匹配路径，返回最高分及其路径。
+fn evaluate(query: &PreparedQuery, fields: &SearchFields, trace: bool) -> BestMatch {
     use matcher::*;
 
-    let mut best_score: u32 = 0;
+    let query_chars = &query.chars[..];
+    let profile = &query.profile;
+    let mut best = BestMatch {
+        score: 0,
+        kind: MatchKind::Similarity,
+        trace: trace.then(Vec::new),
+        chosen: 0,
+    };
 
     // 1. 严格子序列匹配（所有字符按序出现）
-    if let Some(score) = fuzzy_match(query_chars, &fields.lowercase_chars) {
-        best_score = best_score.max(score + NAME_MATCH_BONUS);
+    if let Some(score) = name_match(query, fields) {
+        best.offer(score, profile.name_match_bonus, MatchKind::Name);
+    }
+    // 用户别名：完全一致置顶，否则与名称一样按子序列打分
+    if let Some(score) = exact_alias_match(query_chars, fields, profile) {
+        best.offer(score, profile.alias_exact_bonus, MatchKind::AliasExact);
+    } else if let Some(score) =
+        fields.alias_chars.iter().filter_map(|a| fuzzy_match(query_chars, a, profile)).max()
+    {
+        best.offer(score, profile.alias_bonus, MatchKind::Alias);
     }
-    if let Some(score) = fuzzy_match(query_chars, &fields.pinyin_full_chars) {
-        best_score = best_score.max(score + PINYIN_FULL_BONUS);
+    if let Some(score) =
+        fields.keyword_chars.iter().filter_map(|k| fuzzy_match(query_chars, k, profile)).max()
+    {
+        best.offer(score, profile.keyword_bonus, MatchKind::Keyword);
     }
-    if let Some(score) = fuzzy_match(query_chars, &fields.pinyin_initials_chars) {
-        best_score = best_score.max(score + PINYIN_INITIALS_BONUS);
+    // 多音字：任一读音变体命中即可
+    let mut pinyin_hit = false;
+    for (v, chars) in fields.pinyin_full_chars.iter().enumerate() {
+        if let Some(score) = fuzzy_match(query_chars, chars, profile) {
+            best.offer(score, profile.pinyin_full_bonus, MatchKind::PinyinFull(v));
+            pinyin_hit = true;
+        }
+    }
+    for (v, chars) in fields.pinyin_initials_chars.iter().enumerate() {
+        if let Some(score) = fuzzy_match(query_chars, chars, profile) {
+            best.offer(score, profile.pinyin_initials_bonus, MatchKind::PinyinInitials(v));
+            pinyin_hit = true;
+        }
+    }
+    // 模糊音：仅在精确拼音未命中时启用，保证精确匹配优先
+    if let Some(fuzzy_chars) = query.fuzzy_chars.as_deref() {
+        if !pinyin_hit {
+            for (v, chars) in fields.fuzzy_full_chars.iter().enumerate() {
+                if let Some(score) = fuzzy_match(fuzzy_chars, chars, profile) {
+                    best.offer(score, profile.fuzzy_pinyin_bonus, MatchKind::FuzzyFull(v));
+                }
+            }
+            for (v, chars) in fields.fuzzy_initials_chars.iter().enumerate() {
+                if let Some(score) = fuzzy_match(fuzzy_chars, chars, profile) {
+                    best.offer(score, profile.fuzzy_pinyin_bonus, MatchKind::FuzzyInitials(v));
+                }
+            }
+        }
+    }
+    // 双拼：解码出的候选全拼 × 条目全拼变体
+    for (c, candidate) in query.double_pinyin_chars.iter().enumerate() {
+        for (v, chars) in fields.pinyin_full_chars.iter().enumerate() {
+            if let Some(score) = fuzzy_match(candidate, chars, profile) {
+                best.offer(score, profile.double_pinyin_bonus, MatchKind::DoublePinyin(c, v));
+            }
+        }
     }
 
     // 2. 反向拼音匹配：中文查询 → 拼音 → 匹配英文应用名
-    if let (Some(query_pinyin), Some(query_pinyin_chars)) = (query_pinyin, query_pinyin_chars) {
-        if let Some(score) = fuzzy_match(query_pinyin_chars, &fields.lowercase_chars) {
-            best_score = best_score.max(score + REVERSE_PINYIN_BONUS);
+    if let (Some(query_pinyin), Some(query_pinyin_chars)) =
+        (query.pinyin.as_deref(), query.pinyin_chars.as_deref())
+    {
+        if let Some(score) = fuzzy_match(query_pinyin_chars, &fields.lowercase_chars, profile) {
+            best.offer(score, profile.reverse_pinyin_bonus, MatchKind::ReversePinyin);
         }
         // 目标名包含查询拼音（如 "weixinshurufa" 包含 "weixin"）
         if fields.lowercase_name.contains(query_pinyin) {
-            best_score = best_score.max(CONTAINS_BONUS);
+            best.offer(0, profile.contains_bonus, MatchKind::Contains);
         }
         // Smith-Waterman 处理拼音罗马化差异（如 "weixin" vs "wechat"）
         // 跨语言匹配降低阈值，因为 "weixin" vs "wechat" 仅有 "we" 前缀匹配
-        if best_score == 0 {
+        if best.score == 0 {
             if let Some(score) = similarity_match(
                 query_pinyin_chars,
                 &fields.lowercase_chars,
-                CROSS_LANG_THRESHOLD,
+                profile.cross_lang_threshold,
+                profile,
             ) {
-                best_score = best_score.max(score + CROSS_LANG_SIM_BONUS);
+                best.offer(score, profile.cross_lang_sim_bonus, MatchKind::CrossLangSimilarity);
             }
         }
     }
 
     // 3. Smith-Waterman 局部序列比对：处理拼写变体、缩写、罗马化差异
     //    仅在严格匹配无结果时启用，避免过度匹配
-    if best_score == 0 {
-        if let Some(score) =
-            similarity_match(query_chars, &fields.lowercase_chars, GENERAL_SIM_THRESHOLD)
-        {
-            best_score = best_score.max(score);
+    if best.score == 0 {
+        if let Some(score) = similarity_match(
+            query_chars,
+            &fields.lowercase_chars,
+            profile.general_sim_threshold,
+            profile,
+        ) {
+            best.offer(score, 0, MatchKind::Similarity);
         }
     }
 
-    if best_score > 0 {
-        Some(best_score)
+    best
+}
+
+/// query 与某个别名写法完全一致时返回该写法上的子序列匹配分。
+fn exact_alias_match(
+    query_chars: &[char],
+    fields: &SearchFields,
+    profile: &ScoringProfile,
+) -> Option<u32> {
+    fields
+        .alias_chars
+        .iter()
+        .find(|a| a.as_slice() == query_chars)
+        .and_then(|a| matcher::fuzzy_match(query_chars, a, profile))
+}
+
+/// 名称上的词首感知子序列匹配，返回分数，对齐下标留在 `query.scratch`。
+/// 多词 query 中的词从各词首及词中命中处分别尝试。
+fn name_match(query: &PreparedQuery, fields: &SearchFields) -> Option<u32> {
+    let scratch = &mut query.scratch.borrow_mut();
+    if query.boundary_aware {
+        matcher::fuzzy_match_at_boundaries(
+            &query.chars,
+            &fields.lowercase_chars,
+            &fields.boundaries,
+            &query.profile,
+            scratch,
+        )
     } else {
-        None
+        matcher::fuzzy_match_with_boundaries(
+            &query.chars,
+            &fields.lowercase_chars,
+            &fields.boundaries,
+            &query.profile,
+            scratch,
+        )
     }
 }
 
+/// 显示名上的高亮区间。多词 query 取各词命中位置的并集。
+fn highlight_ranges(
+    query: &PreparedQuery,
+    app: &AppEntry,
+    fields: &SearchFields,
+) -> Vec<(usize, usize)> {
+    let single = |q: &PreparedQuery| {
+        calculate_match_score(q, fields)
+            .map(|(_, kind)| highlight_indices(kind, q, app, fields))
+            .unwrap_or_default()
+    };
+    let mut indices: Vec<usize> = if query.tokens.is_empty() {
+        single(query)
+    } else {
+        query.tokens.iter().flat_map(single).collect()
+    };
+    indices.sort_unstable();
+    indices.dedup();
+    matcher::indices_to_ranges(&indices)
+}
+
+/// 把命中路径上的字符下标映射回显示名字符下标。
+fn highlight_indices(
+    kind: MatchKind,
+    query: &PreparedQuery,
+    app: &AppEntry,
+    fields: &SearchFields,
+) -> Vec<usize> {
+    // (命中字段中的下标, 字段字符 → 显示名字符下标)
+    let (indices, owners) = match kind {
+        MatchKind::Name => (
+            name_match(query, fields).map(|_| query.scratch.borrow().indices().to_vec()),
+            lowercase_owners(&normalize::fold(&app.name)),
+        ),
+        MatchKind::PinyinFull(v) => (
+            matcher::fuzzy_match_indices(&query.chars, &fields.pinyin_full_chars[v]),
+            full_owners(&app.name).swap_remove(v),
+        ),
+        MatchKind::PinyinInitials(v) => (
+            matcher::fuzzy_match_indices(&query.chars, &fields.pinyin_initials_chars[v]),
+            initials_owners(&app.name).swap_remove(v),
+        ),
+        MatchKind::FuzzyFull(v) => (
+            fuzzy_indices(query, &fields.pinyin_full_chars[v]),
+            full_owners(&app.name).swap_remove(v),
+        ),
+        MatchKind::FuzzyInitials(v) => (
+            fuzzy_indices(query, &fields.pinyin_initials_chars[v]),
+            initials_owners(&app.name).swap_remove(v),
+        ),
+        MatchKind::DoublePinyin(c, v) => (
+            matcher::fuzzy_match_indices(
+                &query.double_pinyin_chars[c],
+                &fields.pinyin_full_chars[v],
+            ),
+            full_owners(&app.name).swap_remove(v),
+        ),
+        MatchKind::ReversePinyin => (
+            query
+                .pinyin_chars
+                .as_deref()
+                .and_then(|p| matcher::fuzzy_match_indices(p, &fields.lowercase_chars)),
+            lowercase_owners(&normalize::fold(&app.name)),
+        ),
+        MatchKind::Contains => (
+            query.pinyin.as_deref().and_then(|p| {
+                let byte_pos = fields.lowercase_name.find(p)?;
+                let start = fields.lowercase_name[..byte_pos].chars().count();
+                Some((start..start + p.chars().count()).collect())
+            }),
+            lowercase_owners(&normalize::fold(&app.name)),
+        ),
+        // 别名、关键词不是显示名，不高亮
+        MatchKind::AliasExact
+        | MatchKind::Alias
+        | MatchKind::Keyword
+        | MatchKind::CrossLangSimilarity
+        | MatchKind::Similarity => (None, Vec::new()),
+    };
+
+    let Some(indices) = indices else {
+        return Vec::new();
+    };
+    // 缓存的拼音字段与当前转换结果不一致时（理论上不会发生）放弃高亮，而不是标错位置
+    indices
+        .iter()
+        .map(|&i| owners.get(i).copied())
+        .collect::<Option<Vec<usize>>>()
+        .unwrap_or_default()
+}
+
+/// 模糊音命中在原拼音串上的下标：在归一化串上对齐，再经归一化映射回原串。
+fn fuzzy_indices(query: &PreparedQuery, pinyin: &[char]) -> Option<Vec<usize>> {
+    let (normalized, sources) = fuzzy_normalize(pinyin, &query.fuzzy_rules);
+    let indices = matcher::fuzzy_match_indices(query.fuzzy_chars.as_deref()?, &normalized)?;
+    Some(indices.into_iter().map(|i| sources[i]).collect())
+}
+
+/// 小写名称中每个字符对应的原名字符下标（少数字符小写后会变成多个字符）。
+/// 传入折叠后的名称；折叠逐字一一对应，下标同样适用于原名。
+fn lowercase_owners(name: &str) -> Vec<usize> {
+    name.chars()
+        .enumerate()
+        .flat_map(|(i, c)| c.to_lowercase().map(move |_| i))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::AppSource;
+    use crate::search::profile::ScoringPreset;
     use std::path::PathBuf;
 
     fn make(name: &str) -> AppEntry {
@@ -207,6 +1155,8 @@ mod tests {
             name: name.into(),
             path: PathBuf::from("/fake"),
             source: AppSource::StartMenu,
+            icon: None,
+            keywords: Vec::new(),
         }
     }
 
@@ -330,4 +1280,507 @@ mod tests {
             "微信 的拼音 weixin 应匹配 WeixinShuruFa"
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn apply_delta_upserts_and_removes() {
+        let e = SearchEngine::new(vec![AppEntry {
+            path: PathBuf::from("/old"),
+            ..make("Firefox")
+        }]);
+
+        e.apply_delta(
+            &[AppEntry {
+                path: PathBuf::from("/new"),
+                ..make("Chrome")
+            }],
+            &[PathBuf::from("/old")],
+        );
+
+        assert_eq!(e.len(), 1);
+        assert!(e.search("firefox", 10).is_empty());
+        assert_eq!(e.search("chrome", 10)[0].name, "Chrome");
+
+        // 同路径再次 upsert → 替换而非追加
+        e.upsert(AppEntry {
+            path: PathBuf::from("/new"),
+            ..make("Thunderbird")
+        });
+        assert_eq!(e.len(), 1);
+        assert_eq!(e.search("thunderbird", 10)[0].name, "Thunderbird");
+    }
+
+    #[test]
+    fn with_pinyin_uses_cached_fields_unless_stale() {
+        let cached = PinyinFields {
+            full: vec!["cached".to_string()],
+            initials: vec!["c".to_string()],
+            version: crate::search::pinyin::PINYIN_TABLE_VERSION,
+        };
+        let stale = PinyinFields {
+            version: 0,
+            ..cached.clone()
+        };
+        let e = SearchEngine::with_pinyin(vec![
+            (AppEntry { path: PathBuf::from("/a"), ..make("谷歌") }, cached),
+            (AppEntry { path: PathBuf::from("/b"), ..make("微软") }, stale),
+        ]);
+
+        let hits: Vec<String> = e.search("cached", 10).into_iter().map(|a| a.name).collect();
+        assert_eq!(hits, vec!["谷歌"], "当前版本的缓存拼音应被直接使用");
+        assert_eq!(e.search("weiruan", 10)[0].name, "微软", "过期拼音应重新计算");
+    }
+
+    impl SearchEngine {
+        fn ranked_paths(&self, query: &str, limit: usize, prefilter: bool) -> Vec<PathBuf> {
+            let Some(query) = self.prepare(query) else {
+                return Vec::new();
+            };
+            let apps = self.apps.read().unwrap();
+            self.rank(&apps, &query, limit, &[], prefilter)
+                .into_iter()
+                .map(|(i, _)| apps[i].0.path.clone())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn source_filter_limits_candidates() {
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/menu"), ..make("Code") },
+            AppEntry {
+                path: PathBuf::from("/portable"),
+                source: AppSource::UserDirectory,
+                ..make("Code Portable")
+            },
+        ]);
+        assert_eq!(e.search_hits("code", 10).len(), 2);
+        let hits = e.search_hits_from("code", 10, &[AppSource::UserDirectory]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.path, PathBuf::from("/portable"));
+    }
+
+    #[test]
+    fn highlights_map_back_to_display_name() {
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/code"), ..make("VS Code") },
+            AppEntry { path: PathBuf::from("/chrome"), ..make("谷歌浏览器") },
+            AppEntry { path: PathBuf::from("/music"), ..make("QQ音乐") },
+        ]);
+        let ranges = |q: &str| e.search_hits(q, 1).remove(0).highlights;
+
+        // 名称子序列：V S _ C
+        assert_eq!(ranges("vsc"), vec![(0, 2), (3, 4)]);
+        // 拼音首字母 → 对应汉字
+        assert_eq!(ranges("gllq"), vec![(0, 1), (2, 5)]);
+        // 全拼 "yinle" 覆盖 音乐 两个字
+        assert_eq!(ranges("yinle"), vec![(2, 4)]);
+        // 中文直接命中
+        assert_eq!(ranges("浏览"), vec![(2, 4)]);
+    }
+
+    #[test]
+    fn explain_lists_every_matching_strategy() {
+        let e = SearchEngine::new(vec![make("谷歌浏览器")]);
+        let chosen_total = |strategies: &[StrategyScore]| -> Vec<u32> {
+            strategies.iter().filter(|s| s.chosen).map(|s| s.raw + s.bonus).collect()
+        };
+
+        let (hit, strategies) = e.explain("gglq", 1, &[]).remove(0);
+        let labels: Vec<&str> = strategies.iter().map(|s| s.strategy).collect();
+        assert_eq!(labels, vec!["pinyin_full", "pinyin_initials"]);
+        assert_eq!(chosen_total(&strategies), vec![hit.score]);
+        assert_eq!(hit.score, e.search_hits("gglq", 1)[0].score);
+
+        // 多词：按词列出，结果分数为各词最终得分的平均
+        let (hit, strategies) = e.explain("lq gg", 1, &[]).remove(0);
+        assert!(strategies.iter().any(|s| s.token == "lq"));
+        assert!(strategies.iter().any(|s| s.token == "gg"));
+        let totals = chosen_total(&strategies);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(hit.score, totals.iter().sum::<u32>() / 2);
+
+        // 多词 query 整体命中别名：别名路径为唯一采用的路径，即使各词单独都不命中
+        e.set_aliases(HashMap::from([(PathBuf::from("/fake"), vec!["vs code".to_string()])]));
+        let (hit, strategies) = e.explain("vs code", 1, &[]).remove(0);
+        assert_eq!(strategies[0].token, "vs code");
+        assert_eq!(strategies[0].strategy, "alias_exact");
+        assert_eq!(chosen_total(&strategies), vec![hit.score]);
+        assert_eq!(hit.score, e.search_hits("vs code", 1)[0].score);
+    }
+
+    #[test]
+    fn multi_token_query_is_order_independent() {
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/vsc"), ..make("Visual Studio Code") },
+            AppEntry { path: PathBuf::from("/vs"), ..make("Visual Studio") },
+            AppEntry { path: PathBuf::from("/code"), ..make("Code Runner") },
+        ]);
+        let names = |q: &str| -> Vec<String> {
+            e.search(q, 10).into_iter().map(|a| a.name).collect()
+        };
+
+        assert_eq!(names("code visual"), vec!["Visual Studio Code"]);
+        assert_eq!(names("studio vs")[..2], ["Visual Studio Code", "Visual Studio"]);
+        // 任一词不命中 → 整体不命中
+        assert!(names("code firefox").is_empty());
+
+        let hit = e.search_hits("code visual", 1).remove(0);
+        assert_eq!(hit.highlights, vec![(0, 6), (14, 18)]);
+    }
+
+    #[test]
+    fn multi_token_mixes_chinese_and_pinyin() {
+        let e = SearchEngine::new(vec![make("谷歌浏览器"), make("微软办公")]);
+        let hits: Vec<String> = e.search("浏览 gg", 10).into_iter().map(|a| a.name).collect();
+        assert_eq!(hits, vec!["谷歌浏览器"]);
+    }
+
+    #[test]
+    fn acronym_and_camel_case_rank_above_scattered() {
+        let e = SearchEngine::new(vec![
+            make("Devious Scratch"),
+            make("Vascular"),
+            make("Visual Studio Code"),
+        ]);
+        assert_eq!(e.search("vsc", 10)[0].name, "Visual Studio Code");
+
+        let e = SearchEngine::new(vec![make("Pause"), make("PhotoShop")]);
+        assert_eq!(e.search("ps", 10)[0].name, "PhotoShop");
+
+        let e = SearchEngine::new(vec![make("Visual Studio Code")]);
+        assert_eq!(e.search_hits("vsc", 1)[0].highlights, vec![(0, 1), (7, 8), (14, 15)]);
+    }
+
+    #[test]
+    fn polyphonic_readings_are_searchable() {
+        let e = SearchEngine::new(vec![make("重庆地图"), make("银行助手")]);
+        assert_eq!(e.search("chongqing", 10)[0].name, "重庆地图");
+        assert_eq!(e.search("cqdt", 10)[0].name, "重庆地图");
+        assert_eq!(e.search("yinhang", 10)[0].name, "银行助手");
+        // 首选读音照常可用
+        assert_eq!(e.search("zhongqing", 10)[0].name, "重庆地图");
+
+        // 高亮按命中的读音变体映射回汉字
+        assert_eq!(e.search_hits("yinhang", 1)[0].highlights, vec![(0, 2)]);
+    }
+
+    #[test]
+    fn double_pinyin_query_matches_when_enabled() {
+        let e = SearchEngine::new(vec![make("谷歌浏览器"), make("微信")]);
+        // 小鹤双拼：gu ge lq lj → gugeliulan
+        assert!(e.search("gugelqlj", 10).is_empty(), "未启用双拼时不应解码");
+
+        e.set_double_pinyin(Some(DoublePinyinScheme::Xiaohe));
+        assert_eq!(e.search("gugelqlj", 10)[0].name, "谷歌浏览器");
+        assert_eq!(e.search("wwxb", 10)[0].name, "微信");
+        assert_eq!(e.search_hits("lqlj", 1)[0].highlights, vec![(2, 4)]);
+    }
+
+    #[test]
+    fn fuzzy_pinyin_tolerates_dialect_confusions() {
+        let e = SearchEngine::new(vec![make("杂志"), make("蓝牙")]);
+        assert!(e.search("zhazhi", 10).is_empty(), "未启用模糊音时不应命中");
+        assert!(e.search("nanya", 10).is_empty());
+
+        e.set_fuzzy_pinyin(vec![FuzzyRule::ZZh, FuzzyRule::NL]);
+        assert_eq!(e.search("zhazhi", 10)[0].name, "杂志");
+        assert_eq!(e.search("nanya", 10)[0].name, "蓝牙");
+        // 高亮经归一化映射回汉字
+        assert_eq!(e.search_hits("nan", 1)[0].highlights, vec![(0, 1)]);
+
+        // 规则变化后新增条目也按新规则计算
+        e.add(AppEntry { path: PathBuf::from("/n"), ..make("南京") });
+        assert_eq!(e.search("lanjing", 10)[0].name, "南京");
+
+        // 后鼻音按音节归一：xian gu 与 xiang gu 互相命中，g 不会被当作下一音节的声母
+        e.add(AppEntry { path: PathBuf::from("/x"), ..make("香菇") });
+        e.add(AppEntry { path: PathBuf::from("/g"), ..make("仙姑") });
+        e.set_fuzzy_pinyin(vec![FuzzyRule::AnAng]);
+        let names =
+            |q: &str| -> Vec<String> { e.search(q, 10).into_iter().map(|a| a.name).collect() };
+        assert_eq!(names("xiangu"), vec!["仙姑", "香菇"]);
+        assert_eq!(names("xianggu"), vec!["香菇", "仙姑"]);
+
+        e.set_fuzzy_pinyin(Vec::new());
+        assert!(e.search("zhazhi", 10).is_empty());
+    }
+
+    #[test]
+    fn exact_pinyin_outranks_fuzzy() {
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/z"), ..make("杂志") },
+            AppEntry { path: PathBuf::from("/zh"), ..make("扎志") },
+        ]);
+        e.set_fuzzy_pinyin(vec![FuzzyRule::ZZh]);
+        let hits: Vec<String> = e.search("zhazhi", 10).into_iter().map(|a| a.name).collect();
+        assert_eq!(hits, vec!["扎志", "杂志"]);
+    }
+
+    #[test]
+    fn traditional_full_width_and_diacritics_are_folded() {
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/a"), ..make("微軟") },
+            AppEntry { path: PathBuf::from("/b"), ..make("Ｃｈｒｏｍｅ") },
+            AppEntry { path: PathBuf::from("/c"), ..make("Café Rouge") },
+            AppEntry { path: PathBuf::from("/d"), ..make("微信") },
+        ]);
+        assert_eq!(e.search("微软", 10)[0].name, "微軟");
+        assert_eq!(e.search("weiruan", 10)[0].name, "微軟");
+        assert_eq!(e.search("chrome", 10)[0].name, "Ｃｈｒｏｍｅ");
+        assert_eq!(e.search("cafe", 10)[0].name, "Café Rouge");
+        // query 侧同样折叠
+        assert_eq!(e.search("微信", 10)[0].name, "微信");
+        assert_eq!(e.search("ＣＡＦÉ", 10)[0].name, "Café Rouge");
+
+        // 高亮下标对应原名字符
+        assert_eq!(e.search_hits("chrome", 1)[0].highlights, vec![(0, 6)]);
+        assert_eq!(e.search_hits("cafe", 1)[0].highlights, vec![(0, 4)]);
+    }
+
+    #[test]
+    fn scoring_profile_swaps_without_rebuild() {
+        let e = SearchEngine::new(vec![make("Chrome")]);
+        // "chr" 命中 3/6 个位置，归一化 50：恰好达到默认的通用阈值
+        assert_eq!(e.search("chrxyz", 10).len(), 1);
+
+        e.set_scoring_profile(ScoringProfile::preset(ScoringPreset::Strict));
+        assert!(e.search("chrxyz", 10).is_empty());
+
+        let boosted = ScoringProfile { name_match_bonus: 500, ..ScoringProfile::default() };
+        let before = e.search_hits("chrome", 1)[0].score;
+        e.set_scoring_profile(boosted);
+        assert_eq!(e.search_hits("chrome", 1)[0].score, before + 400);
+        assert_eq!(e.search("chrxyz", 10).len(), 1);
+    }
+
+    #[test]
+    fn exact_alias_ranks_first() {
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/ps"), ..make("Adobe Photoshop 2024") },
+            AppEntry { path: PathBuf::from("/pshell"), ..make("PowerShell") },
+            AppEntry { path: PathBuf::from("/wx"), ..make("WeChat") },
+            AppEntry { path: PathBuf::from("/wxwork"), ..make("Wx Work") },
+        ]);
+        assert_eq!(e.search("ps", 10)[0].name, "PowerShell");
+        assert!(e.search("微信", 10).iter().all(|a| a.name != "Wx Work"));
+
+        e.set_aliases(HashMap::from([
+            (PathBuf::from("/ps"), vec!["ps".to_string()]),
+            (PathBuf::from("/wx"), vec![" 微信 ".to_string(), "vs code".to_string()]),
+        ]));
+        assert_eq!(e.search("ps", 10)[0].name, "Adobe Photoshop 2024");
+        // 别名的拼音同样可搜：全拼 / 首字母完全一致也算精确命中
+        assert_eq!(e.search("wx", 10)[0].name, "WeChat");
+        assert_eq!(e.search("weixin", 10)[0].name, "WeChat");
+        assert_eq!(e.search("微信", 10)[0].name, "WeChat");
+        // 多词别名整体匹配；别名前缀按普通子序列打分
+        assert_eq!(e.search("vs code", 10)[0].name, "WeChat");
+        assert!(e.search("weix", 10).iter().any(|a| a.name == "WeChat"));
+        // 别名不是显示名，不高亮
+        assert!(e.search_hits("ps", 1)[0].highlights.is_empty());
+
+        // 新增 / 替换的条目沿用已设置的别名
+        e.upsert(AppEntry { path: PathBuf::from("/ps"), ..make("Photoshop 2025") });
+        assert_eq!(e.search("ps", 10)[0].name, "Photoshop 2025");
+    }
+
+    #[test]
+    fn desktop_keywords_and_generic_name_are_searchable() {
+        let e = SearchEngine::new(vec![
+            AppEntry {
+                path: PathBuf::from("/usr/share/applications/nautilus.desktop"),
+                source: AppSource::DesktopEntry,
+                // desktop_scanner 把未翻译的 Name 放在关键词首位
+                keywords: vec!["Files".into(), "folder".into(), "manager".into()],
+                ..make("文件")
+            },
+            AppEntry { path: PathBuf::from("/fm"), ..make("Fileman") },
+        ]);
+        let names = |q: &str| -> Vec<String> {
+            e.search(q, 10).into_iter().map(|a| a.name).collect()
+        };
+        assert_eq!(names("files")[0], "文件");
+        assert_eq!(names("folder"), vec!["文件"]);
+        // 关键词 bonus 低于名称：名称命中的条目排在前面
+        assert_eq!(names("file")[0], "Fileman");
+        assert!(e.search_hits("folder", 1)[0].highlights.is_empty());
+    }
+
+    #[test]
+    fn hidden_entries_stay_hidden_across_rescans() {
+        use crate::domain::HiddenRule;
+
+        let e = SearchEngine::new(vec![
+            AppEntry { path: PathBuf::from("/chrome"), ..make("Chrome") },
+            AppEntry { path: PathBuf::from("/unins"), ..make("Uninstall Chrome") },
+        ]);
+        e.set_hidden(HiddenFilter::new(&[
+            HiddenRule::Pattern("Uninstall*".into()),
+            HiddenRule::Path("/crash".into()),
+        ]));
+        let names = |q: &str| -> Vec<String> {
+            e.search(q, 10).into_iter().map(|a| a.name).collect()
+        };
+        assert_eq!(names("chrome"), vec!["Chrome"]);
+
+        // 重新扫描：已隐藏的条目被 upsert 回来、新条目命中规则，都不出现
+        e.apply_delta(
+            &[
+                AppEntry { path: PathBuf::from("/unins"), ..make("Uninstall Chrome") },
+                AppEntry { path: PathBuf::from("/crash"), ..make("Chrome Crash Reporter") },
+            ],
+            &[],
+        );
+        assert_eq!(names("chrome"), vec!["Chrome"]);
+        assert!(e.is_hidden(&AppEntry { path: PathBuf::from("/crash"), ..make("x") }));
+        let paths = ["/chrome", "/crash", "/gone"].map(Path::new);
+        let visible = e.get_visible(&paths);
+        assert_eq!(visible.keys().collect::<Vec<_>>(), vec![&Path::new("/chrome")]);
+
+        // 取消隐藏后无需重扫即可恢复
+        e.set_hidden(HiddenFilter::default());
+        assert_eq!(names("chrome").len(), 3);
+        assert_eq!(e.get_visible(&paths).len(), 2);
+    }
+
+    #[test]
+    fn user_entries_replace_their_source_and_match_keywords() {
+        let user = |name: &str, path: &str, keywords: &[&str]| AppEntry {
+            path: PathBuf::from(path),
+            source: AppSource::UserDefined,
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            ..make(name)
+        };
+        let e = SearchEngine::new(vec![make("Visual Studio Code")]);
+        e.replace_source(
+            AppSource::UserDefined,
+            &[user("Work Project", "user-entry://1", &["repo"]), make("Ignored")],
+        );
+        let names = |q: &str| -> Vec<String> {
+            e.search(q, 10).into_iter().map(|a| a.name).collect()
+        };
+        assert_eq!(names("work"), vec!["Work Project"]);
+        assert_eq!(names("repo"), vec!["Work Project"]);
+        assert!(names("ignored").is_empty());
+
+        e.replace_source(AppSource::UserDefined, &[user("Blog", "user-entry://2", &[])]);
+        assert!(names("work").is_empty());
+        assert_eq!(names("blog"), vec!["Blog"]);
+        assert_eq!(e.len(), 2);
+    }
+
+    /// 生成 `n` 个中英文混合的合成应用名（确定性，便于对比和基准）。
+    fn synthetic_corpus(n: usize) -> Vec<AppEntry> {
+        const WORDS: &[&str] = &[
+            "Google", "Chrome", "Visual", "Studio", "Code", "Fire", "fox", "Thunder", "bird",
+            "Office", "Word", "Excel", "Note", "pad", "Player", "Music", "Photo", "shop",
+            "谷歌", "浏览器", "微软", "办公", "微信", "输入法", "音乐", "播放器",
+        ];
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..n)
+            .map(|i| {
+                let mut name = String::new();
+                for _ in 0..3 {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    name.push_str(WORDS[(seed % WORDS.len() as u64) as usize]);
+                }
+                name.push_str(&(i % 97).to_string());
+                AppEntry {
+                    path: PathBuf::from(format!("/app/{}", i)),
+                    ..make(&name)
+                }
+            })
+            .collect()
+    }
+
+    const PROBE_QUERIES: &[&str] = &[
+        "chrome", "vsc", "gllq", "guge", "微信", "weixin", "wechat", "fire fox", "xyz",
+        "offce", "音乐", "yybfq", "note12", "q", "浏览", "photoshop", "zzzzzz",
+    ];
+
+    #[test]
+    fn prefilter_returns_identical_results() {
+        let e = SearchEngine::new(synthetic_corpus(2_000));
+        let fuzzy = vec![FuzzyRule::ZZh, FuzzyRule::NL, FuzzyRule::InIng];
+        for (scheme, rules) in [
+            (None, Vec::new()),
+            (Some(DoublePinyinScheme::Xiaohe), Vec::new()),
+            (None, fuzzy),
+        ] {
+            e.set_double_pinyin(scheme);
+            e.set_fuzzy_pinyin(rules.clone());
+            for q in PROBE_QUERIES {
+                let filtered = e.ranked_paths(q, 50, true);
+                let full = e.ranked_paths(q, 50, false);
+                assert_eq!(
+                    filtered, full,
+                    "query {:?}（双拼 {:?}，模糊音 {:?}）剪枝前后结果不一致",
+                    q, scheme, rules
+                );
+            }
+        }
+
+        e.set_double_pinyin(None);
+        e.set_fuzzy_pinyin(Vec::new());
+        for preset in ScoringPreset::ALL {
+            e.set_scoring_profile(ScoringProfile::preset(preset));
+            for q in PROBE_QUERIES {
+                assert_eq!(
+                    e.ranked_paths(q, 50, true),
+                    e.ranked_paths(q, 50, false),
+                    "query {:?}（预设 {:?}）剪枝前后结果不一致",
+                    q, preset
+                );
+            }
+        }
+
+        e.set_scoring_profile(ScoringProfile::default());
+        e.set_aliases(HashMap::from([
+            (PathBuf::from("/app/3"), vec!["xyz".to_string(), "微信".to_string()]),
+            (PathBuf::from("/app/7"), vec!["fire fox".to_string()]),
+        ]));
+        for q in PROBE_QUERIES {
+            assert_eq!(
+                e.ranked_paths(q, 50, true),
+                e.ranked_paths(q, 50, false),
+                "query {:?}（别名）剪枝前后结果不一致",
+                q
+            );
+        }
+    }
+
+    /// 10 万条目的延迟基准：`cargo test --release bench_100k -- --ignored --nocapture`
+    ///
+    /// 上限很宽（release 下实测约 0.1 秒），只用来拦住数量级的性能退化。
+    #[test]
+    #[ignore]
+    fn bench_100k() {
+        use std::time::{Duration, Instant};
+
+        const BUDGET: Duration = Duration::from_secs(1);
+
+        let e = SearchEngine::new(synthetic_corpus(100_000));
+        for q in PROBE_QUERIES {
+            let start = Instant::now();
+            let filtered = e.ranked_paths(q, 8, true);
+            let with_prefilter = start.elapsed();
+
+            let start = Instant::now();
+            let full = e.ranked_paths(q, 8, false);
+            let without = start.elapsed();
+
+            assert_eq!(filtered.len(), full.len());
+            println!(
+                "{:>10}  prefilter {:>8.2?}  full {:>8.2?}  hits {}",
+                q,
+                with_prefilter,
+                without,
+                filtered.len()
+            );
+            assert!(with_prefilter < BUDGET, "{:?} 查询耗时 {:?}", q, with_prefilter);
+            assert!(without < BUDGET, "{:?} 全量打分耗时 {:?}", q, without);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! 隐藏过滤：把用户的隐藏规则（[`HiddenRule`]）编译成可逐条判断的过滤器。
+//!
+//! 隐藏只作用于查询：条目仍留在索引缓存中，重新扫描不会让它"复活"，
+//! 取消隐藏后也无需重扫即可恢复。
+//!
+//! 通配符只支持 `*`（任意串，可跨路径分隔符）和 `?`（单个字符）；`\` 与 `/` 视为同一个
+//! 分隔符。与文件系统一致，只在 Windows、macOS 上不区分大小写。
+
+use std::collections::HashSet;
+
+use crate::domain::{AppEntry, HiddenRule};
+
+/// 当前平台的文件系统是否（默认）不区分大小写。
+const FOLD_CASE: bool = cfg!(any(windows, target_os = "macos"));
+
+/// 编译后的隐藏规则。
+#[derive(Debug, Clone, Default)]
+pub struct HiddenFilter {
+    /// 按路径隐藏（分隔符、大小写已归一）
+    paths: HashSet<String>,
+    /// 含分隔符的通配符：匹配完整路径
+    path_patterns: Vec<Vec<char>>,
+    /// 不含分隔符的通配符：匹配显示名或文件名
+    name_patterns: Vec<Vec<char>>,
+    /// 是否不区分大小写
+    fold_case: bool,
+}
+
+impl HiddenFilter {
+    pub fn new(rules: &[HiddenRule]) -> Self {
+        Self::build(rules, FOLD_CASE)
+    }
+
+    fn build(rules: &[HiddenRule], fold_case: bool) -> Self {
+        let mut filter = Self { fold_case, ..Self::default() };
+        for rule in rules {
+            let value = normalize(rule.value().trim(), fold_case);
+            if value.is_empty() {
+                continue;
+            }
+            match rule {
+                HiddenRule::Path(_) => {
+                    filter.paths.insert(value);
+                }
+                HiddenRule::Pattern(_) if value.contains('/') => {
+                    filter.path_patterns.push(value.chars().collect())
+                }
+                HiddenRule::Pattern(_) => filter.name_patterns.push(value.chars().collect()),
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.path_patterns.is_empty() && self.name_patterns.is_empty()
+    }
+
+    /// 条目是否被任一规则隐藏。
+    pub fn hides(&self, entry: &AppEntry) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let path = normalize(&entry.path.to_string_lossy(), self.fold_case);
+        if self.paths.contains(&path) {
+            return true;
+        }
+        let path_chars: Vec<char> = path.chars().collect();
+        if self.path_patterns.iter().any(|p| glob_match(p, &path_chars)) {
+            return true;
+        }
+        if self.name_patterns.is_empty() {
+            return false;
+        }
+        let file_name = entry.path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        let texts = [&entry.name[..], &file_name].map(|t| normalize(t, self.fold_case));
+        texts.iter().any(|text| {
+            let chars: Vec<char> = text.chars().collect();
+            self.name_patterns.iter().any(|p| glob_match(p, &chars))
+        })
+    }
+}
+
+/// 把 `\` 统一为 `/`，`fold_case` 时再转小写。
+fn normalize(text: &str, fold_case: bool) -> String {
+    let text = text.replace('\\', "/");
+    if fold_case {
+        text.to_lowercase()
+    } else {
+        text
+    }
+}
+
+/// 通配符匹配（`*` / `?`），贪心 + 回溯到最近的 `*`，O(m·n) 最坏。
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // 最近一个 `*` 的位置，以及它当前吞到的 text 位置
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    p = sp + 1;
+                    t = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AppSource;
+    use std::path::PathBuf;
+
+    fn app(name: &str, path: &str) -> AppEntry {
+        AppEntry {
+            name: name.into(),
+            path: PathBuf::from(path),
+            source: AppSource::StartMenu,
+            icon: None,
+            keywords: Vec::new(),
+        }
+    }
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_match(&chars("uninstall*"), &chars("uninstall foo")));
+        assert!(glob_match(&chars("*crash*"), &chars("crashpad_handler")));
+        assert!(glob_match(&chars("unins???.exe"), &chars("unins000.exe")));
+        assert!(glob_match(&chars("a*b*c"), &chars("axxbyybzc")));
+        assert!(!glob_match(&chars("a*b*c"), &chars("axxbyy")));
+        assert!(!glob_match(&chars("?"), &chars("")));
+        assert!(glob_match(&chars("*"), &chars("")));
+    }
+
+    #[test]
+    fn rules_match_paths_names_and_file_names() {
+        let filter = HiddenFilter::build(&[
+            HiddenRule::Path("C:\\Apps\\Dup.EXE".into()),
+            HiddenRule::Pattern("Uninstall *".into()),
+            HiddenRule::Pattern("*crash*.exe".into()),
+            HiddenRule::Pattern("*/helpers/*".into()),
+            HiddenRule::Pattern("  ".into()),
+        ], true);
+        assert!(filter.hides(&app("Dup", "c:/apps/dup.exe")));
+        assert!(filter.hides(&app("Uninstall Chrome", "/chrome/uninstall.exe")));
+        assert!(filter.hides(&app("Reporter", "/opt/x/CrashReporter.exe")));
+        assert!(filter.hides(&app("Tool", "D:\\X\\Helpers\\tool.exe")));
+        assert!(!filter.hides(&app("Chrome", "/chrome/chrome.exe")));
+        assert!(HiddenFilter::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn case_sensitive_filesystems_keep_case() {
+        let rules = [
+            HiddenRule::Path("/opt/App/run".into()),
+            HiddenRule::Pattern("*Helper*".into()),
+        ];
+        let filter = HiddenFilter::build(&rules, false);
+        assert!(filter.hides(&app("Run", "/opt/App/run")));
+        assert!(!filter.hides(&app("Run", "/opt/app/run")));
+        assert!(filter.hides(&app("Helper", "/opt/x/h")));
+        assert!(!filter.hides(&app("helper", "/opt/x/h")));
+    }
+}
@@ -1,53 +1,65 @@
 //! 匹配算法原语：子序列模糊匹配、Smith-Waterman 局部序列比对。
 //!
 //! 从 engine.rs 拆分出纯函数，方便独立测试和维护。
+//! 子序列打分常数、各路径的 bonus、比对阈值与 Smith-Waterman 参数见 [`ScoringProfile`]。
 
-// ── 评分权重常量 ──
-/// 名称严格子序列匹配 bonus。
-pub const NAME_MATCH_BONUS: u32 = 100;
-/// 拼音全拼匹配 bonus。
-pub const PINYIN_FULL_BONUS: u32 = 50;
-/// 拼音首字母匹配 bonus。
-pub const PINYIN_INITIALS_BONUS: u32 = 75;
-/// 反向拼音匹配 bonus（中文查询转拼音后匹配英文名）。
-pub const REVERSE_PINYIN_BONUS: u32 = 60;
-/// 目标名包含完整查询拼音的 bonus（极高置信度）。
-pub const CONTAINS_BONUS: u32 = 1150;
-/// 跨语言 Smith-Waterman 匹配 bonus。
-pub const CROSS_LANG_SIM_BONUS: u32 = 40;
-/// 跨语言 Smith-Waterman 归一化阈值（宽松）。
-pub const CROSS_LANG_THRESHOLD: u32 = 25;
-/// 通用 Smith-Waterman 归一化阈值（严格）。
-pub const GENERAL_SIM_THRESHOLD: u32 = 50;
-
-// ── fuzzy_match 评分常量 ──
-/// 匹配率缩放因子。
-const MATCH_RATIO_SCALE: u32 = 100;
-/// 连续匹配 bonus 乘数。
-const CONSECUTIVE_MULTIPLIER: u32 = 10;
-/// 早期位置奖励基数。
-const BASE_SCORE: u32 = 1000;
-/// 首匹配位置惩罚乘数。
-const POSITION_PENALTY: u32 = 10;
-
-// ── Smith-Waterman 算法参数 ──
-/// 字符匹配得分。
-const SW_MATCH: i32 = 3;
-/// 字符错配罚分。
-const SW_MISMATCH: i32 = -1;
-/// 空位罚分。
-const SW_GAP: i32 = -1;
-/// 最少匹配字符数（低于此值视为噪音）。
-const SW_MIN_MATCHES: i32 = 2;
+use crate::search::profile::ScoringProfile;
+
+/// 字符位图：`a-z` 占 0..26 位，`0-9` 占 26..36 位，其余字符按码点散列到 36..64 位。
+///
+/// 散列冲突只会多报"可能包含"，因此基于位图的剪枝是保守的：不会漏掉真实匹配。
+pub fn char_bit(c: char) -> u64 {
+    let bit = match c {
+        'a'..='z' => c as u32 - 'a' as u32,
+        '0'..='9' => 26 + (c as u32 - '0' as u32),
+        _ => 36 + (c as u32 % 28),
+    };
+    1u64 << bit
+}
+
+/// 字符序列的位图（各字符 [`char_bit`] 的并集）。
+pub fn char_mask(chars: &[char]) -> u64 {
+    chars.iter().fold(0, |mask, &c| mask | char_bit(c))
+}
+
+/// [`similarity_match`] 的必要条件：query 中只有 `present` 个位置的字符出现在 target 里时，
+/// 能否达到 `threshold`。
+///
+/// 每个匹配位置最多贡献 `sw_match` 分（错配与空位不加分，见 [`ScoringProfile::validate`]），
+/// 所以比对得分上限是 `present * sw_match`，归一化后与 `sw_match` 的取值无关。
+pub fn similarity_possible(
+    present: usize,
+    query_len: usize,
+    threshold: u32,
+    profile: &ScoringProfile,
+) -> bool {
+    present as i32 >= profile.sw_min_matches && present * 100 >= query_len * threshold as usize
+}
 
 /// 检查文本是否包含中文字符。
 pub fn contains_chinese(text: &str) -> bool {
-    text.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c))
+    text.chars().any(is_cjk)
+}
+
+/// CJK 统一表意文字：基本区、扩展 A–H 区及兼容表意文字。
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4e00}'..='\u{9fff}'       // 基本区
+        | '\u{3400}'..='\u{4dbf}'     // 扩展 A
+        | '\u{20000}'..='\u{2ebef}'   // 扩展 B–F（含 I）
+        | '\u{30000}'..='\u{323af}'   // 扩展 G–H
+        | '\u{f900}'..='\u{faff}'     // 兼容表意文字
+        | '\u{2f800}'..='\u{2fa1f}'   // 兼容表意文字补充
+    )
 }
 
 /// 严格子序列匹配：所有 query 字符必须按序出现在 target 中。
-/// 返回匹配分数，分数越高越好。
-pub fn fuzzy_match(query_chars: &[char], target_chars: &[char]) -> Option<u32> {
+/// 返回匹配分数，分数越高越好；各项常数取自 `profile`。
+pub fn fuzzy_match(
+    query_chars: &[char],
+    target_chars: &[char],
+    profile: &ScoringProfile,
+) -> Option<u32> {
     if query_chars.is_empty() {
         return None;
     }
@@ -77,30 +89,215 @@ pub fn fuzzy_match(query_chars: &[char], target_chars: &[char]) -> Option<u32> {
 
     if query_idx == query_chars.len() {
         let first_pos = first_match_pos.unwrap_or(0) as u32;
-        let match_ratio = (total_matches * MATCH_RATIO_SCALE as usize / query_chars.len()) as u32;
-        let consecutive_bonus = consecutive_matches as u32 * CONSECUTIVE_MULTIPLIER;
-        let score = match_ratio
-            + consecutive_bonus
-            + BASE_SCORE.saturating_sub(first_pos * POSITION_PENALTY);
+        let match_ratio =
+            (total_matches * profile.match_ratio_scale as usize / query_chars.len()) as u32;
+        let consecutive_bonus =
+            (consecutive_matches as u32).saturating_mul(profile.consecutive_multiplier);
+        let position =
+            profile.base_score.saturating_sub(first_pos.saturating_mul(profile.position_penalty));
+        let score = match_ratio.saturating_add(consecutive_bonus).saturating_add(position);
         Some(score)
     } else {
         None
     }
 }
 
+/// 词首标记，按**原始大小写**的字符计算。以下位置视为词首：
+/// - 第 0 个字符
+/// - 分隔符（空格、`-`、`_`、`.` 等非字母数字字符）之后的字母数字
+/// - 小写 → 大写的转折（CamelCase，如 Visual**S**tudio）
+/// - 字母 ↔ 数字的转折（如 Win**3**2、Office**2**016）
+pub fn word_boundaries(chars: &[char]) -> Vec<bool> {
+    (0..chars.len())
+        .map(|i| {
+            if i == 0 {
+                return true;
+            }
+            let (prev, cur) = (chars[i - 1], chars[i]);
+            cur.is_alphanumeric()
+                && (!prev.is_alphanumeric()
+                    || (prev.is_lowercase() && cur.is_uppercase())
+                    || (prev.is_ascii_digit() != cur.is_ascii_digit()))
+        })
+        .collect()
+}
+
+/// 词首对齐 DP 的复用缓冲区：同一 query 给各条目打分时反复使用，避免每条都重新分配。
+#[derive(Debug, Default)]
+pub struct AlignScratch {
+    dp: Vec<i32>,
+    indices: Vec<usize>,
+    best: Vec<usize>,
+}
+
+impl AlignScratch {
+    /// 最近一次成功匹配的对齐下标（升序）。
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+/// 词首感知的子序列匹配：[`fuzzy_match`] 的分数 + 词首 bonus。
+///
+/// `boundaries` 与 `target_chars` 等长（见 [`word_boundaries`]）。在所有合法的子序列对齐中
+/// 选落在词首字符最多的一种（同数时取最靠左），每个词首命中加 `word_boundary_bonus`，
+/// 全部命中词首再加 `acronym_bonus`。返回分数，对齐下标见 [`AlignScratch::indices`]。
+pub fn fuzzy_match_with_boundaries(
+    query_chars: &[char],
+    target_chars: &[char],
+    boundaries: &[bool],
+    profile: &ScoringProfile,
+    scratch: &mut AlignScratch,
+) -> Option<u32> {
+    let base = fuzzy_match(query_chars, target_chars, profile)?;
+    let hits = boundary_alignment(query_chars, target_chars, boundaries, scratch)?;
+
+    let mut score = base.saturating_add((hits as u32).saturating_mul(profile.word_boundary_bonus));
+    if hits == query_chars.len() && hits >= 2 {
+        score = score.saturating_add(profile.acronym_bonus);
+    }
+    Some(score)
+}
+
+/// 词首命中最多的子序列对齐（DP，O(m·n)）。返回词首命中数，对齐下标写入 `scratch.indices`。
+fn boundary_alignment(
+    query_chars: &[char],
+    target_chars: &[char],
+    boundaries: &[bool],
+    scratch: &mut AlignScratch,
+) -> Option<usize> {
+    let (m, n) = (query_chars.len(), target_chars.len());
+    // dp(i, j)：前 i 个 query 字符对齐到前 j 个 target 字符时的最多词首命中；-1 表示无法对齐
+    let width = n + 1;
+    let dp = &mut scratch.dp;
+    dp.clear();
+    dp.resize((m + 1) * width, -1);
+    dp[..width].fill(0);
+    let at = |i: usize, j: usize| i * width + j;
+    for i in 1..=m {
+        for j in i..=n {
+            let mut best = dp[at(i, j - 1)];
+            let diag = dp[at(i - 1, j - 1)];
+            if query_chars[i - 1] == target_chars[j - 1] && diag >= 0 {
+                let hit = boundaries.get(j - 1).copied().unwrap_or(false) as i32;
+                best = best.max(diag + hit);
+            }
+            dp[at(i, j)] = best;
+        }
+    }
+    let total = dp[at(m, n)];
+    if total < 0 {
+        return None;
+    }
+
+    // 回溯：能左移就左移，得到同分中最靠左的对齐
+    let indices = &mut scratch.indices;
+    indices.clear();
+    indices.resize(m, 0);
+    let (mut i, mut j) = (m, n);
+    while i > 0 {
+        if dp[at(i, j - 1)] == dp[at(i, j)] {
+            j -= 1;
+        } else {
+            indices[i - 1] = j - 1;
+            i -= 1;
+            j -= 1;
+        }
+    }
+    Some(total as usize)
+}
+
+/// 从每个词首、以及词中与 query 首字符相同的位置分别尝试 [`fuzzy_match_with_boundaries`]
+/// （位置惩罚相对起点计算），取最高分。
+/// 让多词 query 中的每个词都能对齐到名称里的某个单词或词中片段（如 "shop" → Photo**shop**），
+/// 而不受它在名称中的位置影响；词首起点另有词首 bonus，同样的片段落在词首时排在前面。
+///
+/// 返回分数，对齐下标见 [`AlignScratch::indices`]。
+pub fn fuzzy_match_at_boundaries(
+    query_chars: &[char],
+    target_chars: &[char],
+    boundaries: &[bool],
+    profile: &ScoringProfile,
+    scratch: &mut AlignScratch,
+) -> Option<u32> {
+    let first = *query_chars.first()?;
+    let mut best: Option<u32> = None;
+    let starts = (0..target_chars.len())
+        .filter(|&i| boundaries.get(i) == Some(&true) || target_chars[i] == first);
+    for start in starts {
+        let Some(score) = fuzzy_match_with_boundaries(
+            query_chars,
+            &target_chars[start..],
+            &boundaries[start..],
+            profile,
+            scratch,
+        ) else {
+            continue;
+        };
+        if best.is_none_or(|b| score > b) {
+            best = Some(score);
+            scratch.best.clear();
+            scratch.best.extend(scratch.indices.iter().map(|i| i + start));
+        }
+    }
+    if best.is_some() {
+        std::mem::swap(&mut scratch.indices, &mut scratch.best);
+    }
+    best
+}
+
+/// 与 [`fuzzy_match`] 相同的贪心子序列扫描，返回每个 query 字符命中的 target 下标（用于高亮）。
+pub fn fuzzy_match_indices(query_chars: &[char], target_chars: &[char]) -> Option<Vec<usize>> {
+    if query_chars.is_empty() {
+        return None;
+    }
+
+    let mut indices = Vec::with_capacity(query_chars.len());
+    let mut query_iter = query_chars.iter().peekable();
+    for (i, tc) in target_chars.iter().enumerate() {
+        match query_iter.peek() {
+            Some(&qc) if qc == tc => {
+                indices.push(i);
+                query_iter.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    if indices.len() == query_chars.len() {
+        Some(indices)
+    } else {
+        None
+    }
+}
+
+/// 把升序下标合并成左闭右开区间，如 `[0, 1, 2, 5]` → `[(0, 3), (5, 6)]`。
+pub fn indices_to_ranges(indices: &[usize]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &i in indices {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == i => *end += 1,
+            Some((_, end)) if *end > i => {}
+            _ => ranges.push((i, i + 1)),
+        }
+    }
+    ranges
+}
+
 /// Smith-Waterman 局部序列比对。
 ///
 /// 生物信息学经典算法，用于在两条序列中找到最优局部对齐。
 /// 容忍字符缺失、插入和替换，自动处理罗马化差异（如 "weixin" vs "wechat"）。
 ///
-/// 评分参数：
-/// - 匹配: +3  |  错配: -1  |  空位: -1
+/// 评分参数取自 `profile`（默认 匹配: +3  |  错配: -1  |  空位: -1）。
 ///
 /// `threshold`：归一化分数的最低阈值 (0-100)。跨语言匹配场景可降低阈值（如 25），通用匹配保持较高阈值（如 50）。
 pub fn similarity_match(
     query_chars: &[char],
     target_chars: &[char],
     threshold: u32,
+    profile: &ScoringProfile,
 ) -> Option<u32> {
     if query_chars.is_empty() || target_chars.is_empty() {
         return None;
@@ -115,14 +312,14 @@ pub fn similarity_match(
         let mut curr = vec![0i32; cols];
         for j in 1..cols {
             let s = if query_chars[i - 1] == target_chars[j - 1] {
-                SW_MATCH
+                profile.sw_match
             } else {
-                SW_MISMATCH
+                profile.sw_mismatch
             };
 
             curr[j] = (prev[j - 1] + s)
-                .max(prev[j] + SW_GAP)
-                .max(curr[j - 1] + SW_GAP)
+                .max(prev[j] + profile.sw_gap)
+                .max(curr[j - 1] + profile.sw_gap)
                 .max(0);
 
             max_score = max_score.max(curr[j]);
@@ -131,11 +328,11 @@ pub fn similarity_match(
     }
 
     // 至少匹配 N 个字符，避免单字符误匹配（如 "guge" 匹配 "Adobe Photoshop" 中的 'e'）
-    if max_score < SW_MATCH * SW_MIN_MATCHES {
+    if max_score < profile.sw_match * profile.sw_min_matches {
         return None;
     }
 
-    let max_possible = (query_chars.len() as i32).saturating_mul(SW_MATCH);
+    let max_possible = (query_chars.len() as i32).saturating_mul(profile.sw_match);
     if max_possible == 0 {
         return None;
     }
@@ -158,21 +355,139 @@ mod tests {
 
     #[test]
     fn similarity_match_basic() {
+        let p = ScoringProfile::default();
+        let sim = |q: &str, t: &str, threshold| {
+            similarity_match(&chars(q), &chars(t), threshold, &p)
+        };
         // 完全相同 → 应匹配
-        assert_eq!(
-            similarity_match(&chars("chrome"), &chars("chrome"), GENERAL_SIM_THRESHOLD),
-            Some(100)
-        );
+        assert_eq!(sim("chrome", "chrome", p.general_sim_threshold), Some(100));
         // "weixin" vs "wechat"：共享 "we" 前缀，跨语言匹配阈值应匹配
-        let score = similarity_match(&chars("weixin"), &chars("wechat"), CROSS_LANG_THRESHOLD);
+        let score = sim("weixin", "wechat", p.cross_lang_threshold);
         assert!(score.is_some(), "weixin vs wechat 应匹配, got {:?}", score);
         // 通用匹配阈值下不应匹配（33% < 50%）
-        assert!(
-            similarity_match(&chars("weixin"), &chars("wechat"), GENERAL_SIM_THRESHOLD).is_none()
-        );
+        assert!(sim("weixin", "wechat", p.general_sim_threshold).is_none());
         // 完全不相关 → 不应匹配
-        assert!(
-            similarity_match(&chars("weixin"), &chars("whatsapp"), CROSS_LANG_THRESHOLD).is_none()
+        assert!(sim("weixin", "whatsapp", p.cross_lang_threshold).is_none());
+
+        // 错配罚分更重时，拼写差异较大的比对得分下降
+        let strict = ScoringProfile { sw_mismatch: -3, sw_gap: -3, ..p };
+        let loose = similarity_match(&chars("chrmoe"), &chars("chrome"), 1, &p).unwrap();
+        let tight = similarity_match(&chars("chrmoe"), &chars("chrome"), 1, &strict).unwrap();
+        assert!(tight < loose, "{} >= {}", tight, loose);
+    }
+
+    #[test]
+    fn fuzzy_match_indices_follow_greedy_scan() {
+        assert_eq!(fuzzy_match_indices(&chars("vsc"), &chars("vs code")), Some(vec![0, 1, 3]));
+        assert_eq!(fuzzy_match_indices(&chars("xyz"), &chars("vs code")), None);
+        assert_eq!(indices_to_ranges(&[0, 1, 3]), vec![(0, 2), (3, 4)]);
+        assert_eq!(indices_to_ranges(&[2, 2, 3]), vec![(2, 4)]);
+    }
+
+    #[test]
+    fn word_boundaries_cover_separators_camel_case_and_digits() {
+        let name = chars("VisualStudio-code_v2.exe");
+        let starts: Vec<usize> = word_boundaries(&name)
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &b)| b.then_some(i))
+            .collect();
+        // V, S(tudio), c(ode), v, 2, e(xe)
+        assert_eq!(starts, vec![0, 6, 13, 18, 19, 21]);
+    }
+
+    #[test]
+    fn acronym_alignment_beats_scattered_match() {
+        let p = ScoringProfile::default();
+        let mut scratch = AlignScratch::default();
+        let mut score = |q: &str, name: &str| {
+            let lower = chars(&name.to_lowercase());
+            let boundaries = word_boundaries(&chars(name));
+            fuzzy_match_with_boundaries(&chars(q), &lower, &boundaries, &p, &mut scratch)
+                .map(|score| (score, scratch.indices().to_vec()))
+        };
+
+        let (acronym, indices) = score("vsc", "Visual Studio Code").unwrap();
+        assert_eq!(indices, vec![0, 7, 14]);
+        let (scattered, _) = score("vsc", "Vascular").unwrap();
+        assert!(acronym > scattered, "{} <= {}", acronym, scattered);
+
+        // CamelCase：ps → PhotoShop 优于 Pause
+        assert!(score("ps", "PhotoShop").unwrap().0 > score("ps", "Pause").unwrap().0);
+
+        // 词首 bonus 取自配置：清零后只剩子序列分
+        let flat = ScoringProfile { word_boundary_bonus: 0, acronym_bonus: 0, ..p };
+        let target = chars("Visual Studio Code");
+        let lower = chars("visual studio code");
+        let boundaries = word_boundaries(&target);
+        assert_eq!(
+            fuzzy_match_with_boundaries(&chars("vsc"), &lower, &boundaries, &flat, &mut scratch),
+            fuzzy_match(&chars("vsc"), &lower, &flat)
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn boundary_match_scores_later_words_like_first() {
+        let p = ScoringProfile::default();
+        let target = chars("visual studio code");
+        let boundaries = word_boundaries(&target);
+
+        let mut scratch = AlignScratch::default();
+
+        let at_word =
+            fuzzy_match_at_boundaries(&chars("code"), &target, &boundaries, &p, &mut scratch);
+        assert_eq!(scratch.indices(), [14, 15, 16, 17]);
+        let from_start =
+            fuzzy_match_with_boundaries(&chars("code"), &target, &boundaries, &p, &mut scratch);
+        assert!(at_word.unwrap() > from_start.unwrap());
+    }
+
+    #[test]
+    fn boundary_match_finds_mid_word_hits() {
+        let p = ScoringProfile::default();
+        let mut scratch = AlignScratch::default();
+        let mut score = |q: &str, name: &str| {
+            let lower = chars(&name.to_lowercase());
+            let boundaries = word_boundaries(&chars(name));
+            fuzzy_match_at_boundaries(&chars(q), &lower, &boundaries, &p, &mut scratch)
+                .map(|score| (score, scratch.indices().to_vec()))
+        };
+
+        // 词中片段：对齐到连续的 "shop" / "hop"，而不是从词首散落匹配
+        let (mid_word, indices) = score("shop", "Adobe Photoshop").unwrap();
+        assert_eq!(indices, vec![11, 12, 13, 14]);
+        assert_eq!(score("hop", "Adobe Photoshop").unwrap().1, vec![12, 13, 14]);
+
+        // 同样的片段落在词首时排在前面
+        let (at_word, _) = score("shop", "Shop Manager").unwrap();
+        assert!(at_word > mid_word, "{} <= {}", at_word, mid_word);
+        assert!(score("shop", "Adobe Photoshp").is_none());
+    }
+
+    #[test]
+    fn char_mask_and_similarity_bound() {
+        let p = ScoringProfile::default();
+        let (general, cross_lang) = (p.general_sim_threshold, p.cross_lang_threshold);
+        let mask = char_mask(&chars("chrome"));
+        assert_eq!(mask & char_bit('c'), char_bit('c'));
+        assert_eq!(mask & char_bit('z'), 0);
+        assert_ne!(char_bit('a'), char_bit('0'));
+
+        // "chrome" 自比对 6 个位置全部出现 → 可能达到阈值
+        assert!(similarity_possible(6, 6, general, &p));
+        // 6 个位置只有 2 个出现：上限 6/18 = 33% < 50%
+        assert!(!similarity_possible(2, 6, general, &p));
+        assert!(similarity_possible(2, 6, cross_lang, &p));
+        // 少于 sw_min_matches 个匹配视为噪音
+        assert!(!similarity_possible(1, 1, cross_lang, &p));
+    }
+
+    #[test]
+    fn contains_chinese_covers_extension_ranges() {
+        assert!(contains_chinese("微信"));
+        assert!(contains_chinese("㐀")); // 扩展 A
+        assert!(contains_chinese("𠀀")); // 扩展 B
+        assert!(contains_chinese("豈")); // 兼容表意文字
+        assert!(!contains_chinese("Chrome ｃａｆé"));
+    }
+}
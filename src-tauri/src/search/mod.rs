@@ -8,9 +8,20 @@
 //! - v0.1 Step 5：补 `ranker` 模块做频次 + 时间衰减加权
 
 mod engine;
+mod hidden;
 pub mod matcher;
+mod normalize;
 mod pinyin;
+mod profile;
+mod query_parser;
 mod ranker;
 
 pub use engine::SearchEngine;
-pub use ranker::rerank;
\ No newline at end of file
+pub use hidden::HiddenFilter;
+pub use pinyin::{DoublePinyinScheme, FuzzyRule, PinyinFields};
+pub use profile::{ScoringPreset, ScoringProfile};
+pub use query_parser::{ParsedQuery, QueryParser, QueryScope};
+pub use ranker::{
+    learned_selections, learning_key, learning_prefixes, most_frecent, most_recent,
+    rerank_explained, DEFAULT_HALF_LIFE_SECS,
+};
\ No newline at end of file
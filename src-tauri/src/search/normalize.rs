@@ -0,0 +1,148 @@
+//! 文本归一化：名称与 query 在匹配前统一折叠，使不同写法互相命中。
+//!
+//! - 繁体 → 简体（常用字表，见 [`TRADITIONAL`] / [`SIMPLIFIED`]）
+//! - 全角 → 半角（`Ｃｈｒｏｍｅ` → `Chrome`，全角空格 → 空格）
+//! - 去变音符号（`é` → `e`，`ü` → `u`）
+//!
+//! 所有映射都是单字符 → 单字符，折叠前后字符下标一一对应，高亮无需额外映射。
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::search::matcher;
+
+/// 繁体字（与 [`SIMPLIFIED`] 逐字对应，按码点升序）。
+const TRADITIONAL: &str = "\
+來係個們備傳優兩劇動務員問嗎嚴國圍園圓圖團執報場塊塵壇壓壞壯夢夥奧奪奮妝婦媽嬰孫\
+學實審寫寬寶將專尋對導屆層屬岡島嶺帥師帳幣幫幹幾庫廚廟廠廢廣廳張強彈彎彙後徑從復\
+徵徹恆惡惱愛態慣慮憂憑憶應懶懷懸戀戰戲戶拋捨掃掙掛採揚換揮損搖搶撐撥擁擇擊擋擔據\
+擠擬擴擺擾攔攜攝攤敗敘敵數斷於時晉晝暢暫曆曉曬書會東條極構樂樓標樣樹橋機檔檢權歐\
+歡歲歷歸殘殺殼毀氣汙決沒況淚淺測湯準溝溫滅滬滾滿漁漢漲漸潔潛濕濟濾瀏瀟灑灣災為烏\
+無煙煩熱燈爐爭爺爾牆牽狀狹猶獄獅獎獨獲獸現瑪環產畝畢畫異當疊瘋療發盜盡監盤眾碩確\
+碼磚礙礦禍禮稅種稱穀積穩穫窩窮竊競筆節範築簡簽籃籌籤粵糧糾紀約紅紋納紐純紙級紛細\
+紹終組結絕絡給統絲綁經綜綠綢維綱網緊緒線緣編緩緯練縣縫縮縱總績織繞繩繪繼續纖纜罰\
+罵罷羅義習聖聞聯聰聲聳職聽肅脈脫腦腫腳腸膚膠膽臉臟臨臺與興舉舊艙艦艱莊華萊萬葉蒼\
+蓋蕭薑薦薩藍藝藥蘆蘇蘋蘭處虛號蝕蝦螢蟲蠅蠟蠶衆術衛衝裏補裝裡製複襯襲見規覓視親覺\
+覽觀觸訂計訊討訓託記訣訪設訴診詐評詞詢試詩話該詳誇誌認誕語誠誤說誰課調談請論諸諾\
+謀謂謊謎講謝謹證識譜譯議護譽讀變讓讚豎豐豬貓貝貞負財貢貨販貪貫責貴買貸費貼貿賀資\
+賊賓賞賠賢賣質賬賭賴賺購贈贊贏趕趙趨跡踐躍車軌軍軒軟軸較載輔輕輛輝輩輪輯輸轄轉轎\
+轟辦辭辯農迴這連週進遊運過達違遜遞遠適遲選遺邁還邊邏郵鄉鄭鄰醫醬釀釋釘針釣鈔鈕鈴\
+鉛鉤銀銅銷鋒鋪鋼錄錘錢錦錫錯錶鍋鍛鍵鎖鎮鏈鏡鏢鐘鐳鐵鑄鑑鑰長門閃閉開閒間閘閣閩閱\
+闆闊闖關陝陣陰陳陸陽隊階際隨險隱隸隻雖雙雜雞離難雲電霧靈靜韓韻響頁頂項順須頌預頓\
+領頭頸頻顆題額顏願顛類顧顯風颱飄飛飢飯飲飼飽餅養餓餘館饒馬駐駕駛騎騙騰騷驅驗驚驟\
+驢髒體髮鬆鬍鬥鬧鬱魚魯鮮鯨鳥鳳鳴鴨鴻鵝鶴鷹鹽麗麥麵麼黃點黨齊齒齡龍龜";
+
+/// 与 [`TRADITIONAL`] 逐字对应的简体字。
+const SIMPLIFIED: &str = "\
+来系个们备传优两剧动务员问吗严国围园圆图团执报场块尘坛压坏壮梦伙奥夺奋妆妇妈婴孙\
+学实审写宽宝将专寻对导届层属冈岛岭帅师帐币帮干几库厨庙厂废广厅张强弹弯汇后径从复\
+征彻恒恶恼爱态惯虑忧凭忆应懒怀悬恋战戏户抛舍扫挣挂采扬换挥损摇抢撑拨拥择击挡担据\
+挤拟扩摆扰拦携摄摊败叙敌数断于时晋昼畅暂历晓晒书会东条极构乐楼标样树桥机档检权欧\
+欢岁历归残杀壳毁气污决没况泪浅测汤准沟温灭沪滚满渔汉涨渐洁潜湿济滤浏潇洒湾灾为乌\
+无烟烦热灯炉争爷尔墙牵状狭犹狱狮奖独获兽现玛环产亩毕画异当叠疯疗发盗尽监盘众硕确\
+码砖碍矿祸礼税种称谷积稳获窝穷窃竞笔节范筑简签篮筹签粤粮纠纪约红纹纳纽纯纸级纷细\
+绍终组结绝络给统丝绑经综绿绸维纲网紧绪线缘编缓纬练县缝缩纵总绩织绕绳绘继续纤缆罚\
+骂罢罗义习圣闻联聪声耸职听肃脉脱脑肿脚肠肤胶胆脸脏临台与兴举旧舱舰艰庄华莱万叶苍\
+盖萧姜荐萨蓝艺药芦苏苹兰处虚号蚀虾萤虫蝇蜡蚕众术卫冲里补装里制复衬袭见规觅视亲觉\
+览观触订计讯讨训托记诀访设诉诊诈评词询试诗话该详夸志认诞语诚误说谁课调谈请论诸诺\
+谋谓谎谜讲谢谨证识谱译议护誉读变让赞竖丰猪猫贝贞负财贡货贩贪贯责贵买贷费贴贸贺资\
+贼宾赏赔贤卖质账赌赖赚购赠赞赢赶赵趋迹践跃车轨军轩软轴较载辅轻辆辉辈轮辑输辖转轿\
+轰办辞辩农回这连周进游运过达违逊递远适迟选遗迈还边逻邮乡郑邻医酱酿释钉针钓钞钮铃\
+铅钩银铜销锋铺钢录锤钱锦锡错表锅锻键锁镇链镜镖钟镭铁铸鉴钥长门闪闭开闲间闸阁闽阅\
+板阔闯关陕阵阴陈陆阳队阶际随险隐隶只虽双杂鸡离难云电雾灵静韩韵响页顶项顺须颂预顿\
+领头颈频颗题额颜愿颠类顾显风台飘飞饥饭饮饲饱饼养饿余馆饶马驻驾驶骑骗腾骚驱验惊骤\
+驴脏体发松胡斗闹郁鱼鲁鲜鲸鸟凤鸣鸭鸿鹅鹤鹰盐丽麦面么黄点党齐齿龄龙龟";
+
+/// 带变音符号的拉丁字母 → 基本字母（Latin-1 补充 + 拉丁扩展 A 的常见字母）。
+const DIACRITICS: &[(&str, char)] = &[
+    ("àáâãäåāăą", 'a'),
+    ("ÀÁÂÃÄÅĀĂĄ", 'A'),
+    ("çćĉċč", 'c'),
+    ("ÇĆĈĊČ", 'C'),
+    ("ďđ", 'd'),
+    ("ĎĐ", 'D'),
+    ("èéêëēĕėęě", 'e'),
+    ("ÈÉÊËĒĔĖĘĚ", 'E'),
+    ("ĝğġģ", 'g'),
+    ("ĜĞĠĢ", 'G'),
+    ("ĥħ", 'h'),
+    ("ĤĦ", 'H'),
+    ("ìíîïĩīĭįı", 'i'),
+    ("ÌÍÎÏĨĪĬĮİ", 'I'),
+    ("ĵ", 'j'),
+    ("Ĵ", 'J'),
+    ("ķ", 'k'),
+    ("Ķ", 'K'),
+    ("ĺļľŀł", 'l'),
+    ("ĹĻĽĿŁ", 'L'),
+    ("ñńņňŉ", 'n'),
+    ("ÑŃŅŇ", 'N'),
+    ("òóôõöøōŏő", 'o'),
+    ("ÒÓÔÕÖØŌŎŐ", 'O'),
+    ("ŕŗř", 'r'),
+    ("ŔŖŘ", 'R'),
+    ("śŝşš", 's'),
+    ("ŚŜŞŠ", 'S'),
+    ("ţťŧ", 't'),
+    ("ŢŤŦ", 'T'),
+    ("ùúûüũūŭůűų", 'u'),
+    ("ÙÚÛÜŨŪŬŮŰŲ", 'U'),
+    ("ŵ", 'w'),
+    ("Ŵ", 'W'),
+    ("ýÿŷ", 'y'),
+    ("ÝŶŸ", 'Y'),
+    ("źżž", 'z'),
+    ("ŹŻŽ", 'Z'),
+];
+
+/// 折叠整段文本（逐字符 [`fold_char`]，字符数不变）。
+pub fn fold(text: &str) -> String {
+    text.chars().map(fold_char).collect()
+}
+
+/// 折叠单个字符：繁体 → 简体、全角 → 半角、去变音符号；其余原样返回。
+pub fn fold_char(c: char) -> char {
+    match c {
+        c if c.is_ascii() => c,
+        // 全角 ASCII 区：U+FF01..=U+FF5E 与 U+0021..=U+007E 一一对应
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        c if matcher::is_cjk(c) => simplified(c),
+        c => DIACRITICS
+            .iter()
+            .find(|(from, _)| from.contains(c))
+            .map_or(c, |&(_, to)| to),
+    }
+}
+
+/// 繁体字对应的简体字；不在字表中的字原样返回。
+fn simplified(c: char) -> char {
+    static TABLE: OnceLock<HashMap<char, char>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| TRADITIONAL.chars().zip(SIMPLIFIED.chars()).collect());
+    table.get(&c).copied().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_are_aligned() {
+        assert_eq!(TRADITIONAL.chars().count(), SIMPLIFIED.chars().count());
+        let traditional: Vec<char> = TRADITIONAL.chars().collect();
+        assert!(traditional.windows(2).all(|w| w[0] < w[1]), "繁体字表应按码点升序且无重复");
+        for (from, _) in DIACRITICS {
+            assert!(from.chars().all(|c| fold_char(c).is_ascii()), "{} 应全部折叠为 ASCII", from);
+        }
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold("微軟雲端"), "微软云端");
+        assert_eq!(fold("Ｃｈｒｏｍｅ　１２"), "Chrome 12");
+        assert_eq!(fold("Café Éclair"), "Cafe Eclair");
+        // 简体、ASCII 不变，字符数不变
+        assert_eq!(fold("微信 WeChat"), "微信 WeChat");
+        assert_eq!(fold("體驗").chars().count(), 2);
+    }
+}
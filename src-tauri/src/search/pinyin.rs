@@ -1,15 +1,73 @@
 //! 拼音模块：将中文字符转换为拼音，支持首字母和全拼匹配。
+//!
+//! 多音字（如 重庆 chongqing、银行 yinhang）按读音组合展开为多个变体，
+//! 第一个变体总是每字取首选读音（与 [`to_pinyin`] / [`to_initials`] 一致），
+//! 组合数受 [`MAX_VARIANTS`] 限制。
+//!
+//! 汉字先经 [`normalize::fold_char`] 折叠（繁体按简体取音），ü 统一写作 v（输入法习惯）。
+//!
+//! 双拼（[`DoublePinyinScheme`]）：把 query 按两键一音节解码为候选全拼
+//! （[`decode_double_pinyin`]），再与条目的全拼变体匹配。
+//!
+//! 模糊音（[`FuzzyRule`]）：z/zh、n/l、an/ang 等方言易混音按规则归一化
+//! （[`fuzzy_normalize`]），条目拼音与 query 用同一规则归一后再匹配。
 
-use pinyin::{to_pinyin_vec, Pinyin};
+use std::collections::HashSet;
+use std::sync::OnceLock;
 
-/// 将文本转换为拼音（小写，无音调）。
+use pinyin::{to_pinyin_vec, Pinyin, ToPinyinMulti};
+
+use crate::search::normalize;
+
+/// 拼音转换规则版本。升级 pinyin 字表或修改转换逻辑时递增，
+/// 使持久化的 [`PinyinFields`] 失效并在加载时重新计算。
+pub const PINYIN_TABLE_VERSION: u32 = 3;
+
+/// 单字最多取几个读音（字表中生僻读音排在后面）。
+const MAX_READINGS_PER_CHAR: usize = 4;
+
+/// 一个名称最多展开多少个拼音变体；超出的组合被截断（优先保留首选读音）。
+pub const MAX_VARIANTS: usize = 8;
+
+/// 名称的拼音字段（全拼 + 首字母的各读音变体），可随索引持久化以加速冷启动。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinyinFields {
+    /// 全拼变体，`full[0]` 为首选读音
+    pub full: Vec<String>,
+    /// 首字母变体，`initials[0]` 为首选读音
+    pub initials: Vec<String>,
+    /// 计算时的 [`PINYIN_TABLE_VERSION`]
+    pub version: u32,
+}
+
+impl PinyinFields {
+    /// 按当前转换规则计算。
+    pub fn compute(name: &str) -> Self {
+        Self {
+            full: full_variants(name).into_iter().map(|v| v.text).collect(),
+            initials: initials_variants(name).into_iter().map(|v| v.text).collect(),
+            version: PINYIN_TABLE_VERSION,
+        }
+    }
+
+    /// 是否由当前版本的转换规则生成（否则应重新计算）。
+    pub fn is_current(&self) -> bool {
+        self.version == PINYIN_TABLE_VERSION
+    }
+}
+
+/// 将文本转换为拼音（小写，无音调，ü 写作 v）。
 /// 非中文字符保留原样。
 pub fn to_pinyin(text: &str) -> String {
-    to_pinyin_vec(text, Pinyin::plain).join("").to_lowercase()
+    to_pinyin_vec(&normalize::fold(text), Pinyin::plain)
+        .join("")
+        .to_lowercase()
+        .replace('ü', "v")
 }
 
-/// 提取拼音首字母。
+/// 提取拼音首字母（仅首选读音）。
 /// 非中文字符保留原样。
+#[allow(dead_code)] // 索引改用 PinyinFields 的多音字变体；保留作首选读音的对照
 pub fn to_initials(text: &str) -> String {
     text.chars()
         .map(|c| {
@@ -28,6 +86,516 @@ pub fn to_initials(text: &str) -> String {
         .to_lowercase()
 }
 
+/// 全拼各变体中每个字符对应的原文字符下标，与 [`PinyinFields::full`] 逐项对齐
+/// （用于把拼音命中映射回原文高亮）。
+pub fn full_owners(text: &str) -> Vec<Vec<usize>> {
+    full_variants(text).into_iter().map(|v| v.owners).collect()
+}
+
+/// 首字母各变体中每个字符对应的原文字符下标，与 [`PinyinFields::initials`] 逐项对齐。
+pub fn initials_owners(text: &str) -> Vec<Vec<usize>> {
+    initials_variants(text).into_iter().map(|v| v.owners).collect()
+}
+
+/// 一个读音组合：拼音串 + 其中每个字符对应的原文字符下标。
+#[derive(Default, Clone)]
+struct Variant {
+    text: String,
+    owners: Vec<usize>,
+}
+
+/// 单字的去重读音（无音调小写，ü 写作 v），首个为首选读音；非汉字返回空。
+fn readings(c: char) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    if let Some(multi) = normalize::fold_char(c).to_pinyin_multi() {
+        for reading in multi {
+            let plain = reading.plain().to_lowercase().replace('ü', "v");
+            if !out.contains(&plain) {
+                out.push(plain);
+            }
+            if out.len() == MAX_READINGS_PER_CHAR {
+                break;
+            }
+        }
+    }
+    out
+}
+
+/// 全拼变体。非汉字不参与（与 [`to_pinyin`] 一致）。
+fn full_variants(text: &str) -> Vec<Variant> {
+    let mut variants = vec![Variant::default()];
+    for (i, c) in text.chars().enumerate() {
+        let options = readings(c);
+        if !options.is_empty() {
+            variants = expand(variants, &options, i);
+        }
+    }
+    variants
+}
+
+/// 首字母变体。非汉字原样保留（与 [`to_initials`] 一致）。
+fn initials_variants(text: &str) -> Vec<Variant> {
+    let mut variants = vec![Variant::default()];
+    for (i, c) in text.chars().enumerate() {
+        let mut options: Vec<String> = Vec::new();
+        for reading in readings(c) {
+            let initial: String = reading.chars().take(1).collect();
+            if !options.contains(&initial) {
+                options.push(initial);
+            }
+        }
+        if options.is_empty() {
+            options.push(normalize::fold_char(c).to_lowercase().collect());
+        }
+        variants = expand(variants, &options, i);
+    }
+    variants
+}
+
+/// 把每个已有变体与该字的每个读音组合。外层按读音遍历，
+/// 截断时先保留"该字取首选读音"的全部组合。
+fn expand(variants: Vec<Variant>, options: &[String], owner: usize) -> Vec<Variant> {
+    if options.len() == 1 {
+        return variants
+            .into_iter()
+            .map(|v| push(v, &options[0], owner))
+            .collect();
+    }
+    let mut out = Vec::with_capacity((variants.len() * options.len()).min(MAX_VARIANTS));
+    'outer: for option in options {
+        for v in &variants {
+            if out.len() == MAX_VARIANTS {
+                break 'outer;
+            }
+            out.push(push(v.clone(), option, owner));
+        }
+    }
+    out
+}
+
+fn push(mut variant: Variant, piece: &str, owner: usize) -> Variant {
+    variant.text.push_str(piece);
+    variant.owners.extend(piece.chars().map(|_| owner));
+    variant
+}
+
+// ── 双拼 ──
+
+/// 双拼方案。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoublePinyinScheme {
+    Xiaohe,
+    Microsoft,
+    Ziranma,
+    Sogou,
+}
+
+impl DoublePinyinScheme {
+    /// 解析配置取值（`xiaohe` / `microsoft` / `ziranma` / `sogou`）；
+    /// 空串或未知值返回 None（关闭双拼）。
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "xiaohe" => Some(Self::Xiaohe),
+            "microsoft" => Some(Self::Microsoft),
+            "ziranma" => Some(Self::Ziranma),
+            "sogou" => Some(Self::Sogou),
+            _ => None,
+        }
+    }
+
+    /// 韵母键位。一键多韵母时按常用程度排列。
+    fn finals(self, key: char) -> &'static [&'static str] {
+        match (self, key) {
+            (_, 'a') => &["a"],
+            (_, 'e') => &["e"],
+            (_, 'i') => &["i"],
+            (_, 'u') => &["u"],
+            (_, 'o') => &["uo", "o"],
+            (_, 'q') => &["iu"],
+            (_, 's') => &["ong", "iong"],
+            (_, 'f') => &["en"],
+            (_, 'g') => &["eng"],
+            (_, 'h') => &["ang"],
+            (_, 'j') => &["an"],
+
+            (Self::Xiaohe, 'w') => &["ei"],
+            (Self::Xiaohe, 'r') => &["uan"],
+            (Self::Xiaohe, 't') => &["ue", "ve"],
+            (Self::Xiaohe, 'y') => &["un"],
+            (Self::Xiaohe, 'p') => &["ie"],
+            (Self::Xiaohe, 'd') => &["ai"],
+            (Self::Xiaohe, 'k') => &["ing", "uai"],
+            (Self::Xiaohe, 'l') => &["iang", "uang"],
+            (Self::Xiaohe, 'z') => &["ou"],
+            (Self::Xiaohe, 'x') => &["ia", "ua"],
+            (Self::Xiaohe, 'c') => &["ao"],
+            (Self::Xiaohe, 'v') => &["ui", "v"],
+            (Self::Xiaohe, 'b') => &["in"],
+            (Self::Xiaohe, 'n') => &["iao"],
+            (Self::Xiaohe, 'm') => &["ian"],
+
+            (Self::Ziranma, 'y') => &["ing", "uai"],
+            (Self::Ziranma, 't') => &["ue", "ve"],
+            (Self::Microsoft, 'r') | (Self::Sogou, 'r') => &["uan", "er"],
+            (Self::Microsoft, 't') => &["ue"],
+            (Self::Sogou, 't') => &["ue", "ve"],
+            (Self::Microsoft, 'y') | (Self::Sogou, 'y') => &["uai", "v"],
+            (Self::Microsoft, ';') | (Self::Sogou, ';') => &["ing"],
+            (Self::Microsoft, 'v') => &["ui", "ve"],
+            (_, 'v') => &["ui", "v"],
+            (_, 'w') => &["ia", "ua"],
+            (_, 'r') => &["uan"],
+            (_, 'p') => &["un"],
+            (_, 'd') => &["iang", "uang"],
+            (_, 'k') => &["ao"],
+            (_, 'l') => &["ai"],
+            (_, 'z') => &["ei"],
+            (_, 'x') => &["ie"],
+            (_, 'c') => &["iao"],
+            (_, 'b') => &["ou"],
+            (_, 'n') => &["in"],
+            (_, 'm') => &["ian"],
+            _ => &[],
+        }
+    }
+
+    /// 零声母音节是否以 `o` 键引导（微软、搜狗）；否则首键即韵母首字母（小鹤、自然码）。
+    fn uses_o_for_zero_initial(self) -> bool {
+        matches!(self, Self::Microsoft | Self::Sogou)
+    }
+}
+
+/// 合法的零声母音节。
+const ZERO_INITIAL_SYLLABLES: &[&str] =
+    &["a", "o", "e", "ai", "ei", "ao", "ou", "an", "en", "ang", "eng", "er"];
+
+/// 声母 + 韵母的合法组合：双拼解码时剪掉不存在的音节（如 "xong"），模糊音按它切分音节。
+const VALID_SYLLABLES: &str = "\
+ba bo bai bei bao ban ben bang beng bi bie biao bian bin bing bu \
+pa po pai pei pao pou pan pen pang peng pi pie piao pian pin ping pu \
+ma mo me mai mei mao mou man men mang meng mi mie miao miu mian min ming mu \
+fa fo fei fou fan fen fang feng fu \
+da de dai dei dao dou dan den dang deng dong di die diao diu dian ding du duo dui duan dun \
+ta te tai tao tou tan tang teng tong ti tie tiao tian ting tu tuo tui tuan tun \
+na ne nai nei nao nou nan nen nang neng nong ni nie niao niu nian nin niang ning nu nuo \
+nuan nv nve lv lve \
+la le lai lei lao lou lan lang leng long li lia lie liao liu lian lin liang ling lu luo \
+luan lun \
+ga ge gai gei gao gou gan gen gang geng gong gu gua guo guai gui guan gun guang \
+ka ke kai kei kao kou kan ken kang keng kong ku kua kuo kuai kui kuan kun kuang \
+ha he hai hei hao hou han hen hang heng hong hu hua huo huai hui huan hun huang \
+ji jia jie jiao jiu jian jin jiang jing jiong ju jue juan jun \
+qi qia qie qiao qiu qian qin qiang qing qiong qu que quan qun \
+xi xia xie xiao xiu xian xin xiang xing xiong xu xue xuan xun \
+zha zhe zhi zhai zhei zhao zhou zhan zhen zhang zheng zhong zhu zhua zhuo zhuai zhui zhuan \
+zhun zhuang \
+cha che chi chai chao chou chan chen chang cheng chong chu chua chuo chuai chui chuan chun \
+chuang \
+sha she shi shai shei shao shou shan shen shang sheng shu shua shuo shuai shui shuan shun \
+shuang \
+re ri rao rou ran ren rang reng rong ru rua ruo rui ruan run \
+za ze zi zai zei zao zou zan zen zang zeng zong zu zuo zui zuan zun \
+ca ce ci cai cao cou can cen cang ceng cong cu cuo cui cuan cun \
+sa se si sai sao sou san sen sang seng song su suo sui suan sun \
+ya yo ye yao you yan yin yang ying yong yi yu yue yuan yun \
+wa wo wai wei wan wen wang weng wu";
+
+/// 最长的音节（`zhuang` 等）的字母数。
+const MAX_SYLLABLE_LEN: usize = 6;
+
+/// 全部合法音节及其前缀。
+struct SyllableTable {
+    full: HashSet<&'static str>,
+    prefixes: HashSet<&'static str>,
+}
+
+/// 由 [`VALID_SYLLABLES`] 与 [`ZERO_INITIAL_SYLLABLES`] 构建的音节表，只构建一次。
+fn syllables() -> &'static SyllableTable {
+    static TABLE: OnceLock<SyllableTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let full: HashSet<&'static str> = VALID_SYLLABLES
+            .split_whitespace()
+            .chain(ZERO_INITIAL_SYLLABLES.iter().copied())
+            .collect();
+        let prefixes = full.iter().flat_map(|s| (1..=s.len()).map(move |n| &s[..n])).collect();
+        SyllableTable { full, prefixes }
+    })
+}
+
+/// 一个两键组合可能对应的全拼音节。
+fn decode_pair(scheme: DoublePinyinScheme, k1: char, k2: char) -> Vec<String> {
+    let finals = scheme.finals(k2);
+
+    let zero_initial = if scheme.uses_o_for_zero_initial() {
+        k1 == 'o'
+    } else {
+        matches!(k1, 'a' | 'e' | 'o')
+    };
+    if zero_initial {
+        let mut out: Vec<String> = Vec::new();
+        let mut offer = |syllable: String| {
+            if ZERO_INITIAL_SYLLABLES.contains(&syllable.as_str()) && !out.contains(&syllable) {
+                out.push(syllable);
+            }
+        };
+        if scheme.uses_o_for_zero_initial() {
+            finals.iter().for_each(|f| offer(f.to_string()));
+            if k2 == 'o' {
+                offer("o".to_string());
+            }
+        } else {
+            // aa → a、ai → ai、ah → ang（首键是韵母首字母，次键是完整韵母或韵母键）
+            if k1 == k2 {
+                offer(k1.to_string());
+            }
+            offer(format!("{}{}", k1, k2));
+            finals.iter().filter(|f| f.starts_with(k1)).for_each(|f| offer(f.to_string()));
+        }
+        return out;
+    }
+
+    let Some(initial) = initial_for_key(k1) else {
+        return Vec::new();
+    };
+    finals
+        .iter()
+        .map(|f| format!("{}{}", initial, f))
+        .filter(|syllable| syllables().full.contains(syllable.as_str()))
+        .collect()
+}
+
+/// 声母键位（四种方案一致：v=zh、i=ch、u=sh）。
+fn initial_for_key(key: char) -> Option<&'static str> {
+    const SINGLE: &str = "bpmfdtnlgkhjqxrzcsyw";
+    match key {
+        'v' => Some("zh"),
+        'i' => Some("ch"),
+        'u' => Some("sh"),
+        _ => SINGLE.find(key).map(|i| &SINGLE[i..i + 1]),
+    }
+}
+
+/// 把双拼 query 解码为候选全拼（最多 [`MAX_VARIANTS`] 个）。
+///
+/// 两键一个音节，整个 query 都能解码为合法音节时才视为双拼：含非法键、长度为奇数
+/// 或任一两键组合无法成音时返回空，避免把普通英文 query 误解码。
+pub fn decode_double_pinyin(query: &str, scheme: DoublePinyinScheme) -> Vec<String> {
+    let keys: Vec<char> = query.chars().collect();
+    if keys.len() < 2
+        || !keys.len().is_multiple_of(2)
+        || !keys.iter().all(|c| c.is_ascii_lowercase() || *c == ';')
+    {
+        return Vec::new();
+    }
+
+    let mut candidates = vec![String::new()];
+    for pair in keys.chunks_exact(2) {
+        let options = decode_pair(scheme, pair[0], pair[1]);
+        if options.is_empty() {
+            return Vec::new();
+        }
+        let mut next = Vec::with_capacity((candidates.len() * options.len()).min(MAX_VARIANTS));
+        'outer: for option in &options {
+            for prefix in &candidates {
+                if next.len() == MAX_VARIANTS {
+                    break 'outer;
+                }
+                next.push(format!("{}{}", prefix, option));
+            }
+        }
+        candidates = next;
+    }
+    candidates
+}
+
+// ── 模糊音 ──
+
+/// 模糊音规则：每条规则把一组易混音视为等价。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzyRule {
+    /// z = zh
+    ZZh,
+    /// c = ch
+    CCh,
+    /// s = sh
+    SSh,
+    /// n = l
+    NL,
+    /// an = ang
+    AnAng,
+    /// en = eng
+    EnEng,
+    /// in = ing
+    InIng,
+}
+
+impl FuzzyRule {
+    /// 解析配置取值（`z-zh` / `c-ch` / `s-sh` / `n-l` / `an-ang` / `en-eng` / `in-ing`）。
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "z-zh" => Some(Self::ZZh),
+            "c-ch" => Some(Self::CCh),
+            "s-sh" => Some(Self::SSh),
+            "n-l" => Some(Self::NL),
+            "an-ang" => Some(Self::AnAng),
+            "en-eng" => Some(Self::EnEng),
+            "in-ing" => Some(Self::InIng),
+            _ => None,
+        }
+    }
+
+    /// 卷舌规则对应的平舌声母。
+    fn retroflex(self) -> Option<char> {
+        match self {
+            Self::ZZh => Some('z'),
+            Self::CCh => Some('c'),
+            Self::SSh => Some('s'),
+            _ => None,
+        }
+    }
+
+    /// 后鼻音规则对应的主元音。
+    fn nasal(self) -> Option<char> {
+        match self {
+            Self::AnAng => Some('a'),
+            Self::EnEng => Some('e'),
+            Self::InIng => Some('i'),
+            _ => None,
+        }
+    }
+}
+
+/// 按规则把拼音串归一化：zh → z、l → n、ang → an 等。
+/// 返回归一后的字符，以及每个字符在原串中的下标（用于高亮映射）。
+///
+/// 连续的小写字母先按合法音节切分（音节数最少，末段允许是音节前缀以支持边打边搜），
+/// 规则逐音节应用：卷舌与 n/l 只看声母，后鼻音只看韵尾。这样 `xiangu`（xian gu）与
+/// `xianggu`（xiang gu）归一后一致，不会把后鼻音的 g 和下一音节的声母混在一起。
+/// 无法切分的片段（英文单词、首字母串）按字符逐个应用规则。
+pub fn fuzzy_normalize(chars: &[char], rules: &[FuzzyRule]) -> (Vec<char>, Vec<usize>) {
+    let mut out = Vec::with_capacity(chars.len());
+    let mut sources = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_lowercase() {
+            out.push(chars[i]);
+            sources.push(i);
+            i += 1;
+            continue;
+        }
+        let end = chars[i..]
+            .iter()
+            .position(|c| !c.is_ascii_lowercase())
+            .map_or(chars.len(), |n| i + n);
+        let run = &chars[i..end];
+        match segment_syllables(run) {
+            Some(pieces) => {
+                for (start, stop) in pieces {
+                    let syllable = &run[start..stop];
+                    normalize_syllable(syllable, i + start, rules, &mut out, &mut sources);
+                }
+            }
+            None => normalize_chars(run, i, rules, &mut out, &mut sources),
+        }
+        i = end;
+    }
+    (out, sources)
+}
+
+/// 把拼音串切分为音节数最少的合法音节序列，返回各音节的区间；末段可以是音节前缀。
+/// 音节数相同时靠后的音节取较长的一种（`fangan` → fan gan）。无法切分时返回 None。
+fn segment_syllables(run: &[char]) -> Option<Vec<(usize, usize)>> {
+    let table = syllables();
+    let n = run.len();
+    // best[end]：run[..end] 的最少音节数及最后一个音节的起点
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; n + 1];
+    best[0] = Some((0, 0));
+    let mut piece = String::new();
+    for end in 1..=n {
+        for start in end.saturating_sub(MAX_SYLLABLE_LEN)..end {
+            let Some((count, _)) = best[start] else {
+                continue;
+            };
+            piece.clear();
+            piece.extend(&run[start..end]);
+            let valid = table.full.contains(piece.as_str())
+                || (end == n && table.prefixes.contains(piece.as_str()));
+            if valid && best[end].is_none_or(|(c, _)| count + 1 < c) {
+                best[end] = Some((count + 1, start));
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut end = n;
+    while end > 0 {
+        let (_, start) = best[end]?;
+        pieces.push((start, end));
+        end = start;
+    }
+    pieces.reverse();
+    Some(pieces)
+}
+
+/// 对单个音节应用规则：卷舌声母与 l 只在音节开头替换，`ang/eng/ing` 只在音节末尾替换。
+fn normalize_syllable(
+    syllable: &[char],
+    offset: usize,
+    rules: &[FuzzyRule],
+    out: &mut Vec<char>,
+    sources: &mut Vec<usize>,
+) {
+    let len = syllable.len();
+    let drop_h =
+        syllable.get(1) == Some(&'h') && enabled(rules, FuzzyRule::retroflex, syllable[0]);
+    let drop_g = len >= 3
+        && syllable[len - 2..] == ['n', 'g']
+        && enabled(rules, FuzzyRule::nasal, syllable[len - 3]);
+    for (j, &c) in syllable.iter().enumerate() {
+        if (drop_h && j == 1) || (drop_g && j == len - 1) {
+            continue;
+        }
+        let c = if j == 0 && c == 'l' && rules.contains(&FuzzyRule::NL) { 'n' } else { c };
+        out.push(c);
+        sources.push(offset + j);
+    }
+}
+
+/// 无法切分为音节的片段：按字符逐个应用规则。
+fn normalize_chars(
+    chars: &[char],
+    offset: usize,
+    rules: &[FuzzyRule],
+    out: &mut Vec<char>,
+    sources: &mut Vec<usize>,
+) {
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if next == Some('h') && enabled(rules, FuzzyRule::retroflex, c) {
+            out.push(c);
+            sources.push(offset + i);
+            i += 2;
+        } else if next == Some('n')
+            && chars.get(i + 2) == Some(&'g')
+            && enabled(rules, FuzzyRule::nasal, c)
+        {
+            out.extend([c, 'n']);
+            sources.extend([offset + i, offset + i + 1]);
+            i += 3;
+        } else {
+            out.push(if c == 'l' && rules.contains(&FuzzyRule::NL) { 'n' } else { c });
+            sources.push(offset + i);
+            i += 1;
+        }
+    }
+}
+
+fn enabled(rules: &[FuzzyRule], f: fn(FuzzyRule) -> Option<char>, c: char) -> bool {
+    rules.iter().any(|&r| f(r) == Some(c))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,9 +620,120 @@ mod tests {
         assert_eq!(initials, "ggllq");
     }
 
+    #[test]
+    fn test_pinyin_fields_version() {
+        let fields = PinyinFields::compute("微软");
+        assert_eq!(fields.full, vec!["weiruan"]);
+        assert_eq!(fields.initials, vec!["wr"]);
+        assert!(fields.is_current());
+        assert!(!PinyinFields { version: 0, ..fields }.is_current());
+    }
+
     #[test]
     fn test_initials_mixed() {
         let initials = to_initials("VSCode编辑器");
         assert!(initials.contains("vsc"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_polyphonic_variants() {
+        let fields = PinyinFields::compute("重庆地图");
+        assert_eq!(fields.full[0], to_pinyin("重庆地图"));
+        assert_eq!(fields.initials[0], to_initials("重庆地图"));
+        assert!(fields.full.contains(&"chongqingditu".to_string()));
+        assert!(fields.initials.contains(&"cqdt".to_string()));
+
+        let fields = PinyinFields::compute("银行助手");
+        assert!(fields.full.contains(&"yinhangzhushou".to_string()));
+        assert!(fields.initials.contains(&"yhzs".to_string()));
+    }
+
+    #[test]
+    fn test_variants_are_bounded() {
+        // 每个字两个读音 → 2^5 种组合，截断到 MAX_VARIANTS
+        let fields = PinyinFields::compute("重行长乐会");
+        assert_eq!(fields.full.len(), MAX_VARIANTS);
+        assert_eq!(fields.full[0], to_pinyin("重行长乐会"));
+    }
+
+    #[test]
+    fn test_owners_align_with_output() {
+        let name = "QQ音乐";
+        let fields = PinyinFields::compute(name);
+        let full = full_owners(name);
+        assert_eq!(full.len(), fields.full.len());
+        for (owners, text) in full.iter().zip(&fields.full) {
+            assert_eq!(owners.len(), text.chars().count());
+        }
+        assert_eq!(initials_owners(name)[0], vec![0, 1, 2, 3]);
+        // "yinle"：y-i-n 属于 音(2)，l-e 属于 乐(3)
+        assert_eq!(full[0], vec![2, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_double_pinyin_xiaohe() {
+        let decode = |q| decode_double_pinyin(q, DoublePinyinScheme::Xiaohe);
+        // 谷歌浏览器：gu ge lq lj qi
+        assert_eq!(decode("gugelqljqi"), vec!["gugeliulanqi"]);
+        // 重庆：vs → zhong（zhiong 不合法被剪掉）；qk → qing
+        assert_eq!(decode("vsqk"), vec!["zhongqing"]);
+        // 零声母：ah → ang，aa → a
+        assert_eq!(decode("ahaa"), vec!["anga"]);
+        // 奇数长度不是完整的双拼，不解码
+        assert!(decode("wwx").is_empty());
+    }
+
+    #[test]
+    fn test_double_pinyin_schemes_differ() {
+        // 微信：小鹤 ww xb，微软 wz xn
+        assert_eq!(decode_double_pinyin("wwxb", DoublePinyinScheme::Xiaohe), vec!["weixin"]);
+        assert_eq!(decode_double_pinyin("wzxn", DoublePinyinScheme::Microsoft), vec!["weixin"]);
+        assert_eq!(decode_double_pinyin("wzxn", DoublePinyinScheme::Ziranma), vec!["weixin"]);
+        // 微软/搜狗：; 为 ing，o 引导零声母
+        assert_eq!(decode_double_pinyin("q;", DoublePinyinScheme::Sogou), vec!["qing"]);
+        assert_eq!(decode_double_pinyin("oj", DoublePinyinScheme::Microsoft), vec!["an"]);
+        // 非双拼
+        assert!(decode_double_pinyin("xo", DoublePinyinScheme::Xiaohe).is_empty());
+        assert!(decode_double_pinyin("a1", DoublePinyinScheme::Xiaohe).is_empty());
+        assert_eq!(DoublePinyinScheme::parse("Sogou"), Some(DoublePinyinScheme::Sogou));
+        assert_eq!(DoublePinyinScheme::parse(""), None);
+    }
+
+    #[test]
+    fn test_fuzzy_normalize() {
+        let all = [
+            FuzzyRule::ZZh,
+            FuzzyRule::CCh,
+            FuzzyRule::SSh,
+            FuzzyRule::NL,
+            FuzzyRule::AnAng,
+            FuzzyRule::EnEng,
+            FuzzyRule::InIng,
+        ];
+        let normalize = |s: &str, rules: &[FuzzyRule]| -> String {
+            let chars: Vec<char> = s.chars().collect();
+            fuzzy_normalize(&chars, rules).0.into_iter().collect()
+        };
+
+        assert_eq!(normalize("zhongshan", &all), "zongsan");
+        assert_eq!(normalize("lanqing", &all), "nanqin");
+        assert_eq!(normalize("changcheng", &all), "cancen");
+        // 逐音节应用：香菇 xiang gu 与模糊输入 xian gu 归一后一致
+        assert_eq!(normalize("xianggu", &all), normalize("xiangu", &all));
+        assert_eq!(normalize("xiangu", &[FuzzyRule::AnAng]), "xiangu");
+        // 末段是音节前缀（边打边搜）
+        assert_eq!(normalize("zhangs", &all), "zans");
+        // 无法切分的片段按字符处理
+        assert_eq!(normalize("chrome", &all), "crome");
+        // 只应用启用的规则
+        assert_eq!(normalize("zhangsan", &[FuzzyRule::ZZh]), "zangsan");
+        assert_eq!(normalize("zhangsan", &[]), "zhangsan");
+
+        // 下标映射回原串
+        let chars: Vec<char> = "shang".chars().collect();
+        assert_eq!(fuzzy_normalize(&chars, &all).1, vec![0, 2, 3]);
+
+        assert_eq!(FuzzyRule::parse("N-L"), Some(FuzzyRule::NL));
+        assert_eq!(FuzzyRule::parse("x-y"), None);
+    }
+}
@@ -0,0 +1,254 @@
+//! 打分配置：子序列打分常数、各匹配路径的 bonus、序列比对阈值与 Smith-Waterman 参数。
+//!
+//! 引擎在每次查询开始时读取当前配置（[`SearchEngine::set_scoring_profile`] 即时替换），
+//! 这些参数只参与打分，不影响预计算的搜索字段，换配置无需重建索引。
+//!
+//! [`SearchEngine::set_scoring_profile`]: crate::search::SearchEngine::set_scoring_profile
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// 单项 bonus 上限：防止分数相加溢出，也避免一条路径压过所有排序因素。
+pub const MAX_BONUS: u32 = 10_000;
+
+/// Smith-Waterman 单字符匹配得分上限。
+const MAX_SW_MATCH: i32 = 100;
+
+/// Smith-Waterman 最少匹配字符数上限。
+const MAX_SW_MIN_MATCHES: i32 = 16;
+
+/// 内置预设。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringPreset {
+    /// 只接受可靠的匹配：提高比对阈值、压低模糊路径的 bonus
+    Strict,
+    /// 默认
+    Balanced,
+    /// 尽量给出结果：放宽比对阈值，容忍拼写差异
+    Lenient,
+}
+
+impl ScoringPreset {
+    pub const ALL: [ScoringPreset; 3] = [Self::Strict, Self::Balanced, Self::Lenient];
+
+    /// 解析设置中保存的预设名（`strict` / `balanced` / `lenient`）。
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "strict" => Some(Self::Strict),
+            "balanced" => Some(Self::Balanced),
+            "lenient" => Some(Self::Lenient),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Balanced => "balanced",
+            Self::Lenient => "lenient",
+        }
+    }
+}
+
+/// 打分配置。缺省字段按 [`ScoringPreset::Balanced`] 补齐。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringProfile {
+    /// 名称严格子序列匹配 bonus。
+    pub name_match_bonus: u32,
+    /// 拼音全拼匹配 bonus。
+    pub pinyin_full_bonus: u32,
+    /// 拼音首字母匹配 bonus。
+    pub pinyin_initials_bonus: u32,
+    /// 双拼解码后匹配全拼的 bonus（解码可能有歧义，略低于直接输入全拼）。
+    pub double_pinyin_bonus: u32,
+    /// 模糊音（z/zh、n/l 等归一化后）匹配拼音的 bonus，低于精确拼音。
+    pub fuzzy_pinyin_bonus: u32,
+    /// 反向拼音匹配 bonus（中文查询转拼音后匹配英文名）。
+    pub reverse_pinyin_bonus: u32,
+    /// 目标名包含完整查询拼音的 bonus（极高置信度）。
+    pub contains_bonus: u32,
+    /// 跨语言 Smith-Waterman 匹配 bonus。
+    pub cross_lang_sim_bonus: u32,
+    /// 用户别名（含别名的拼音）子序列匹配 bonus。
+    pub alias_bonus: u32,
+    /// query 与别名（或别名的全拼 / 首字母）完全一致的 bonus，高于其他所有路径。
+    pub alias_exact_bonus: u32,
+    /// 条目关键词（`.desktop` 的 `Keywords`、未翻译的名称等）子序列匹配 bonus，低于名称与别名。
+    pub keyword_bonus: u32,
+    /// 子序列匹配：命中率缩放因子（全部命中时得此分）。
+    pub match_ratio_scale: u32,
+    /// 子序列匹配：末尾连续命中的每个字符的 bonus。
+    pub consecutive_multiplier: u32,
+    /// 子序列匹配：首个命中在第 0 位时的位置分，越靠后越少。
+    pub base_score: u32,
+    /// 子序列匹配：首个命中每后移一位扣除的位置分。
+    pub position_penalty: u32,
+    /// 名称匹配中每个落在词首的 query 字符的 bonus。
+    pub word_boundary_bonus: u32,
+    /// 所有 query 字符都落在词首（缩写匹配，如 `vsc` → Visual Studio Code）的额外 bonus。
+    pub acronym_bonus: u32,
+    /// 跨语言 Smith-Waterman 归一化阈值（1–100，宽松）。
+    pub cross_lang_threshold: u32,
+    /// 通用 Smith-Waterman 归一化阈值（1–100，严格）。
+    pub general_sim_threshold: u32,
+    /// Smith-Waterman 字符匹配得分（正数）。
+    pub sw_match: i32,
+    /// Smith-Waterman 字符错配罚分（≤ 0）。
+    pub sw_mismatch: i32,
+    /// Smith-Waterman 空位罚分（≤ 0）。
+    pub sw_gap: i32,
+    /// Smith-Waterman 最少匹配字符数，低于此值视为噪音（避免单字符误匹配）。
+    pub sw_min_matches: i32,
+}
+
+impl Default for ScoringProfile {
+    fn default() -> Self {
+        Self::preset(ScoringPreset::Balanced)
+    }
+}
+
+impl ScoringProfile {
+    /// 预设对应的配置。
+    pub fn preset(preset: ScoringPreset) -> Self {
+        let balanced = Self {
+            name_match_bonus: 100,
+            pinyin_full_bonus: 50,
+            pinyin_initials_bonus: 75,
+            double_pinyin_bonus: 45,
+            fuzzy_pinyin_bonus: 20,
+            reverse_pinyin_bonus: 60,
+            contains_bonus: 1150,
+            cross_lang_sim_bonus: 40,
+            alias_bonus: 80,
+            alias_exact_bonus: 2000,
+            keyword_bonus: 70,
+            match_ratio_scale: 100,
+            consecutive_multiplier: 10,
+            base_score: 1000,
+            position_penalty: 10,
+            word_boundary_bonus: 30,
+            acronym_bonus: 60,
+            cross_lang_threshold: 25,
+            general_sim_threshold: 50,
+            sw_match: 3,
+            sw_mismatch: -1,
+            sw_gap: -1,
+            sw_min_matches: 2,
+        };
+        match preset {
+            ScoringPreset::Balanced => balanced,
+            ScoringPreset::Strict => Self {
+                fuzzy_pinyin_bonus: 10,
+                cross_lang_sim_bonus: 20,
+                cross_lang_threshold: 40,
+                general_sim_threshold: 70,
+                sw_mismatch: -2,
+                sw_gap: -2,
+                ..balanced
+            },
+            ScoringPreset::Lenient => Self {
+                fuzzy_pinyin_bonus: 35,
+                cross_lang_sim_bonus: 60,
+                cross_lang_threshold: 20,
+                general_sim_threshold: 35,
+                ..balanced
+            },
+        }
+    }
+
+    /// 与某个预设完全一致时返回该预设，否则为自定义配置。
+    pub fn matching_preset(&self) -> Option<ScoringPreset> {
+        ScoringPreset::ALL.into_iter().find(|&p| Self::preset(p) == *self)
+    }
+
+    /// 校验取值范围。序列比对的剪枝（`matcher::similarity_possible`）依赖
+    /// 匹配得分为正、错配与空位不加分，这里一并保证。
+    pub fn validate(&self) -> Result<()> {
+        let bonuses = [
+            ("name_match_bonus", self.name_match_bonus),
+            ("pinyin_full_bonus", self.pinyin_full_bonus),
+            ("pinyin_initials_bonus", self.pinyin_initials_bonus),
+            ("double_pinyin_bonus", self.double_pinyin_bonus),
+            ("fuzzy_pinyin_bonus", self.fuzzy_pinyin_bonus),
+            ("reverse_pinyin_bonus", self.reverse_pinyin_bonus),
+            ("contains_bonus", self.contains_bonus),
+            ("cross_lang_sim_bonus", self.cross_lang_sim_bonus),
+            ("alias_bonus", self.alias_bonus),
+            ("alias_exact_bonus", self.alias_exact_bonus),
+            ("keyword_bonus", self.keyword_bonus),
+            ("match_ratio_scale", self.match_ratio_scale),
+            ("consecutive_multiplier", self.consecutive_multiplier),
+            ("base_score", self.base_score),
+            ("position_penalty", self.position_penalty),
+            ("word_boundary_bonus", self.word_boundary_bonus),
+            ("acronym_bonus", self.acronym_bonus),
+        ];
+        for (name, value) in bonuses {
+            if value > MAX_BONUS {
+                bail!("{} 超出范围: {}（最大 {}）", name, value, MAX_BONUS);
+            }
+        }
+        for (name, value) in [
+            ("cross_lang_threshold", self.cross_lang_threshold),
+            ("general_sim_threshold", self.general_sim_threshold),
+        ] {
+            if !(1..=100).contains(&value) {
+                bail!("{} 应在 1–100 之间: {}", name, value);
+            }
+        }
+        if !(1..=MAX_SW_MATCH).contains(&self.sw_match) {
+            bail!("sw_match 应在 1–{} 之间: {}", MAX_SW_MATCH, self.sw_match);
+        }
+        if !(-MAX_SW_MATCH..=0).contains(&self.sw_mismatch) {
+            bail!("sw_mismatch 应在 -{}–0 之间: {}", MAX_SW_MATCH, self.sw_mismatch);
+        }
+        if !(-MAX_SW_MATCH..=0).contains(&self.sw_gap) {
+            bail!("sw_gap 应在 -{}–0 之间: {}", MAX_SW_MATCH, self.sw_gap);
+        }
+        if !(1..=MAX_SW_MIN_MATCHES).contains(&self.sw_min_matches) {
+            bail!("sw_min_matches 应在 1–{} 之间: {}", MAX_SW_MIN_MATCHES, self.sw_min_matches);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid_and_distinct() {
+        for preset in ScoringPreset::ALL {
+            let profile = ScoringProfile::preset(preset);
+            profile.validate().unwrap();
+            assert_eq!(profile.matching_preset(), Some(preset));
+            assert_eq!(ScoringPreset::parse(preset.as_str()), Some(preset));
+        }
+        let strict = ScoringProfile::preset(ScoringPreset::Strict);
+        let lenient = ScoringProfile::preset(ScoringPreset::Lenient);
+        assert!(strict.general_sim_threshold > lenient.general_sim_threshold);
+
+        let custom = ScoringProfile { contains_bonus: 900, ..ScoringProfile::default() };
+        assert_eq!(custom.matching_preset(), None);
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_values() {
+        let base = ScoringProfile::default();
+        assert!(ScoringProfile { general_sim_threshold: 0, ..base }.validate().is_err());
+        assert!(ScoringProfile { cross_lang_threshold: 101, ..base }.validate().is_err());
+        assert!(ScoringProfile { sw_match: 0, ..base }.validate().is_err());
+        assert!(ScoringProfile { sw_gap: 1, ..base }.validate().is_err());
+        assert!(ScoringProfile { sw_min_matches: 0, ..base }.validate().is_err());
+        assert!(ScoringProfile { acronym_bonus: MAX_BONUS + 1, ..base }.validate().is_err());
+        assert!(ScoringProfile { contains_bonus: MAX_BONUS + 1, ..base }.validate().is_err());
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_balanced() {
+        let profile: ScoringProfile = serde_json::from_str(r#"{"contains_bonus": 800}"#).unwrap();
+        assert_eq!(profile.contains_bonus, 800);
+        assert_eq!(profile.name_match_bonus, ScoringProfile::default().name_match_bonus);
+    }
+}
@@ -0,0 +1,187 @@
+//! query 解析：把用户输入拆成 范围（scope）+ 过滤条件 + 自由文本。
+//!
+//! 语法：
+//! - 开头的 `app:` / `note:` / `todo:` / `file:`（或用户配置的别名）限定搜索范围，
+//!   冒号后可以直接跟文本（`app:chrome`），也可以隔空格
+//! - 任意位置的 `source:<来源>` 只保留指定来源的应用（可出现多次，取并集），
+//!   未指定范围时隐含 `app:`
+//! - 其余部分为自由文本，交给各提供者匹配
+//!
+//! 不认识的 `xxx:` 前缀（如 `C:\`）原样保留在文本中。
+
+use crate::domain::AppSource;
+
+/// 搜索范围：决定查询交给哪些提供者。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryScope {
+    App,
+    Note,
+    Todo,
+    File,
+}
+
+impl QueryScope {
+    /// 解析范围名（即默认前缀关键字）。
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "app" => Some(Self::App),
+            "note" => Some(Self::Note),
+            "todo" => Some(Self::Todo),
+            "file" => Some(Self::File),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::App => "app",
+            Self::Note => "note",
+            Self::Todo => "todo",
+            Self::File => "file",
+        }
+    }
+}
+
+const DEFAULT_SCOPES: [QueryScope; 4] =
+    [QueryScope::App, QueryScope::Note, QueryScope::Todo, QueryScope::File];
+
+/// 来源过滤的关键字。
+const SOURCE_FILTER: &str = "source:";
+
+/// 解析后的 query。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParsedQuery {
+    /// 限定的范围；None 表示搜索全部提供者
+    pub scope: Option<QueryScope>,
+    /// 只保留这些来源的应用；为空表示不限
+    pub sources: Vec<AppSource>,
+    /// 去掉前缀与过滤条件后的文本
+    pub text: String,
+}
+
+impl ParsedQuery {
+    /// 范围是否包含提供者负责的任一 `scopes`。
+    pub fn targets(&self, scopes: &[QueryScope]) -> bool {
+        self.scope.is_none_or(|scope| scopes.contains(&scope))
+    }
+}
+
+/// query 解析器，持有 前缀关键字 → 范围 的映射。
+#[derive(Debug, Clone)]
+pub struct QueryParser {
+    keywords: Vec<(String, QueryScope)>,
+}
+
+impl Default for QueryParser {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl QueryParser {
+    /// 默认关键字（`app` / `note` / `todo` / `file`）加上用户别名；别名不区分大小写。
+    pub fn new(aliases: &[(String, QueryScope)]) -> Self {
+        let keywords = DEFAULT_SCOPES
+            .iter()
+            .map(|&scope| (scope.as_str().to_string(), scope))
+            .chain(aliases.iter().map(|(k, scope)| (k.trim().to_lowercase(), *scope)))
+            .filter(|(k, _)| !k.is_empty())
+            .collect();
+        Self { keywords }
+    }
+
+    pub fn parse(&self, query: &str) -> ParsedQuery {
+        let mut rest = query.trim_start();
+        let mut scope = None;
+        if let Some((prefix, after)) = rest.split_once(':') {
+            let prefix = prefix.to_lowercase();
+            if let Some(&(_, s)) = self.keywords.iter().find(|(k, _)| *k == prefix) {
+                scope = Some(s);
+                rest = after;
+            }
+        }
+
+        let mut sources = Vec::new();
+        let mut words = Vec::new();
+        for word in rest.split_whitespace() {
+            let source = word
+                .get(..SOURCE_FILTER.len())
+                .filter(|p| p.eq_ignore_ascii_case(SOURCE_FILTER))
+                .and_then(|_| parse_source(&word[SOURCE_FILTER.len()..]));
+            match source {
+                Some(source) if !sources.contains(&source) => sources.push(source),
+                Some(_) => {}
+                None => words.push(word),
+            }
+        }
+        if !sources.is_empty() && scope.is_none() {
+            scope = Some(QueryScope::App);
+        }
+
+        ParsedQuery {
+            scope,
+            sources,
+            text: words.join(" "),
+        }
+    }
+}
+
+/// 来源过滤的取值：`start_menu` / `registry` / `user_directory`（`dir`）/ `history` / `desktop`
+/// / `user`（用户自定义条目）。
+fn parse_source(value: &str) -> Option<AppSource> {
+    match value.to_ascii_lowercase().as_str() {
+        "start_menu" | "startmenu" => Some(AppSource::StartMenu),
+        "registry" => Some(AppSource::RegistryUninstall),
+        "user_directory" | "dir" => Some(AppSource::UserDirectory),
+        "history" => Some(AppSource::UserHistory),
+        "desktop" => Some(AppSource::DesktopEntry),
+        "user" => Some(AppSource::UserDefined),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_prefix_and_free_text() {
+        let parser = QueryParser::default();
+        let parsed = parser.parse("app:chrome");
+        assert_eq!(parsed.scope, Some(QueryScope::App));
+        assert_eq!(parsed.text, "chrome");
+
+        let parsed = parser.parse("  Todo:  buy milk ");
+        assert_eq!(parsed.scope, Some(QueryScope::Todo));
+        assert_eq!(parsed.text, "buy milk");
+        assert!(parsed.targets(&[QueryScope::Note, QueryScope::Todo]));
+        assert!(!parsed.targets(&[QueryScope::App]));
+
+        // 不认识的前缀保留为文本
+        let parsed = parser.parse("C:\\Windows");
+        assert_eq!(parsed.scope, None);
+        assert_eq!(parsed.text, "C:\\Windows");
+        assert!(parsed.targets(&[QueryScope::File]));
+    }
+
+    #[test]
+    fn source_filters_imply_app_scope() {
+        let parser = QueryParser::default();
+        let parsed = parser.parse("vs source:dir code SOURCE:desktop source:dir");
+        assert_eq!(parsed.scope, Some(QueryScope::App));
+        assert_eq!(parsed.sources, vec![AppSource::UserDirectory, AppSource::DesktopEntry]);
+        assert_eq!(parsed.text, "vs code");
+
+        // 无法识别的来源按普通文本处理
+        assert_eq!(parser.parse("source:nowhere").text, "source:nowhere");
+    }
+
+    #[test]
+    fn aliases_extend_default_keywords() {
+        let parser = QueryParser::new(&[("YY".to_string(), QueryScope::App)]);
+        let parsed = parser.parse("yy:wx");
+        assert_eq!(parsed.scope, Some(QueryScope::App));
+        assert_eq!(parsed.text, "wx");
+        assert_eq!(parser.parse("note:x").scope, Some(QueryScope::Note));
+    }
+}
@@ -1,100 +1,352 @@
 //! 频次加权排序器：对搜索引擎的结果做二次排序。
 //!
-//! 纯函数，不依赖 IO —— 由 bridge 层组装 frequency_map 后传入。
+//! 纯函数，不依赖 IO —— 由 bridge 层组装启动记录后传入。
+//!
+//! 使用频率按 frecency 计：每次启动贡献 `0.5^(距今时长 / 半衰期)`，
+//! 最近常用的应用靠前，很久不用的应用逐渐回落。
+//!
+//! 选择学习：记住每个 query 前缀下用户实际打开的应用（[`learning_prefixes`]），
+//! 同一前缀再次出现时大幅提升这些应用，即使别的应用文本匹配更好。
+//!
+//! 空 query 时没有文本分数，首页直接按最近启动（[`most_recent`]）和 frecency
+//! （[`most_frecent`]）列出应用。
 
 use std::collections::HashMap;
 
-use crate::domain::AppEntry;
+use crate::config::DEFAULT_FRECENCY_HALF_LIFE_DAYS;
+use crate::search::engine::SearchHit;
+
+/// 默认半衰期（秒），由配置项默认值 [`DEFAULT_FRECENCY_HALF_LIFE_DAYS`] 换算。
+pub const DEFAULT_HALF_LIFE_SECS: u64 = DEFAULT_FRECENCY_HALF_LIFE_DAYS as u64 * 24 * 3600;
+
+/// frecency 加分权重：`FRECENCY_WEIGHT * log2(1 + frecency)`。
+/// 约 10 次近期启动 ≈ +280，足以越过同一 query 下多数文本分差，又不会压过明显更好的匹配。
+const FRECENCY_WEIGHT: f64 = 80.0;
+
+/// 选择学习加分权重：`LEARNED_WEIGHT * log2(1 + 该前缀下的选择次数)`。
+/// 选过一次 +400，足以越过单字符 query 下的大多数文本分差。
+const LEARNED_WEIGHT: f64 = 400.0;
+
+/// 参与选择学习的前缀最大长度（字符）。更长的 query 已足够精确，不需要学习。
+const MAX_LEARNED_PREFIX_CHARS: usize = 32;
+
+/// 选择学习的 query 归一化：去首尾空白、小写、连续空白合并为一个空格。
+pub fn learning_key(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
 
-/// 对搜索结果按频次加权重排序。
+/// 一次选择要记录的全部前缀：`"chr"` → `["c", "ch", "chr"]`。
 ///
-/// 算法：`final_score = bonus = 1 + log2(count + 1)`
-/// - 无频次记录 → count=0 → bonus=1.0 → 保持原始顺序
-/// - 有频次 → bonus > 1 → 乘性提升，对数衰减避免垄断
+/// 记录所有前缀，之后只输入其中较短的前缀时同样能命中；以空格结尾的前缀跳过。
+pub fn learning_prefixes(query: &str) -> Vec<String> {
+    let key = learning_key(query);
+    let mut prefixes = Vec::new();
+    let mut prefix = String::new();
+    for c in key.chars().take(MAX_LEARNED_PREFIX_CHARS) {
+        prefix.push(c);
+        if c != ' ' {
+            prefixes.push(prefix.clone());
+        }
+    }
+    prefixes
+}
+
+/// 当前 query 下选过的应用：没有完全一致的已学习前缀时退回到最长的已学习前缀
+/// （query 超过 [`MAX_LEARNED_PREFIX_CHARS`] 或多打了几个字时仍能用上已学到的选择）。
+pub fn learned_selections<'a>(
+    learned: &'a HashMap<String, Vec<(String, u32)>>,
+    query: &str,
+) -> &'a [(String, u32)] {
+    let key = learning_key(query);
+    let ends: Vec<usize> = key.char_indices().map(|(i, c)| i + c.len_utf8()).collect();
+    ends.iter()
+        .rev()
+        .find_map(|&end| learned.get(&key[..end]))
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// 带时间衰减的使用频率。
+///
+/// * `launches` — 启动时间戳（秒）
+/// * `now` — 当前时间戳（秒）；晚于 `now` 的启动（时钟回拨）按刚刚启动计
+/// * `half_life_secs` — 半衰期（秒），0 视为 1 秒
+pub fn frecency(launches: &[u64], now: u64, half_life_secs: u64) -> f64 {
+    let half_life = half_life_secs.max(1) as f64;
+    launches
+        .iter()
+        .map(|&t| 0.5f64.powf(now.saturating_sub(t) as f64 / half_life))
+        .sum()
+}
+
+/// 一条结果在重排序中获得的加分明细。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RankBoost {
+    /// 带时间衰减的使用频率（见 [`frecency`]）
+    pub frecency: f64,
+    /// `FRECENCY_WEIGHT * log2(1 + frecency)`
+    pub frecency_bonus: f64,
+    /// 当前 query 下选过该应用的次数
+    pub selections: u32,
+    /// `LEARNED_WEIGHT * log2(1 + selections)`
+    pub learned_bonus: f64,
+    /// 重排序使用的最终分数：文本分 + 两项加分
+    pub final_score: f64,
+}
+
+/// 对搜索结果按文本分数 + frecency + 选择学习重排序。
+///
+/// 算法：`final_score = text_score + FRECENCY_WEIGHT * log2(1 + frecency)
+///                     + LEARNED_WEIGHT * log2(1 + 选择次数)`
+/// - 无启动/选择记录 → 加分为 0 → 保持引擎的文本排序
+/// - 有记录 → 加法提升，对数衰减避免垄断；同分保持原顺序
+///
+/// 返回按最终分数降序的 (在 `results` 中的下标, 加分明细)，加分明细供打分解释使用。
 ///
 /// # 参数
 /// * `results` — 搜索引擎的原始结果（已按文本分数排序）
-/// * `frequency_map` — 应用路径 → 启动次数 的映射
-pub fn rerank<'a>(
-    results: &'a [AppEntry],
-    frequency_map: &HashMap<String, u32>,
-) -> Vec<&'a AppEntry> {
-    let mut scored: Vec<(&AppEntry, f64)> = results
+/// * `launches` — 应用路径 → 启动时间戳 的映射
+/// * `learned` — 当前 query（[`learning_key`]）下选过的 (应用路径, 次数)
+/// * `now` / `half_life_secs` — 见 [`frecency`]
+pub fn rerank_explained(
+    results: &[SearchHit],
+    launches: &HashMap<String, Vec<u64>>,
+    learned: &[(String, u32)],
+    now: u64,
+    half_life_secs: u64,
+) -> Vec<(usize, RankBoost)> {
+    let mut scored: Vec<(usize, RankBoost)> = results
         .iter()
-        .map(|app| {
-            let count = frequency_map
-                .get(app.path.to_string_lossy().as_ref())
-                .copied()
-                .unwrap_or(0);
-            let bonus = 1.0 + (count as f64 + 1.0).log2();
-            (app, bonus)
+        .enumerate()
+        .map(|(i, hit)| {
+            let path = hit.entry.path.to_string_lossy();
+            let frecency = launches
+                .get(path.as_ref())
+                .map(|l| frecency(l, now, half_life_secs))
+                .unwrap_or(0.0);
+            let selections = learned
+                .iter()
+                .find(|(p, _)| *p == path)
+                .map_or(0, |&(_, count)| count);
+            let frecency_bonus = FRECENCY_WEIGHT * (1.0 + frecency).log2();
+            let learned_bonus = LEARNED_WEIGHT * (1.0 + selections as f64).log2();
+            let boost = RankBoost {
+                frecency,
+                frecency_bonus,
+                selections,
+                learned_bonus,
+                final_score: hit.score as f64 + frecency_bonus + learned_bonus,
+            };
+            (i, boost)
         })
         .collect();
 
-    // 如果所有条目的频次 bonus 都相同，跳过排序
-    let needs_sort = scored
-        .windows(2)
-        .any(|w| (w[0].1 - w[1].1).abs() > f64::EPSILON);
+    // 稳定排序：同分保持引擎顺序
+    scored.sort_by(|a, b| {
+        b.1.final_score
+            .partial_cmp(&a.1.final_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    scored
+}
 
-    if needs_sort {
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    }
-    scored.into_iter().map(|(app, _)| app).collect()
+/// 按最近一次启动时间降序排列的 (应用路径, 时间戳)，供空 query 首页的"最近使用"；
+/// 同一时刻按路径排序，保证结果稳定。
+pub fn most_recent(launches: &HashMap<String, Vec<u64>>) -> Vec<(&str, u64)> {
+    let mut recent: Vec<(&str, u64)> = launches
+        .iter()
+        .filter_map(|(path, l)| Some((path.as_str(), *l.iter().max()?)))
+        .collect();
+    recent.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    recent
+}
+
+/// 按 [`frecency`] 降序排列的 (应用路径, frecency)，供空 query 首页的"常用"；同分按路径排序。
+pub fn most_frecent(
+    launches: &HashMap<String, Vec<u64>>,
+    now: u64,
+    half_life_secs: u64,
+) -> Vec<(&str, f64)> {
+    let mut frequent: Vec<(&str, f64)> = launches
+        .iter()
+        .filter(|(_, l)| !l.is_empty())
+        .map(|(path, l)| (path.as_str(), frecency(l, now, half_life_secs)))
+        .collect();
+    frequent.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(b.0))
+    });
+    frequent
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::AppSource;
+    use crate::domain::{AppEntry, AppSource};
     use std::path::PathBuf;
 
-    fn make(name: &str, path: &str) -> AppEntry {
-        AppEntry {
-            name: name.into(),
-            path: PathBuf::from(path),
-            source: AppSource::StartMenu,
+    const DAY: u64 = 24 * 3600;
+    const NOW: u64 = 1_000 * DAY;
+
+    fn make(name: &str, path: &str, score: u32) -> SearchHit {
+        SearchHit {
+            entry: AppEntry {
+                name: name.into(),
+                path: PathBuf::from(path),
+                source: AppSource::StartMenu,
+                icon: None,
+                keywords: Vec::new(),
+            },
+            score,
+            highlights: Vec::new(),
         }
     }
 
+    /// 重排序后的名称顺序。
+    fn ranked_names(
+        results: &[SearchHit],
+        launches: &HashMap<String, Vec<u64>>,
+        learned: &[(String, u32)],
+        now: u64,
+        half_life_secs: u64,
+    ) -> Vec<String> {
+        rerank_explained(results, launches, learned, now, half_life_secs)
+            .into_iter()
+            .map(|(i, _)| results[i].entry.name.clone())
+            .collect()
+    }
+
     #[test]
     fn empty_results_returns_empty() {
         let map = HashMap::new();
-        let results: Vec<AppEntry> = vec![];
-        assert!(rerank(&results, &map).is_empty());
+        let results: Vec<SearchHit> = vec![];
+        assert!(ranked_names(&results, &map, &[], NOW, DEFAULT_HALF_LIFE_SECS).is_empty());
     }
 
     #[test]
     fn no_frequency_preserves_order() {
-        let a = make("A", "/a");
-        let b = make("B", "/b");
-        let results = vec![a, b];
+        let results = vec![make("A", "/a", 1000), make("B", "/b", 1000)];
         let map = HashMap::new();
-        let out = rerank(&results, &map);
-        assert_eq!(out[0].name, "A");
-        assert_eq!(out[1].name, "B");
+        let out = ranked_names(&results, &map, &[], NOW, DEFAULT_HALF_LIFE_SECS);
+        assert_eq!(out, vec!["A", "B"]);
     }
 
     #[test]
     fn frequent_app_moves_up() {
-        let a = make("A", "/a");
-        let b = make("B", "/b");
-        let results = vec![a, b];
+        let results = vec![make("A", "/a", 1000), make("B", "/b", 1000)];
         let mut map = HashMap::new();
-        map.insert("/b".to_string(), 100);
-        let out = rerank(&results, &map);
-        assert_eq!(out[0].name, "B");
-        assert_eq!(out[1].name, "A");
+        map.insert("/b".to_string(), vec![NOW; 10]);
+        let out = ranked_names(&results, &map, &[], NOW, DEFAULT_HALF_LIFE_SECS);
+        assert_eq!(out, vec!["B", "A"]);
     }
 
     #[test]
     fn log_dampening_prevents_monopoly() {
-        let a = make("A", "/a");
-        let b = make("B", "/b");
-        let results = vec![a, b];
+        let results = vec![make("A", "/a", 1000), make("B", "/b", 1000)];
+        let mut map = HashMap::new();
+        map.insert("/a".to_string(), vec![NOW; 2]);
+        map.insert("/b".to_string(), vec![NOW; 32]);
+        let out = ranked_names(&results, &map, &[], NOW, DEFAULT_HALF_LIFE_SECS);
+        assert_eq!(out, vec!["B", "A"]);
+
+        // 文本分数明显更高的结果不会被使用频率压过
+        let results = vec![make("Exact", "/a", 1500), make("Weak", "/b", 1000)];
         let mut map = HashMap::new();
-        map.insert("/a".to_string(), 2);
-        map.insert("/b".to_string(), 10000);
-        let out = rerank(&results, &map);
-        assert_eq!(out[0].name, "B");
+        map.insert("/b".to_string(), vec![NOW; 32]);
+        let out = ranked_names(&results, &map, &[], NOW, DEFAULT_HALF_LIFE_SECS);
+        assert_eq!(out, vec!["Exact", "Weak"]);
+    }
+
+    #[test]
+    fn old_launches_decay() {
+        // 每个半衰期贡献减半
+        assert!((frecency(&[NOW], NOW, 14 * DAY) - 1.0).abs() < 1e-9);
+        assert!((frecency(&[NOW - 14 * DAY], NOW, 14 * DAY) - 0.5).abs() < 1e-9);
+        // 时钟回拨：未来的启动按刚刚启动计
+        assert!((frecency(&[NOW + DAY], NOW, 14 * DAY) - 1.0).abs() < 1e-9);
+
+        // 很久以前频繁使用 < 最近偶尔使用
+        let results = vec![make("Old", "/old", 1000), make("Recent", "/recent", 1000)];
+        let mut map = HashMap::new();
+        map.insert("/old".to_string(), vec![NOW - 120 * DAY; 20]);
+        map.insert("/recent".to_string(), vec![NOW - DAY; 3]);
+        let out = ranked_names(&results, &map, &[], NOW, DEFAULT_HALF_LIFE_SECS);
+        assert_eq!(out, vec!["Recent", "Old"]);
+
+        // 半衰期更长时旧的使用记录仍然占优
+        let out = ranked_names(&results, &map, &[], NOW, 365 * DAY);
+        assert_eq!(out, vec!["Old", "Recent"]);
+    }
+
+    #[test]
+    fn learned_selection_beats_better_text_match() {
+        let results = vec![make("Calculator", "/calc", 1300), make("Chrome", "/chrome", 1100)];
+        let launches = HashMap::new();
+        let learned = vec![("/chrome".to_string(), 2)];
+        let out = ranked_names(&results, &launches, &learned, NOW, DEFAULT_HALF_LIFE_SECS);
+        assert_eq!(out, vec!["Chrome", "Calculator"]);
+    }
+
+    #[test]
+    fn explained_boost_adds_up_to_final_score() {
+        let results = vec![make("Calculator", "/calc", 1300), make("Chrome", "/chrome", 1100)];
+        let mut launches = HashMap::new();
+        launches.insert("/chrome".to_string(), vec![NOW]);
+        let learned = vec![("/chrome".to_string(), 1)];
+        let out = rerank_explained(&results, &launches, &learned, NOW, DEFAULT_HALF_LIFE_SECS);
+
+        assert_eq!(out.iter().map(|&(i, _)| i).collect::<Vec<_>>(), vec![1, 0]);
+        let (_, chrome) = out[0];
+        assert!((chrome.frecency - 1.0).abs() < 1e-9);
+        assert!((chrome.frecency_bonus - FRECENCY_WEIGHT).abs() < 1e-9);
+        assert_eq!(chrome.selections, 1);
+        assert!((chrome.final_score - (1100.0 + FRECENCY_WEIGHT + LEARNED_WEIGHT)).abs() < 1e-9);
+        assert_eq!(out[1].1, RankBoost { final_score: 1300.0, ..RankBoost::default() });
+    }
+
+    #[test]
+    fn home_lists_sort_by_last_launch_and_frecency() {
+        let mut launches = HashMap::new();
+        launches.insert("/daily".to_string(), vec![NOW - 3 * DAY, NOW - 2 * DAY, NOW - DAY]);
+        launches.insert("/once".to_string(), vec![NOW - 60]);
+        launches.insert("/old".to_string(), vec![NOW - 90 * DAY; 8]);
+        launches.insert("/empty".to_string(), Vec::new());
+
+        let recent: Vec<&str> = most_recent(&launches).into_iter().map(|(p, _)| p).collect();
+        assert_eq!(recent, vec!["/once", "/daily", "/old"]);
+
+        let frequent = most_frecent(&launches, NOW, DEFAULT_HALF_LIFE_SECS);
+        let paths: Vec<&str> = frequent.iter().map(|&(p, _)| p).collect();
+        assert_eq!(paths, vec!["/daily", "/once", "/old"]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn learning_prefixes_cover_every_prefix() {
+        assert_eq!(learning_prefixes("  Chr "), vec!["c", "ch", "chr"]);
+        assert_eq!(
+            learning_prefixes("vs  code"),
+            vec!["v", "vs", "vs c", "vs co", "vs cod", "vs code"]
+        );
+        assert_eq!(learning_key(" VS   Code "), "vs code");
+        assert!(learning_prefixes("   ").is_empty());
+    }
+
+    #[test]
+    fn learned_selections_fall_back_to_longest_prefix() {
+        let learned = HashMap::from([
+            ("c".to_string(), vec![("/code".to_string(), 1)]),
+            ("chr".to_string(), vec![("/chrome".to_string(), 2)]),
+        ]);
+        assert_eq!(learned_selections(&learned, "CHR"), &[("/chrome".to_string(), 2)]);
+        // 没有完全一致的前缀：取最长的已学习前缀 "chr"，而不是 "c"
+        assert_eq!(learned_selections(&learned, "chrom"), &[("/chrome".to_string(), 2)]);
+        assert_eq!(learned_selections(&learned, "cx"), &[("/code".to_string(), 1)]);
+        assert!(learned_selections(&learned, "x").is_empty());
+        assert!(learned_selections(&learned, "").is_empty());
+    }
+}
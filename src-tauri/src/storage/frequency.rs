@@ -1,10 +1,12 @@
-//! 使用频次存储：记录每个应用的启动次数。
+//! 使用频次存储：记录每个应用的启动次数与最近的启动时间。
 //!
 //! 表结构：
-//! - key: 应用路径（String）
-//! - value: 启动次数（u32）
+//! - `frequency`：应用路径（String）→ 累计启动次数（u32）
+//! - `launch_history`：应用路径（String）→ 最近 [`MAX_LAUNCH_HISTORY`] 次启动的时间戳
+//!   （秒，u64 小端序连续存放，升序），供排序器计算带时间衰减的 frecency
 
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use redb::{Database, ReadableTable, TableDefinition};
@@ -13,6 +15,12 @@ use tracing::{debug, info};
 /// 频次表定义。
 const FREQUENCY: TableDefinition<&str, u32> = TableDefinition::new("frequency");
 
+/// 启动时间表定义。
+const LAUNCH_HISTORY: TableDefinition<&str, &[u8]> = TableDefinition::new("launch_history");
+
+/// 每个应用保留的启动时间条数；更早的启动对 frecency 的贡献已衰减到可忽略。
+pub const MAX_LAUNCH_HISTORY: usize = 32;
+
 /// 频次存储访问器。
 ///
 /// 使用 `Arc<Database>` 共享数据库引用，避免悬垂指针。
@@ -28,29 +36,49 @@ impl FrequencyStore {
         Self { db }
     }
 
-    /// 初始化频次表（仅在首次运行时创建，已有则跳过）。
+    /// 初始化频次表与启动时间表（仅在首次运行时创建，已有则跳过）。
+    ///
+    /// 旧版本只有累计次数：迁移时把每个应用记为在迁移时刻启动了 `min(次数, 上限)` 次，
+    /// 之后随时间正常衰减。
     pub fn init_table(db: &Database) -> Result<()> {
         // 检查表是否已存在，避免不必要的写事务
         let exists = db
             .begin_read()
             .ok()
-            .and_then(|txn| txn.open_table(FREQUENCY).ok())
+            .and_then(|txn| txn.open_table(LAUNCH_HISTORY).ok())
             .is_some();
 
         if exists {
             return Ok(());
         }
 
+        let now = now_secs();
         let write_txn = db.begin_write()?;
         {
-            let _table = write_txn.open_table(FREQUENCY)?;
+            let counts = write_txn.open_table(FREQUENCY)?;
+            let mut history = write_txn.open_table(LAUNCH_HISTORY)?;
+            let mut migrated = 0;
+            for entry in counts.iter()? {
+                let (key, value) = entry?;
+                let launches = vec![now; (value.value() as usize).min(MAX_LAUNCH_HISTORY)];
+                history.insert(key.value(), encode_launches(&launches).as_slice())?;
+                migrated += 1;
+            }
+            if migrated > 0 {
+                info!(count = migrated, "已为旧频次数据生成启动时间");
+            }
         }
         write_txn.commit()?;
         Ok(())
     }
 
-    /// 记录一次应用启动。
+    /// 记录一次应用启动（当前时间）。
     pub fn record_launch(&self, app_path: &str) -> Result<()> {
+        self.record_launch_at(app_path, now_secs())
+    }
+
+    /// 记录一次发生在 `at`（秒）的应用启动：累计次数 +1，追加启动时间并截断到上限。
+    fn record_launch_at(&self, app_path: &str, at: u64) -> Result<()> {
         let write_txn = self.db.begin_write()?;
 
         {
@@ -58,6 +86,17 @@ impl FrequencyStore {
             let current = table.get(app_path)?.map(|v| v.value()).unwrap_or(0);
             let new_count = current + 1;
             table.insert(app_path, &new_count)?;
+
+            let mut history = write_txn.open_table(LAUNCH_HISTORY)?;
+            let mut launches = history
+                .get(app_path)?
+                .map(|v| decode_launches(v.value()))
+                .unwrap_or_default();
+            launches.push(at);
+            if launches.len() > MAX_LAUNCH_HISTORY {
+                launches.drain(..launches.len() - MAX_LAUNCH_HISTORY);
+            }
+            history.insert(app_path, encode_launches(&launches).as_slice())?;
         }
 
         write_txn.commit()?;
@@ -66,6 +105,22 @@ impl FrequencyStore {
         Ok(())
     }
 
+    /// 获取所有应用的最近启动时间。
+    ///
+    /// 返回 (应用路径, 启动时间戳列表（秒，升序）) 的列表。
+    pub fn get_launch_history(&self) -> Result<Vec<(String, Vec<u64>)>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(LAUNCH_HISTORY)?;
+
+        let mut results = Vec::new();
+        for entry in table.iter()? {
+            let (key, value) = entry?;
+            results.push((key.value().to_string(), decode_launches(value.value())));
+        }
+
+        Ok(results)
+    }
+
     /// 获取应用的启动次数。
     pub fn get_count(&self, app_path: &str) -> Result<u32> {
         let read_txn = self.db.begin_read()?;
@@ -110,6 +165,26 @@ impl FrequencyStore {
     }
 }
 
+/// 当前时间戳（秒）。
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn encode_launches(launches: &[u64]) -> Vec<u8> {
+    launches.iter().flat_map(|t| t.to_le_bytes()).collect()
+}
+
+/// 解码启动时间；末尾不足 8 字节的残片忽略。
+fn decode_launches(bytes: &[u8]) -> Vec<u64> {
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,4 +249,48 @@ mod tests {
         assert_eq!(store.get_count("/app/chrome").unwrap(), 5);
         assert_eq!(store.get_count("/app/vscode").unwrap(), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_launch_history_is_capped() {
+        let (store, _temp_dir) = temp_db();
+
+        for t in 0..(MAX_LAUNCH_HISTORY as u64 + 5) {
+            store.record_launch_at("/app/a", 1_000 + t).unwrap();
+        }
+
+        let history = store.get_launch_history().unwrap();
+        assert_eq!(history.len(), 1);
+        let launches = &history[0].1;
+        assert_eq!(launches.len(), MAX_LAUNCH_HISTORY);
+        assert_eq!(launches.first(), Some(&1_005), "应丢弃最早的启动");
+        assert_eq!(store.get_count("/app/a").unwrap(), MAX_LAUNCH_HISTORY as u32 + 5);
+    }
+
+    #[test]
+    fn test_legacy_counts_are_migrated() {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "sparknova_freq_migrate_{:?}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&temp_dir).unwrap();
+        let db = Arc::new(Database::create(temp_dir.join("test.db")).unwrap());
+
+        // 旧版本：只有频次表
+        let write_txn = db.begin_write().unwrap();
+        {
+            let mut table = write_txn.open_table(FREQUENCY).unwrap();
+            table.insert("/app/old", &3).unwrap();
+        }
+        write_txn.commit().unwrap();
+
+        FrequencyStore::init_table(&db).unwrap();
+        let store = FrequencyStore::new(db);
+        let history = store.get_launch_history().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].1.len(), 3);
+        assert_eq!(store.get_count("/app/old").unwrap(), 3);
+    }
+}
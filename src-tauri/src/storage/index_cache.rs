@@ -1,34 +1,80 @@
 //! 应用索引缓存：存储扫描到的应用列表，实现冷启动秒加载。
 //!
+//! 表结构：
+//! - `index_entries`：key = 规范化的应用路径（[`entry_key`]），value = [`super::index_codec`]
+//!   编码的单条记录（原始路径、名称、来源、图标、关键词、预计算拼音），增删改均为单 key 操作。
+//!   拼音随条目一起重写，名称变化即失效；拼音规则升级后由版本号判定过期
+//! - `index_cache`：扫描元数据（`last_scan_time`）；旧版整表 JSON 快照
+//!   `index_snapshot` 在 [`IndexCache::init_table`] 中自动迁移后删除
+//!
 //! 支持增量更新：
 //! - 保存上次扫描时间 (`last_scan_time`)
 //! - 提供方法判断是否需要重新扫描
+//! - 按来源对账（[`IndexCache::reconcile`]）：新增、改名、移除已卸载的条目
+//!
+//! 用户隐藏的条目照常保存在缓存里，隐藏规则另存于 SQLite、由搜索引擎在查询时过滤，
+//! 因此重新扫描、合并（[`IndexCache::merge_new_entries`]）都不会让它重新出现。
 
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use anyhow::{Context, Result};
-use redb::{Database, TableDefinition};
+use anyhow::Result;
+use redb::{Database, ReadableTable, TableDefinition};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{info, warn};
 
+use super::index_codec::{self, IndexRecord};
 use crate::domain::{AppEntry, AppSource};
+use crate::search::PinyinFields;
 
 const INDEX_CACHE: TableDefinition<&str, &str> = TableDefinition::new("index_cache");
+const INDEX_ENTRIES: TableDefinition<&str, &[u8]> = TableDefinition::new("index_entries");
 const LAST_SCAN_TIME_KEY: &str = "last_scan_time";
+/// 旧版整表 JSON 快照的 key，仅用于迁移。
+const LEGACY_SNAPSHOT_KEY: &str = "index_snapshot";
 const SCAN_INTERVAL_SECS: u64 = 3600; // 默认1小时后才重新全量扫描
 
+/// 旧版 JSON 快照中的条目格式，仅用于迁移。
 #[derive(Serialize, Deserialize)]
 struct SerializedEntry {
     name: String,
     path: String,
     source: AppSource,
+    #[serde(default)]
+    icon: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
 }
 
 pub struct IndexCache {
     db: Arc<Database>,
 }
 
+/// 一次对账的变更明细，可直接回放到内存中的 `SearchEngine`。
+#[derive(Debug, Default, Clone)]
+pub struct ReconcileReport {
+    /// 新出现的条目
+    pub added: Vec<AppEntry>,
+    /// 已从来源中消失而被移除的条目路径
+    pub removed: Vec<PathBuf>,
+    /// 名称/图标/关键词/来源有变化的条目（新值）
+    pub updated: Vec<AppEntry>,
+}
+
+impl ReconcileReport {
+    /// 是否有任何变化。
+    pub fn is_changed(&self) -> bool {
+        !(self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty())
+    }
+
+    /// 需要 upsert 到搜索引擎的条目（新增 + 更新）。
+    pub fn upserts(&self) -> Vec<AppEntry> {
+        self.added.iter().chain(&self.updated).cloned().collect()
+    }
+}
+
 impl IndexCache {
     pub fn new(db: Arc<Database>) -> Self {
         Self { db }
@@ -38,46 +84,137 @@ impl IndexCache {
         let exists = db
             .begin_read()
             .ok()
-            .and_then(|txn| txn.open_table(INDEX_CACHE).ok())
-            .is_some();
+            .map(|txn| {
+                txn.open_table(INDEX_CACHE).is_ok() && txn.open_table(INDEX_ENTRIES).is_ok()
+            })
+            .unwrap_or(false);
 
-        if exists {
-            return Ok(());
+        if !exists {
+            let write_txn = db.begin_write()?;
+            {
+                let _cache = write_txn.open_table(INDEX_CACHE)?;
+                let _entries = write_txn.open_table(INDEX_ENTRIES)?;
+            }
+            write_txn.commit()?;
         }
 
+        Self::migrate_snapshot(db)
+    }
+
+    /// 把旧版 `index_snapshot` JSON 快照拆成逐条记录（一次性，迁移后删除旧 key）。
+    fn migrate_snapshot(db: &Database) -> Result<()> {
+        let json = {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(INDEX_CACHE)?;
+            match table.get(LEGACY_SNAPSHOT_KEY)? {
+                Some(v) => v.value().to_string(),
+                None => return Ok(()),
+            }
+        };
+
+        // 解析失败只丢弃旧快照，下次扫描会重建
+        let entries: Vec<AppEntry> = match serde_json::from_str::<Vec<SerializedEntry>>(&json) {
+            Ok(serialized) => serialized
+                .into_iter()
+                .map(|s| AppEntry {
+                    name: s.name,
+                    path: PathBuf::from(s.path),
+                    source: s.source,
+                    icon: s.icon,
+                    keywords: s.keywords,
+                })
+                .collect(),
+            Err(e) => {
+                warn!(error = %e, "旧索引快照解析失败，丢弃");
+                Vec::new()
+            }
+        };
+
         let write_txn = db.begin_write()?;
         {
-            let _table = write_txn.open_table(INDEX_CACHE)?;
+            let mut table = write_txn.open_table(INDEX_ENTRIES)?;
+            for entry in &entries {
+                let value = index_codec::encode(&to_record(entry));
+                table.insert(entry_key(&entry.path).as_str(), value.as_slice())?;
+            }
+            let mut cache = write_txn.open_table(INDEX_CACHE)?;
+            cache.remove(LEGACY_SNAPSHOT_KEY)?;
         }
         write_txn.commit()?;
+
+        info!(count = entries.len(), "旧索引快照已迁移为逐条存储");
         Ok(())
     }
 
+    /// 整体替换索引内容。
     pub fn save(&self, entries: &[AppEntry]) -> Result<()> {
-        let serialized: Vec<SerializedEntry> = entries
-            .iter()
-            .map(|e| SerializedEntry {
-                name: e.name.clone(),
-                path: e.path.to_string_lossy().to_string(),
-                source: e.source,
-            })
-            .collect();
+        let write_txn = self.db.begin_write()?;
+        {
+            write_txn.delete_table(INDEX_ENTRIES)?;
+            let mut table = write_txn.open_table(INDEX_ENTRIES)?;
+            for entry in entries {
+                let value = index_codec::encode(&to_record(entry));
+                table.insert(entry_key(&entry.path).as_str(), value.as_slice())?;
+            }
+        }
+        write_txn.commit()?;
 
-        let json = serde_json::to_string(&serialized).context("序列化索引失败")?;
+        info!(count = entries.len(), "保存索引");
+        Ok(())
+    }
 
-        let write_txn = self.db.begin_write()?;
+    /// 插入或覆盖单条记录。
+    pub fn upsert(&self, entry: &AppEntry) -> Result<()> {
+        self.write_changes(std::slice::from_ref(entry), &[])
+    }
 
-        {
-            let mut table = write_txn.open_table(INDEX_CACHE)?;
-            table.insert("index_snapshot", json.as_str())?;
+    /// 删除单条记录。
+    pub fn remove(&self, path: &Path) -> Result<()> {
+        self.write_changes(&[], &[path.to_path_buf()])
+    }
+
+    /// 在一个写事务里应用一批 upsert + remove（拼音字段按当前规则重新计算）。
+    pub fn write_changes(&self, upserts: &[AppEntry], removed: &[PathBuf]) -> Result<()> {
+        if upserts.is_empty() && removed.is_empty() {
+            return Ok(());
         }
 
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(INDEX_ENTRIES)?;
+            for path in removed {
+                table.remove(entry_key(path).as_str())?;
+            }
+            for entry in upserts {
+                let value = index_codec::encode(&to_record(entry));
+                table.insert(entry_key(&entry.path).as_str(), value.as_slice())?;
+            }
+        }
         write_txn.commit()?;
-
-        info!(count = entries.len(), "保存索引快照");
         Ok(())
     }
 
+    /// 按当前规则重算并回写拼音字段，只改写仍在缓存中的记录：读出记录之后被对账或文件监听
+    /// 移除的条目不会被写回来。返回实际刷新的条数。
+    pub fn refresh_existing(&self, entries: &[AppEntry]) -> Result<usize> {
+        let mut refreshed = 0;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(INDEX_ENTRIES)?;
+            for entry in entries {
+                let key = entry_key(&entry.path);
+                if table.get(key.as_str())?.is_none() {
+                    continue;
+                }
+                let value = index_codec::encode(&to_record(entry));
+                table.insert(key.as_str(), value.as_slice())?;
+                refreshed += 1;
+            }
+        }
+        write_txn.commit()?;
+        Ok(refreshed)
+    }
+
     /// 获取上次扫描时间（秒级时间戳）
     pub fn get_last_scan_time(&self) -> Result<u64> {
         let read_txn = self.db.begin_read()?;
@@ -126,62 +263,201 @@ impl IndexCache {
         Ok(now.saturating_sub(last_scan))
     }
 
+    /// 追加单条记录（路径已存在则忽略）。
     pub fn append(&self, entry: &AppEntry) -> Result<()> {
-        let mut entries = self.load().unwrap_or_default();
-        if entries.iter().any(|e| e.path == entry.path) {
+        if self.contains(&entry.path)? {
             return Ok(());
         }
-        entries.push(entry.clone());
-        self.save(&entries)
+        self.upsert(entry)
+    }
+
+    /// 是否已有指定路径的记录。
+    pub fn contains(&self, path: &Path) -> Result<bool> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(INDEX_ENTRIES)?;
+        Ok(table.get(entry_key(path).as_str())?.is_some())
     }
     
-    /// 合并新条目到缓存（去重，基于路径）
+    /// 合并新条目到缓存（去重，基于路径）。只增不删，全量扫描请用 [`Self::reconcile`]。
+    /// 被隐藏的条目同样写入缓存（隐藏在查询时生效，见模块文档）。
+    #[allow(dead_code)] // 全量扫描已改走 reconcile；保留给只追加的场景
     pub fn merge_new_entries(&self, new_entries: &[AppEntry]) -> Result<Vec<AppEntry>> {
         let mut existing = self.load().unwrap_or_default();
-        let existing_paths: std::collections::HashSet<_> = existing.iter()
+        let existing_paths: HashSet<_> = existing.iter()
             .map(|e| e.path.clone())
             .collect();
         
-        let mut added_count = 0;
-        for entry in new_entries {
-            if !existing_paths.contains(&entry.path) {
-                existing.push(entry.clone());
-                added_count += 1;
-            }
-        }
-        
-        if added_count > 0 {
-            self.save(&existing)?;
-            info!(added = added_count, "合并新条目到索引缓存");
+        let added: Vec<AppEntry> = new_entries
+            .iter()
+            .filter(|e| !existing_paths.contains(&e.path))
+            .cloned()
+            .collect();
+
+        if !added.is_empty() {
+            self.write_changes(&added, &[])?;
+            info!(added = added.len(), "合并新条目到索引缓存");
+            existing.extend(added);
         }
         
         Ok(existing)
     }
 
+    /// 用一次全量扫描结果对账缓存。
+    ///
+    /// - `sources`：本次**成功**扫描的来源。只有这些来源的旧条目会被判定为"已消失"，
+    ///   扫描失败的来源保持原样，避免一次失败清空索引
+    /// - `scanned`：这些来源产出的全部条目（按路径去重，先出现者优先）
+    ///
+    /// `UserHistory`（"用过即学"）条目从不被移除或覆盖。
+    pub fn reconcile(
+        &self,
+        sources: &[AppSource],
+        scanned: &[AppEntry],
+    ) -> Result<(Vec<AppEntry>, ReconcileReport)> {
+        let existing = self.load().unwrap_or_default();
+        let (merged, report) = reconcile_entries(existing, sources, scanned);
+
+        if report.is_changed() {
+            self.write_changes(&report.upserts(), &report.removed)?;
+            info!(
+                added = report.added.len(),
+                removed = report.removed.len(),
+                updated = report.updated.len(),
+                "索引缓存对账完成"
+            );
+        }
+
+        Ok((merged, report))
+    }
+
     pub fn load(&self) -> Result<Vec<AppEntry>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(INDEX_CACHE)?;
+        let entries: Vec<AppEntry> =
+            self.load_records()?.into_iter().map(|r| r.entry).collect();
+        info!(count = entries.len(), "加载索引");
+        Ok(entries)
+    }
 
-        let json = match table.get("index_snapshot")? {
-            Some(v) => v.value().to_string(),
-            None => return Ok(Vec::new()),
-        };
+    /// 加载全部记录（含预计算拼音字段）。拼音字段可能已过期（见 [`PinyinFields::is_current`]），
+    /// 由调用方重算并回写。
+    ///
+    /// 损坏或其他编码版本的记录视为缓存未命中：删除这些记录并清零上次扫描时间，
+    /// 下次检查（[`Self::needs_scan`]）时重新扫描补回。
+    pub fn load_records(&self) -> Result<Vec<IndexRecord>> {
+        let mut records = Vec::new();
+        let mut invalid = Vec::new();
+        {
+            let read_txn = self.db.begin_read()?;
+            let table = read_txn.open_table(INDEX_ENTRIES)?;
+            for item in table.iter()? {
+                let (key, value) = item?;
+                match index_codec::decode(value.value()) {
+                    Ok(record) => records.push(record),
+                    Err(e) => {
+                        warn!(path = key.value(), error = %e, "丢弃无法解码的索引记录");
+                        invalid.push(key.value().to_string());
+                    }
+                }
+            }
+        }
+
+        if !invalid.is_empty() {
+            let write_txn = self.db.begin_write()?;
+            {
+                let mut table = write_txn.open_table(INDEX_ENTRIES)?;
+                for key in &invalid {
+                    table.remove(key.as_str())?;
+                }
+                let mut cache = write_txn.open_table(INDEX_CACHE)?;
+                cache.insert(LAST_SCAN_TIME_KEY, "0")?;
+            }
+            write_txn.commit()?;
+            info!(count = invalid.len(), "索引缓存未命中，等待重新扫描");
+        }
+        Ok(records)
+    }
+}
 
-        let serialized: Vec<SerializedEntry> =
-            serde_json::from_str(&json).context("解析索引缓存失败")?;
+/// 表 key：路径的规范形式，同一文件的不同写法得到同一个 key。
+///
+/// 去掉多余的分隔符与 `.`；Windows 上统一为 `\` 并折叠大小写（文件系统不区分大小写，
+/// `C:\Apps\X.lnk` 与 `c:/apps/x.lnk` 是同一条目）。
+fn entry_key(path: &Path) -> String {
+    canonical_key(path, cfg!(windows))
+}
 
-        let entries: Vec<AppEntry> = serialized
-            .into_iter()
-            .map(|s| AppEntry {
-                name: s.name,
-                path: std::path::PathBuf::from(s.path),
-                source: s.source,
-            })
-            .collect();
+fn canonical_key(path: &Path, windows: bool) -> String {
+    let normalized: PathBuf = path.components().collect();
+    let key = normalized.to_string_lossy();
+    if windows {
+        key.replace('/', "\\").to_lowercase()
+    } else {
+        key.into_owned()
+    }
+}
 
-        info!(count = entries.len(), "加载索引快照");
-        Ok(entries)
+/// 为条目计算持久化记录（预计算拼音，冷启动时免去重复转换）。
+fn to_record(entry: &AppEntry) -> IndexRecord {
+    IndexRecord {
+        pinyin: PinyinFields::compute(&entry.name),
+        entry: entry.clone(),
+    }
+}
+
+/// 对账纯函数：不做 IO，便于单测。
+fn reconcile_entries(
+    existing: Vec<AppEntry>,
+    sources: &[AppSource],
+    scanned: &[AppEntry],
+) -> (Vec<AppEntry>, ReconcileReport) {
+    let mut report = ReconcileReport::default();
+
+    // 扫描结果按规范化路径去重，先出现者优先（与来源注册顺序一致）
+    let mut fresh: HashMap<String, &AppEntry> = HashMap::new();
+    for entry in scanned {
+        fresh.entry(entry_key(&entry.path)).or_insert(entry);
+    }
+
+    let mut merged = Vec::with_capacity(existing.len().max(fresh.len()));
+    let mut kept_paths = HashSet::new();
+
+    for old in existing {
+        let key = entry_key(&old.path);
+        if old.source == AppSource::UserHistory {
+            kept_paths.insert(key);
+            merged.push(old);
+            continue;
+        }
+        match fresh.get(&key) {
+            Some(new) => {
+                if old.name != new.name
+                    || old.source != new.source
+                    || old.icon != new.icon
+                    || old.keywords != new.keywords
+                {
+                    report.updated.push((*new).clone());
+                    merged.push((*new).clone());
+                } else {
+                    merged.push(old);
+                }
+                kept_paths.insert(key);
+            }
+            // 本次未扫描的来源：原样保留
+            None if !sources.contains(&old.source) => {
+                kept_paths.insert(key);
+                merged.push(old);
+            }
+            None => report.removed.push(old.path),
+        }
+    }
+
+    for entry in scanned {
+        if kept_paths.insert(entry_key(&entry.path)) {
+            merged.push(entry.clone());
+            report.added.push(entry.clone());
+        }
     }
+
+    (merged, report)
 }
 
 #[cfg(test)]
@@ -218,11 +494,15 @@ mod tests {
                 name: "Chrome".to_string(),
                 path: std::path::PathBuf::from("/app/chrome"),
                 source: AppSource::StartMenu,
+                icon: None,
+                keywords: Vec::new(),
             },
             AppEntry {
                 name: "VSCode".to_string(),
                 path: std::path::PathBuf::from("/app/vscode"),
                 source: AppSource::RegistryUninstall,
+                icon: None,
+                keywords: Vec::new(),
             },
         ];
 
@@ -241,4 +521,158 @@ mod tests {
         let loaded = cache.load().unwrap();
         assert!(loaded.is_empty());
     }
-}
\ No newline at end of file
+
+    fn entry(name: &str, path: &str, source: AppSource) -> AppEntry {
+        AppEntry {
+            name: name.to_string(),
+            path: std::path::PathBuf::from(path),
+            source,
+            icon: None,
+            keywords: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_reconcile_add_remove_update() {
+        let (cache, _temp_dir) = temp_db();
+        cache
+            .save(&[
+                entry("Chrome", "/app/chrome", AppSource::StartMenu),
+                entry("OldApp", "/app/old", AppSource::StartMenu),
+                entry("Code", "/app/code", AppSource::StartMenu),
+            ])
+            .unwrap();
+
+        let scanned = vec![
+            entry("Google Chrome", "/app/chrome", AppSource::StartMenu),
+            entry("Code", "/app/code", AppSource::StartMenu),
+            entry("NewApp", "/app/new", AppSource::StartMenu),
+        ];
+        let (merged, report) = cache.reconcile(&[AppSource::StartMenu], &scanned).unwrap();
+
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.removed, vec![std::path::PathBuf::from("/app/old")]);
+        assert_eq!(report.updated[0].name, "Google Chrome");
+        let names: Vec<&str> = merged.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Google Chrome", "Code", "NewApp"]);
+        assert_eq!(cache.load().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_reconcile_keeps_unscanned_sources_and_history() {
+        let existing = vec![
+            entry("Learned", "/app/learned", AppSource::UserHistory),
+            entry("Green", "/app/green", AppSource::UserDirectory),
+            entry("Gone", "/app/gone", AppSource::StartMenu),
+        ];
+
+        let (merged, report) = reconcile_entries(existing, &[AppSource::StartMenu], &[]);
+
+        assert_eq!(report.removed.len(), 1);
+        let paths: Vec<_> = merged.iter().map(|e| e.path.clone()).collect();
+        assert!(paths.contains(&std::path::PathBuf::from("/app/learned")));
+        assert!(paths.contains(&std::path::PathBuf::from("/app/green")));
+    }
+
+    #[test]
+    fn test_reconcile_dedups_scanned_paths() {
+        let scanned = vec![
+            entry("A", "/app/a", AppSource::StartMenu),
+            entry("A dup", "/app/a", AppSource::UserDirectory),
+        ];
+        let (merged, report) = reconcile_entries(Vec::new(), &[AppSource::StartMenu], &scanned);
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].name, "A");
+    }
+
+    #[test]
+    fn test_entry_key_is_canonical() {
+        let key = |p: &str, windows| canonical_key(Path::new(p), windows);
+        assert_eq!(key("/app//chrome/./bin", false), "/app/chrome/bin");
+        assert_eq!(key("/App/Chrome", false), "/App/Chrome");
+        assert_eq!(key("C:/Apps/Chrome.lnk", true), key("c:\\apps\\chrome.LNK", true));
+
+        // 同一路径的不同写法在对账时视为同一条目
+        let existing = vec![entry("A", "/app//a", AppSource::StartMenu)];
+        let scanned = vec![entry("A", "/app/a", AppSource::StartMenu)];
+        let (merged, report) = reconcile_entries(existing, &[AppSource::StartMenu], &scanned);
+        assert!(!report.is_changed());
+        assert_eq!(merged.len(), 1);
+    }
+
+    #[test]
+    fn test_upsert_and_remove_single_entry() {
+        let (cache, _temp_dir) = temp_db();
+        cache.save(&[entry("Chrome", "/app/chrome", AppSource::StartMenu)]).unwrap();
+
+        cache.upsert(&entry("Google Chrome", "/app/chrome", AppSource::StartMenu)).unwrap();
+        cache.upsert(&entry("Code", "/app/code", AppSource::StartMenu)).unwrap();
+        cache.remove(std::path::Path::new("/app/chrome")).unwrap();
+
+        let loaded = cache.load().unwrap();
+        assert_eq!(loaded, vec![entry("Code", "/app/code", AppSource::StartMenu)]);
+    }
+
+    #[test]
+    fn test_records_carry_precomputed_pinyin() {
+        let (cache, _temp_dir) = temp_db();
+        cache.upsert(&entry("微信", "/app/wechat", AppSource::StartMenu)).unwrap();
+
+        let records = cache.load_records().unwrap();
+        assert_eq!(records[0].pinyin, PinyinFields::compute("微信"));
+        assert!(records[0].pinyin.is_current());
+    }
+
+    #[test]
+    fn test_refresh_existing_skips_removed_entries() {
+        let (cache, _temp_dir) = temp_db();
+        let chrome = entry("Chrome", "/app/chrome", AppSource::StartMenu);
+        let code = entry("Code", "/app/code", AppSource::StartMenu);
+        cache.save(&[chrome.clone(), code.clone()]).unwrap();
+
+        // 读出之后 code 被移除：刷新只改写仍在缓存里的 chrome
+        cache.remove(&code.path).unwrap();
+        assert_eq!(cache.refresh_existing(&[chrome.clone(), code]).unwrap(), 1);
+        assert_eq!(cache.load().unwrap(), vec![chrome]);
+    }
+
+    #[test]
+    fn test_undecodable_records_force_rescan() {
+        let (cache, _temp_dir) = temp_db();
+        cache.save(&[entry("Chrome", "/app/chrome", AppSource::StartMenu)]).unwrap();
+        cache.set_last_scan_time(IndexCache::current_timestamp()).unwrap();
+        let write_txn = cache.db.begin_write().unwrap();
+        {
+            let mut table = write_txn.open_table(INDEX_ENTRIES).unwrap();
+            table.insert("/app/old", [index_codec::FORMAT_VERSION + 1, 0].as_slice()).unwrap();
+        }
+        write_txn.commit().unwrap();
+        assert!(!cache.needs_scan().unwrap());
+
+        let records = cache.load_records().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(cache.needs_scan().unwrap());
+        assert!(!cache.contains(Path::new("/app/old")).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_snapshot_is_migrated() {
+        let (cache, _temp_dir) = temp_db();
+        let json = r#"[{"name":"Chrome","path":"/app/chrome","source":"StartMenu"}]"#;
+        let write_txn = cache.db.begin_write().unwrap();
+        {
+            let mut table = write_txn.open_table(INDEX_CACHE).unwrap();
+            table.insert(LEGACY_SNAPSHOT_KEY, json).unwrap();
+        }
+        write_txn.commit().unwrap();
+
+        IndexCache::init_table(&cache.db).unwrap();
+
+        let loaded = cache.load().unwrap();
+        assert_eq!(loaded, vec![entry("Chrome", "/app/chrome", AppSource::StartMenu)]);
+        let read_txn = cache.db.begin_read().unwrap();
+        let table = read_txn.open_table(INDEX_CACHE).unwrap();
+        assert!(table.get(LEGACY_SNAPSHOT_KEY).unwrap().is_none());
+    }
+}
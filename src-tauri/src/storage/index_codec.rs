@@ -0,0 +1,212 @@
+//! 索引条目的紧凑二进制编码（`index_entries` 表的 value）。
+//!
+//! 布局（小端）：
+//!
+//! ```text
+//! [version u8][source u8][path str][name str][icon opt_str][keywords list]
+//! [pinyin_full list][pinyin_initials list][pinyin_version u32]
+//!
+//! str     = u32 字节长度 + UTF-8 字节
+//! opt_str = u8 (0 = None, 1 = Some) + str
+//! list    = u16 个数 + str*
+//! ```
+//!
+//! 拼音只存名称的全拼 / 首字母；关键词的拼音有意不存，由搜索引擎构造字段时现算。
+//!
+//! 表的 key 是规范化的路径（Windows 上折叠大小写），原始路径保存在 value 中。
+//!
+//! 只解码当前版本；其他版本的记录视为缓存未命中，由索引缓存删除后重新扫描。
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+use crate::domain::{AppEntry, AppSource};
+use crate::search::PinyinFields;
+
+/// 编码格式版本。布局变化时递增；其他版本的记录在加载时丢弃并触发重新扫描。
+pub const FORMAT_VERSION: u8 = 1;
+
+/// 一条持久化的索引记录：条目本身 + 预计算的拼音字段。
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexRecord {
+    pub entry: AppEntry,
+    pub pinyin: PinyinFields,
+}
+
+/// 来源标签 ↔ 字节。显式映射，避免枚举重排导致已存数据错位。
+fn source_tag(source: AppSource) -> u8 {
+    match source {
+        AppSource::StartMenu => 0,
+        AppSource::RegistryUninstall => 1,
+        AppSource::UserDirectory => 2,
+        AppSource::UserHistory => 3,
+        AppSource::DesktopEntry => 4,
+        AppSource::UserDefined => 5,
+    }
+}
+
+fn source_from_tag(tag: u8) -> Result<AppSource> {
+    Ok(match tag {
+        0 => AppSource::StartMenu,
+        1 => AppSource::RegistryUninstall,
+        2 => AppSource::UserDirectory,
+        3 => AppSource::UserHistory,
+        4 => AppSource::DesktopEntry,
+        5 => AppSource::UserDefined,
+        other => bail!("未知的来源标签: {}", other),
+    })
+}
+
+/// 编码一条记录。
+pub fn encode(record: &IndexRecord) -> Vec<u8> {
+    let entry = &record.entry;
+    let mut buf = Vec::with_capacity(64 + entry.name.len() * 2);
+
+    buf.push(FORMAT_VERSION);
+    buf.push(source_tag(entry.source));
+    put_str(&mut buf, &entry.path.to_string_lossy());
+    put_str(&mut buf, &entry.name);
+    match &entry.icon {
+        Some(icon) => {
+            buf.push(1);
+            put_str(&mut buf, icon);
+        }
+        None => buf.push(0),
+    }
+    put_list(&mut buf, &entry.keywords);
+    put_list(&mut buf, &record.pinyin.full);
+    put_list(&mut buf, &record.pinyin.initials);
+    buf.extend_from_slice(&record.pinyin.version.to_le_bytes());
+
+    buf
+}
+
+/// 解码一条记录。版本不是 [`FORMAT_VERSION`] 时返回错误。
+pub fn decode(bytes: &[u8]) -> Result<IndexRecord> {
+    let mut r = Reader { bytes, pos: 0 };
+
+    let version = r.u8()?;
+    if version != FORMAT_VERSION {
+        bail!("不支持的索引记录版本: {}", version);
+    }
+    let source = source_from_tag(r.u8()?)?;
+    let path = r.str()?;
+    let name = r.str()?;
+    let icon = match r.u8()? {
+        0 => None,
+        _ => Some(r.str()?),
+    };
+    let keywords = r.list()?;
+    let full = r.list()?;
+    let initials = r.list()?;
+    let pinyin_version = r.u32()?;
+
+    Ok(IndexRecord {
+        entry: AppEntry {
+            name,
+            path: PathBuf::from(path),
+            source,
+            icon,
+            keywords,
+        },
+        pinyin: PinyinFields {
+            full,
+            initials,
+            version: pinyin_version,
+        },
+    })
+}
+
+fn put_list(buf: &mut Vec<u8>, items: &[String]) {
+    let count = items.len().min(u16::MAX as usize);
+    buf.extend_from_slice(&(count as u16).to_le_bytes());
+    for item in items.iter().take(count) {
+        put_str(buf, item);
+    }
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// 带边界检查的顺序读取器。
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8]> {
+        let end = self.pos.checked_add(n).context("索引记录长度溢出")?;
+        let slice = self.bytes.get(self.pos..end).context("索引记录被截断")?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn str(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        let raw = self.take(len)?;
+        String::from_utf8(raw.to_vec()).context("索引记录包含非法 UTF-8")
+    }
+
+    fn list(&mut self) -> Result<Vec<String>> {
+        let count = self.u16()?;
+        (0..count).map(|_| self.str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> IndexRecord {
+        IndexRecord {
+            entry: AppEntry {
+                name: "谷歌浏览器".to_string(),
+                path: PathBuf::from("/app/chrome"),
+                source: AppSource::DesktopEntry,
+                icon: Some("google-chrome".to_string()),
+                keywords: vec!["Google Chrome".to_string(), "browser".to_string()],
+            },
+            pinyin: PinyinFields {
+                full: vec!["gugeliulanqi".to_string(), "yugeliulanqi".to_string()],
+                initials: vec!["ggllq".to_string(), "ygllq".to_string()],
+                version: 7,
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let rec = record();
+        assert_eq!(decode(&encode(&rec)).unwrap(), rec);
+    }
+
+    #[test]
+    fn truncated_and_other_versions_are_rejected() {
+        let bytes = encode(&record());
+        assert!(decode(&bytes[..bytes.len() - 3]).is_err());
+
+        for version in [0, FORMAT_VERSION + 1] {
+            let mut other = bytes.clone();
+            other[0] = version;
+            assert!(decode(&other).is_err());
+        }
+    }
+}
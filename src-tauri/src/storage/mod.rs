@@ -1,13 +1,18 @@
 //! 存储层：redb + SQLite 双数据库架构。
 //!
-//! - redb: 高性能 KV 存储，用于 index_cache（搜索索引）、frequency（启动频次）
+//! - redb: 高性能 KV 存储，用于 index_cache（搜索索引，逐条二进制存储）、frequency（启动频次与启动时间）、
+//!   selection（query 前缀 → 选中的应用）、pins（首页固定的应用及顺序）
 //! - SQLite: 结构化数据存储，用于 notes（笔记），支持复杂查询
 
 mod frequency;
 mod index_cache;
+mod index_codec;
 mod note_store;
+mod pins;
+mod selection;
 mod sqlite_db;
 
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -16,10 +21,16 @@ use tracing::info;
 
 use crate::paths::app_data_dir;
 
-pub use frequency::FrequencyStore;
+pub use frequency::{FrequencyStore, MAX_LAUNCH_HISTORY};
 pub use index_cache::IndexCache;
 pub use note_store::NoteStore;
+pub use pins::PinStore;
+pub use selection::{add_selection, SelectionStore, Selections, MAX_PATH_BYTES};
 pub use sqlite_db::SqliteDb;
+use sqlite_db::SQLITE_FILE;
+
+/// redb 数据库文件名（位于数据目录下）。
+const REDB_FILE: &str = "sparknova.db";
 
 /// 数据库版本号，用于 schema 变更时的迁移检查。
 const DB_VERSION: u32 = 1;
@@ -39,14 +50,22 @@ impl Storage {
     /// - redb 数据库：应用索引、启动频次
     /// - SQLite 数据库：笔记等结构化数据
     pub fn open() -> Result<Self> {
+        Self::open_in(&app_data_dir())
+    }
+
+    /// 在指定目录下打开或创建两个数据库（测试用临时目录隔离）。
+    pub fn open_in(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
         // 打开 redb
-        let redb_path = get_redb_path()?;
+        let redb_path = dir.join(REDB_FILE);
         info!(path = ?redb_path, "打开 redb 数据库");
 
         let db = Arc::new(Database::create(&redb_path).context("创建/打开 redb 数据库失败")?);
 
         // 打开 SQLite
-        let sqlite = SqliteDb::open().context("打开 SQLite 数据库失败")?;
+        let sqlite =
+            SqliteDb::open_at(&dir.join(SQLITE_FILE)).context("打开 SQLite 数据库失败")?;
 
         let storage = Self { db, sqlite };
         storage.init_schema()?;
@@ -78,6 +97,8 @@ impl Storage {
         // 初始化 redb 子模块的表
         FrequencyStore::init_table(&self.db)?;
         IndexCache::init_table(&self.db)?;
+        SelectionStore::init_table(&self.db)?;
+        PinStore::init_table(&self.db)?;
 
         // SQLite 的 notes 表在 SqliteDb::init_schema() 中已创建
 
@@ -90,6 +111,16 @@ impl Storage {
         FrequencyStore::new(self.db.clone())
     }
 
+    /// 获取查询选择记录访问器。
+    pub fn selections(&self) -> SelectionStore {
+        SelectionStore::new(self.db.clone())
+    }
+
+    /// 获取首页固定条目访问器。
+    pub fn pins(&self) -> PinStore {
+        PinStore::new(self.db.clone())
+    }
+
     /// 获取索引缓存访问器。
     pub fn index_cache(&self) -> IndexCache {
         IndexCache::new(self.db.clone())
@@ -122,13 +153,6 @@ impl Storage {
     }
 }
 
-/// 获取 redb 数据库文件路径。
-fn get_redb_path() -> Result<std::path::PathBuf> {
-    let sparknova_dir = app_data_dir();
-    std::fs::create_dir_all(&sparknova_dir)?;
-    Ok(sparknova_dir.join("sparknova.db"))
-}
-
 /// 获取 SQLite 数据库文件路径（在 sqlite_db.rs 中定义，这里仅用于说明）
 const _: &str = "SQLite 数据库路径: {app_data_dir}/sparknova_sqlite.db";
 
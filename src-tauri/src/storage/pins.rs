@@ -0,0 +1,173 @@
+//! 固定条目：用户钉在空 query 首页顶部的应用，顺序由用户决定。
+//!
+//! 表结构：
+//! - key: 应用路径（String）
+//! - value: 显示位置（u32，从 0 开始连续编号）
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use redb::{Database, ReadableTable, TableDefinition};
+use tracing::debug;
+
+/// 固定条目表定义。
+const PINNED_ENTRIES: TableDefinition<&str, u32> = TableDefinition::new("pinned_entries");
+
+/// 固定条目访问器。
+pub struct PinStore {
+    db: Arc<Database>,
+}
+
+impl PinStore {
+    /// 创建新的固定条目访问器。
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// 初始化固定条目表（仅在首次运行时创建，已有则跳过）。
+    pub fn init_table(db: &Database) -> Result<()> {
+        let exists = db
+            .begin_read()
+            .ok()
+            .and_then(|txn| txn.open_table(PINNED_ENTRIES).ok())
+            .is_some();
+
+        if exists {
+            return Ok(());
+        }
+
+        let write_txn = db.begin_write()?;
+        {
+            let _table = write_txn.open_table(PINNED_ENTRIES)?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// 按显示顺序列出固定的应用路径。
+    pub fn list(&self) -> Result<Vec<String>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PINNED_ENTRIES)?;
+        read_order(&table)
+    }
+
+    /// 固定一个应用（追加到末尾）；已固定则不变。返回新的顺序。
+    pub fn pin(&self, app_path: &str) -> Result<Vec<String>> {
+        self.update(|order| {
+            if !order.iter().any(|p| p == app_path) {
+                order.push(app_path.to_string());
+            }
+        })
+    }
+
+    /// 取消固定，后面的条目依次前移。返回新的顺序。
+    pub fn unpin(&self, app_path: &str) -> Result<Vec<String>> {
+        self.update(|order| order.retain(|p| p != app_path))
+    }
+
+    /// 按 `paths` 重排（见 [`apply_order`]）。返回新的顺序。
+    pub fn reorder(&self, paths: &[String]) -> Result<Vec<String>> {
+        self.update(|order| apply_order(order, paths))
+    }
+
+    /// 在一个写事务内读出当前顺序、修改并整体写回（位置重新连续编号）。
+    fn update(&self, f: impl FnOnce(&mut Vec<String>)) -> Result<Vec<String>> {
+        let write_txn = self.db.begin_write()?;
+        let order = {
+            let mut table = write_txn.open_table(PINNED_ENTRIES)?;
+            let old = read_order(&table)?;
+            let mut order = old.clone();
+            f(&mut order);
+            for path in &old {
+                table.remove(path.as_str())?;
+            }
+            for (position, path) in order.iter().enumerate() {
+                table.insert(path.as_str(), position as u32)?;
+            }
+            order
+        };
+        write_txn.commit()?;
+
+        debug!(count = order.len(), "更新固定条目");
+        Ok(order)
+    }
+}
+
+fn read_order(table: &impl ReadableTable<&'static str, u32>) -> Result<Vec<String>> {
+    let mut entries = Vec::new();
+    for entry in table.iter()? {
+        let (key, value) = entry?;
+        entries.push((value.value(), key.value().to_string()));
+    }
+    entries.sort();
+    Ok(entries.into_iter().map(|(_, path)| path).collect())
+}
+
+/// 重排：`paths` 中已固定的条目按给定顺序排在前面（重复的只取第一次），
+/// 未列出的固定条目保持原有相对顺序接在后面；未固定的路径忽略。
+pub fn apply_order(order: &mut Vec<String>, paths: &[String]) {
+    let mut reordered: Vec<String> = Vec::with_capacity(order.len());
+    for path in paths {
+        if order.contains(path) && !reordered.contains(path) {
+            reordered.push(path.clone());
+        }
+    }
+    for path in order.iter() {
+        if !reordered.contains(path) {
+            reordered.push(path.clone());
+        }
+    }
+    *order = reordered;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_db() -> (PinStore, std::path::PathBuf) {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "sparknova_pins_test_{:?}_{:?}",
+            std::thread::current().id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let db = Arc::new(Database::create(temp_dir.join("test.db")).unwrap());
+        PinStore::init_table(&db).unwrap();
+        (PinStore::new(db), temp_dir)
+    }
+
+    fn strings(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pin_unpin_and_reorder() {
+        let (store, _temp_dir) = temp_db();
+        assert!(store.list().unwrap().is_empty());
+
+        store.pin("/app/a").unwrap();
+        store.pin("/app/b").unwrap();
+        store.pin("/app/c").unwrap();
+        store.pin("/app/a").unwrap();
+        assert_eq!(store.list().unwrap(), strings(&["/app/a", "/app/b", "/app/c"]));
+
+        store.unpin("/app/b").unwrap();
+        assert_eq!(store.list().unwrap(), strings(&["/app/a", "/app/c"]));
+
+        let order = store.reorder(&strings(&["/app/c", "/app/a"])).unwrap();
+        assert_eq!(order, strings(&["/app/c", "/app/a"]));
+        assert_eq!(store.list().unwrap(), order);
+    }
+
+    #[test]
+    fn test_apply_order_keeps_unlisted_entries() {
+        let mut order = strings(&["/a", "/b", "/c", "/d"]);
+        apply_order(&mut order, &strings(&["/c", "/x", "/a", "/c"]));
+        assert_eq!(order, strings(&["/c", "/a", "/b", "/d"]));
+    }
+}
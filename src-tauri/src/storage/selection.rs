@@ -0,0 +1,309 @@
+//! 查询选择记录：记住用户输入某个 query 前缀时最终打开了哪个应用。
+//!
+//! 表结构：
+//! - `query_selections`：归一化的 query 前缀 → 该前缀下选过的应用，按 (路径, 次数) 依次编码：
+//!   `u16 路径字节长度 | 路径 UTF-8 | u32 次数`（小端序）
+//! - `query_selection_used`：前缀 → 最近一次记录的时间戳（秒），用于按 LRU 淘汰前缀
+//!
+//! 每个前缀最多记 [`MAX_SELECTIONS_PER_PREFIX`] 个应用，前缀总数不超过 [`MAX_PREFIXES`]。
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use redb::{Database, ReadableTable, ReadableTableMetadata, Table, TableDefinition};
+use tracing::debug;
+
+use super::IndexCache;
+
+/// 选择记录表定义。
+const QUERY_SELECTIONS: TableDefinition<&str, &[u8]> = TableDefinition::new("query_selections");
+
+/// 前缀最近使用时间表定义。
+const QUERY_SELECTION_USED: TableDefinition<&str, u64> =
+    TableDefinition::new("query_selection_used");
+
+/// 一个前缀下选过的应用：(应用路径, 次数)。
+pub type Selections = Vec<(String, u32)>;
+
+/// 每个前缀最多记住几个应用；超出时淘汰次数最少的。
+pub const MAX_SELECTIONS_PER_PREFIX: usize = 8;
+
+/// 最多记住多少个前缀；超出时淘汰最久没有记录过选择的前缀。
+pub const MAX_PREFIXES: usize = 4096;
+
+/// 可记录的应用路径最大字节数（编码中的长度字段为 u16）。
+pub const MAX_PATH_BYTES: usize = u16::MAX as usize;
+
+/// 查询选择记录访问器。
+pub struct SelectionStore {
+    db: Arc<Database>,
+}
+
+impl SelectionStore {
+    /// 创建新的选择记录访问器。
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// 初始化选择记录表（仅在首次运行时创建，已有则跳过）。
+    pub fn init_table(db: &Database) -> Result<()> {
+        let exists = db
+            .begin_read()
+            .ok()
+            .map(|txn| {
+                txn.open_table(QUERY_SELECTIONS).is_ok()
+                    && txn.open_table(QUERY_SELECTION_USED).is_ok()
+            })
+            .unwrap_or(false);
+
+        if exists {
+            return Ok(());
+        }
+
+        let write_txn = db.begin_write()?;
+        {
+            let _table = write_txn.open_table(QUERY_SELECTIONS)?;
+            let _used = write_txn.open_table(QUERY_SELECTION_USED)?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// 记录一次选择：`prefixes` 中每个前缀下 `app_path` 的次数 +1。
+    ///
+    /// 返回因前缀数超过 [`MAX_PREFIXES`] 被淘汰的前缀，调用方据此同步内存缓存。
+    /// 路径超过 [`MAX_PATH_BYTES`] 时报错，不截断。
+    pub fn record(&self, prefixes: &[String], app_path: &str) -> Result<Vec<String>> {
+        self.record_capped(prefixes, app_path, MAX_PREFIXES)
+    }
+
+    fn record_capped(
+        &self,
+        prefixes: &[String],
+        app_path: &str,
+        max_prefixes: usize,
+    ) -> Result<Vec<String>> {
+        if app_path.len() > MAX_PATH_BYTES {
+            bail!("应用路径超过 {} 字节，不记录选择", MAX_PATH_BYTES);
+        }
+        let now = IndexCache::current_timestamp();
+        let write_txn = self.db.begin_write()?;
+
+        let evicted = {
+            let mut table = write_txn.open_table(QUERY_SELECTIONS)?;
+            let mut used = write_txn.open_table(QUERY_SELECTION_USED)?;
+            for prefix in prefixes {
+                let mut selections = match table.get(prefix.as_str())? {
+                    Some(v) => decode(v.value()).unwrap_or_default(),
+                    None => Vec::new(),
+                };
+                add_selection(&mut selections, app_path);
+                table.insert(prefix.as_str(), encode(&selections)?.as_slice())?;
+                used.insert(prefix.as_str(), now)?;
+            }
+            evict_least_recent(&mut table, &mut used, max_prefixes)?
+        };
+
+        write_txn.commit()?;
+
+        debug!(
+            path = app_path,
+            prefixes = prefixes.len(),
+            evicted = evicted.len(),
+            "记录查询选择"
+        );
+        Ok(evicted)
+    }
+
+    /// 获取全部选择记录。
+    ///
+    /// 返回 (query 前缀, [(应用路径, 次数)]) 的列表；损坏的记录跳过。
+    pub fn get_all(&self) -> Result<Vec<(String, Selections)>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(QUERY_SELECTIONS)?;
+
+        let mut results = Vec::new();
+        for entry in table.iter()? {
+            let (key, value) = entry?;
+            match decode(value.value()) {
+                Ok(selections) => results.push((key.value().to_string(), selections)),
+                Err(e) => debug!(prefix = key.value(), "跳过损坏的选择记录: {:?}", e),
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// 次数 +1；新应用在已满时替换次数最少的一条（同次数淘汰较早加入的）。
+pub fn add_selection(selections: &mut Selections, app_path: &str) {
+    if let Some((_, count)) = selections.iter_mut().find(|(p, _)| p == app_path) {
+        *count = count.saturating_add(1);
+        return;
+    }
+    if selections.len() >= MAX_SELECTIONS_PER_PREFIX {
+        if let Some(weakest) = selections
+            .iter()
+            .enumerate()
+            .min_by_key(|(i, (_, count))| (*count, *i))
+            .map(|(i, _)| i)
+        {
+            selections.remove(weakest);
+        }
+    }
+    selections.push((app_path.to_string(), 1));
+}
+
+/// 前缀数超过 `max_prefixes` 时删除最久未记录的前缀（没有使用时间的旧记录最先淘汰）。
+fn evict_least_recent(
+    table: &mut Table<&str, &[u8]>,
+    used: &mut Table<&str, u64>,
+    max_prefixes: usize,
+) -> Result<Vec<String>> {
+    let count = table.len()? as usize;
+    if count <= max_prefixes {
+        return Ok(Vec::new());
+    }
+
+    let mut by_use = Vec::with_capacity(count);
+    for entry in table.iter()? {
+        let (key, _) = entry?;
+        let prefix = key.value().to_string();
+        let last_used = used.get(prefix.as_str())?.map(|v| v.value()).unwrap_or(0);
+        by_use.push((last_used, prefix));
+    }
+    by_use.sort();
+
+    let evicted: Vec<String> =
+        by_use.into_iter().take(count - max_prefixes).map(|(_, prefix)| prefix).collect();
+    for prefix in &evicted {
+        table.remove(prefix.as_str())?;
+        used.remove(prefix.as_str())?;
+    }
+    Ok(evicted)
+}
+
+fn encode(selections: &[(String, u32)]) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    for (path, count) in selections {
+        let bytes = path.as_bytes();
+        if bytes.len() > MAX_PATH_BYTES {
+            bail!("应用路径超过 {} 字节，无法编码", MAX_PATH_BYTES);
+        }
+        buf.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+        buf.extend_from_slice(bytes);
+        buf.extend_from_slice(&count.to_le_bytes());
+    }
+    Ok(buf)
+}
+
+fn decode(mut bytes: &[u8]) -> Result<Selections> {
+    let mut selections = Vec::new();
+    while !bytes.is_empty() {
+        if bytes.len() < 2 {
+            bail!("选择记录被截断");
+        }
+        let len = u16::from_le_bytes([bytes[0], bytes[1]]) as usize;
+        if bytes.len() < 2 + len + 4 {
+            bail!("选择记录被截断");
+        }
+        let path = std::str::from_utf8(&bytes[2..2 + len])?.to_string();
+        let count = u32::from_le_bytes(bytes[2 + len..2 + len + 4].try_into()?);
+        selections.push((path, count));
+        bytes = &bytes[2 + len + 4..];
+    }
+    Ok(selections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_db() -> (SelectionStore, std::path::PathBuf) {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "sparknova_selection_test_{:?}_{:?}",
+            std::thread::current().id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let db = Arc::new(Database::create(temp_dir.join("test.db")).unwrap());
+        SelectionStore::init_table(&db).unwrap();
+        (SelectionStore::new(db), temp_dir)
+    }
+
+    #[test]
+    fn test_record_and_get_all() {
+        let (store, _temp_dir) = temp_db();
+        let prefixes = vec!["c".to_string(), "ch".to_string()];
+
+        store.record(&prefixes, "/app/chrome").unwrap();
+        store.record(&prefixes[..1], "/app/chrome").unwrap();
+        store.record(&prefixes[..1], "/app/code").unwrap();
+
+        let mut all = store.get_all().unwrap();
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                (
+                    "c".to_string(),
+                    vec![("/app/chrome".to_string(), 2), ("/app/code".to_string(), 1)]
+                ),
+                ("ch".to_string(), vec![("/app/chrome".to_string(), 1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_full_prefix_evicts_least_chosen() {
+        let mut selections: Selections = (0..MAX_SELECTIONS_PER_PREFIX)
+            .map(|i| (format!("/app/{}", i), 2))
+            .collect();
+        selections[3].1 = 1;
+
+        add_selection(&mut selections, "/app/new");
+        assert_eq!(selections.len(), MAX_SELECTIONS_PER_PREFIX);
+        assert!(!selections.iter().any(|(p, _)| p == "/app/3"));
+        assert_eq!(selections.last(), Some(&("/app/new".to_string(), 1)));
+
+        assert!(decode(&encode(&selections).unwrap()[..5]).is_err());
+    }
+
+    #[test]
+    fn test_overlong_path_is_rejected() {
+        let (store, _temp_dir) = temp_db();
+        let long = "/".repeat(MAX_PATH_BYTES + 1);
+        assert!(store.record(&["c".to_string()], &long).is_err());
+        assert!(store.get_all().unwrap().is_empty());
+        assert!(encode(&[(long, 1)]).is_err());
+    }
+
+    #[test]
+    fn test_least_recent_prefixes_are_evicted() {
+        let (store, _temp_dir) = temp_db();
+        let prefixes = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+
+        assert!(store.record_capped(&prefixes(&["a", "ab"]), "/app/a", 3).unwrap().is_empty());
+        // 让 "a"/"ab" 的使用时间早于后续记录
+        {
+            let txn = store.db.begin_write().unwrap();
+            {
+                let mut used = txn.open_table(QUERY_SELECTION_USED).unwrap();
+                used.insert("a", 1).unwrap();
+                used.insert("ab", 2).unwrap();
+            }
+            txn.commit().unwrap();
+        }
+        let evicted = store.record_capped(&prefixes(&["c", "cd"]), "/app/c", 3).unwrap();
+        assert_eq!(evicted, vec!["a".to_string()]);
+
+        let mut keys: Vec<String> = store.get_all().unwrap().into_iter().map(|(k, _)| k).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["ab", "c", "cd"]);
+    }
+}
@@ -1,15 +1,19 @@
 //! SQLite 数据库模块：笔记等结构化数据的持久化存储。
 //!
 //! 使用 rusqlite 实现，支持复杂的查询能力（按类型筛选、按日期范围等）。
+//! 另存设置、扫描目录、用户给应用起的别名（`app_aliases`：应用路径 → 别名/关键词）
+//! 与隐藏规则（`hidden_entries`：按路径或通配符屏蔽的条目），
+//! 以及用户自定义条目（`user_entries`：参数、环境变量、关键词存为 JSON 文本列）。
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use rusqlite::{params, Connection};
 use tracing::info;
 
-use crate::domain::{Note, NoteKind};
+use crate::domain::{HiddenRule, Note, NoteKind, UserEntry};
 use crate::paths::app_data_dir;
 
 /// SQLite 数据库封装。
@@ -21,10 +25,14 @@ pub struct SqliteDb {
 impl SqliteDb {
     /// 打开或创建 SQLite 数据库。
     pub fn open() -> Result<Self> {
-        let db_path = get_db_path()?;
+        Self::open_at(&get_db_path()?)
+    }
+
+    /// 打开或创建指定路径的数据库。
+    pub fn open_at(db_path: &Path) -> Result<Self> {
         info!(path = ?db_path, "打开 SQLite 数据库");
 
-        let conn = Connection::open(&db_path).context("打开 SQLite 数据库失败")?;
+        let conn = Connection::open(db_path).context("打开 SQLite 数据库失败")?;
 
         let db = Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -71,6 +79,46 @@ impl SqliteDb {
         )
         .context("创建 scan_dirs 表失败")?;
 
+        // 创建应用别名表
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS app_aliases (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                path TEXT NOT NULL,
+                alias TEXT NOT NULL,
+                UNIQUE (path, alias)
+            )",
+            [],
+        )
+        .context("创建 app_aliases 表失败")?;
+
+        // 创建隐藏规则表
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS hidden_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                value TEXT NOT NULL,
+                UNIQUE (kind, value)
+            )",
+            [],
+        )
+        .context("创建 hidden_entries 表失败")?;
+
+        // 创建用户自定义条目表
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                command TEXT NOT NULL,
+                args TEXT NOT NULL DEFAULT '[]',
+                working_dir TEXT,
+                env TEXT NOT NULL DEFAULT '{}',
+                icon TEXT,
+                keywords TEXT NOT NULL DEFAULT '[]'
+            )",
+            [],
+        )
+        .context("创建 user_entries 表失败")?;
+
         info!("SQLite 数据库初始化完成");
         Ok(())
     }
@@ -318,6 +366,199 @@ impl SqliteDb {
         Ok(())
     }
 
+    // ========== 应用别名 ==========
+
+    /// 获取全部别名：按应用路径分组（路径升序），每个应用的别名按添加顺序。
+    pub fn list_aliases(&self) -> Result<BTreeMap<String, Vec<String>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT path, alias FROM app_aliases ORDER BY id ASC")
+            .context("查询应用别名失败")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?;
+
+        let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for row in rows {
+            let (path, alias) = row?;
+            grouped.entry(path).or_default().push(alias);
+        }
+        Ok(grouped)
+    }
+
+    /// 获取单个应用的别名（按添加顺序）。
+    pub fn get_aliases(&self, path: &str) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT alias FROM app_aliases WHERE path = ?1 ORDER BY id ASC")
+            .context("查询应用别名失败")?;
+        let aliases = stmt
+            .query_map(params![path], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(aliases)
+    }
+
+    /// 给应用添加别名（去首尾空白；已存在则跳过）。
+    pub fn add_alias(&self, path: &str, alias: &str) -> Result<()> {
+        let alias = alias.trim();
+        if alias.is_empty() {
+            bail!("别名不能为空");
+        }
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO app_aliases (path, alias) VALUES (?1, ?2)",
+            params![path, alias],
+        )?;
+        Ok(())
+    }
+
+    /// 删除应用的一个别名。
+    pub fn remove_alias(&self, path: &str, alias: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM app_aliases WHERE path = ?1 AND alias = ?2",
+            params![path, alias.trim()],
+        )?;
+        Ok(())
+    }
+
+    /// 整体替换应用的别名（空白项忽略，重复项只保留第一个）；传空列表即清除。
+    pub fn set_aliases(&self, path: &str, aliases: &[String]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM app_aliases WHERE path = ?1", params![path])?;
+        for alias in aliases.iter().map(|a| a.trim()).filter(|a| !a.is_empty()) {
+            tx.execute(
+                "INSERT OR IGNORE INTO app_aliases (path, alias) VALUES (?1, ?2)",
+                params![path, alias],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ========== 隐藏规则 ==========
+
+    /// 获取全部隐藏规则（按添加顺序；无法识别的类型跳过）。
+    pub fn list_hidden(&self) -> Result<Vec<HiddenRule>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT kind, value FROM hidden_entries ORDER BY id ASC")
+            .context("查询隐藏规则失败")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?;
+
+        let mut rules = Vec::new();
+        for row in rows {
+            let (kind, value) = row?;
+            rules.extend(HiddenRule::from_parts(&kind, value));
+        }
+        Ok(rules)
+    }
+
+    /// 添加隐藏规则（去首尾空白；已存在则跳过）。
+    pub fn add_hidden(&self, rule: &HiddenRule) -> Result<()> {
+        let value = rule.value().trim();
+        if value.is_empty() {
+            bail!("隐藏规则不能为空");
+        }
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO hidden_entries (kind, value) VALUES (?1, ?2)",
+            params![rule.kind(), value],
+        )?;
+        Ok(())
+    }
+
+    /// 删除隐藏规则。
+    pub fn remove_hidden(&self, rule: &HiddenRule) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM hidden_entries WHERE kind = ?1 AND value = ?2",
+            params![rule.kind(), rule.value().trim()],
+        )?;
+        Ok(())
+    }
+
+    // ========== 用户自定义条目 ==========
+
+    /// 获取全部用户自定义条目（按创建顺序）。
+    pub fn list_user_entries(&self) -> Result<Vec<UserEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!("{} ORDER BY id ASC", SELECT_USER_ENTRY))
+            .context("查询用户条目失败")?;
+        let rows = stmt.query_map([], user_entry_from_row)?;
+        rows.map(|row| Ok(row?)).collect()
+    }
+
+    /// 获取单个用户自定义条目。
+    pub fn get_user_entry(&self, id: u64) -> Result<Option<UserEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!("{} WHERE id = ?1", SELECT_USER_ENTRY))
+            .context("查询用户条目失败")?;
+        match stmt.query_row(params![id as i64], user_entry_from_row) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e).context("查询用户条目失败"),
+        }
+    }
+
+    /// 新建用户自定义条目（忽略传入的 id），返回校验后带新 id 的条目。
+    pub fn insert_user_entry(&self, entry: &UserEntry) -> Result<UserEntry> {
+        let mut entry = entry.validated()?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO user_entries (name, command, args, working_dir, env, icon, keywords)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry.name,
+                entry.command,
+                serde_json::to_string(&entry.args)?,
+                entry.working_dir,
+                serde_json::to_string(&entry.env)?,
+                entry.icon,
+                serde_json::to_string(&entry.keywords)?,
+            ],
+        )
+        .context("插入用户条目失败")?;
+        entry.id = conn.last_insert_rowid() as u64;
+        info!(entry_id = entry.id, name = %entry.name, "用户条目已创建");
+        Ok(entry)
+    }
+
+    /// 按 id 整体更新用户自定义条目，返回校验后的条目；条目不存在时报错。
+    pub fn update_user_entry(&self, entry: &UserEntry) -> Result<UserEntry> {
+        let entry = entry.validated()?;
+        let conn = self.conn.lock().unwrap();
+        let changed = conn
+            .execute(
+                "UPDATE user_entries SET name = ?1, command = ?2, args = ?3, working_dir = ?4,
+                 env = ?5, icon = ?6, keywords = ?7 WHERE id = ?8",
+                params![
+                    entry.name,
+                    entry.command,
+                    serde_json::to_string(&entry.args)?,
+                    entry.working_dir,
+                    serde_json::to_string(&entry.env)?,
+                    entry.icon,
+                    serde_json::to_string(&entry.keywords)?,
+                    entry.id as i64,
+                ],
+            )
+            .context("更新用户条目失败")?;
+        if changed == 0 {
+            bail!("用户条目不存在: {}", entry.id);
+        }
+        Ok(entry)
+    }
+
+    /// 删除用户自定义条目。
+    pub fn delete_user_entry(&self, id: u64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM user_entries WHERE id = ?1", params![id as i64])
+            .context("删除用户条目失败")?;
+        Ok(())
+    }
+
     /// 获取所有设置（用于迁移或备份）。
     #[allow(dead_code)] // 备份/调试用；v0.4 数据迁移工具有可能会启用
     pub fn get_all_settings(&self) -> Result<Vec<(String, String)>> {
@@ -333,11 +574,44 @@ impl SqliteDb {
     }
 }
 
+const SELECT_USER_ENTRY: &str =
+    "SELECT id, name, command, args, working_dir, env, icon, keywords FROM user_entries";
+
+/// 按 [`SELECT_USER_ENTRY`] 的列顺序读出一条用户条目。
+///
+/// JSON 列损坏时报错而不是按空值处理：否则启动时会静默丢掉用户保存的参数和环境变量。
+fn user_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<UserEntry> {
+    Ok(UserEntry {
+        id: row.get::<_, i64>(0)? as u64,
+        name: row.get(1)?,
+        command: row.get(2)?,
+        args: json_column(row, 3)?,
+        working_dir: row.get(4)?,
+        env: json_column(row, 5)?,
+        icon: row.get(6)?,
+        keywords: json_column(row, 7)?,
+    })
+}
+
+/// 读取并解析 JSON 文本列。
+fn json_column<T: serde::de::DeserializeOwned>(
+    row: &rusqlite::Row,
+    index: usize,
+) -> rusqlite::Result<T> {
+    let text: String = row.get(index)?;
+    serde_json::from_str(&text).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
 /// 获取 SQLite 数据库文件路径。
+/// SQLite 数据库文件名（位于数据目录下）。
+pub(crate) const SQLITE_FILE: &str = "sparknova_sqlite.db";
+
 fn get_db_path() -> Result<PathBuf> {
     let sparknova_dir = app_data_dir();
     std::fs::create_dir_all(&sparknova_dir)?;
-    Ok(sparknova_dir.join("sparknova_sqlite.db"))
+    Ok(sparknova_dir.join(SQLITE_FILE))
 }
 
 #[cfg(test)]
@@ -407,6 +681,98 @@ mod tests {
         assert!(!notes[0].done); // 未完成的在前
     }
 
+    #[test]
+    fn test_alias_crud() {
+        let (db, temp_dir) = temp_db();
+        // 测试间可能共用数据库文件，用唯一路径隔离
+        let path = temp_dir.join("Photoshop.exe").to_string_lossy().into_owned();
+
+        db.add_alias(&path, " ps ").unwrap();
+        db.add_alias(&path, "修图").unwrap();
+        db.add_alias(&path, "ps").unwrap();
+        assert!(db.add_alias(&path, "  ").is_err());
+        assert_eq!(db.get_aliases(&path).unwrap(), vec!["ps", "修图"]);
+
+        db.remove_alias(&path, "ps").unwrap();
+        assert_eq!(db.get_aliases(&path).unwrap(), vec!["修图"]);
+
+        db.set_aliases(&path, &["pshop".to_string(), "".to_string(), "pshop".to_string()])
+            .unwrap();
+        let all = db.list_aliases().unwrap();
+        assert_eq!(all[&path], vec!["pshop".to_string()]);
+
+        db.set_aliases(&path, &[]).unwrap();
+        assert!(db.get_aliases(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hidden_rules() {
+        let (db, temp_dir) = temp_db();
+        let path = temp_dir.join("unins000.exe").to_string_lossy().into_owned();
+        let pattern = format!("{}*", temp_dir.display());
+
+        db.add_hidden(&HiddenRule::Path(path.clone())).unwrap();
+        db.add_hidden(&HiddenRule::Pattern(format!(" {} ", pattern))).unwrap();
+        db.add_hidden(&HiddenRule::Path(path.clone())).unwrap();
+        assert!(db.add_hidden(&HiddenRule::Pattern(" ".into())).is_err());
+
+        let mine = |db: &SqliteDb| -> Vec<HiddenRule> {
+            let dir = temp_dir.to_string_lossy().into_owned();
+            db.list_hidden().unwrap().into_iter().filter(|r| r.value().starts_with(&dir)).collect()
+        };
+        assert_eq!(
+            mine(&db),
+            vec![HiddenRule::Path(path.clone()), HiddenRule::Pattern(pattern.clone())]
+        );
+
+        db.remove_hidden(&HiddenRule::Path(path)).unwrap();
+        assert_eq!(mine(&db), vec![HiddenRule::Pattern(pattern)]);
+    }
+
+    #[test]
+    fn test_user_entry_crud() {
+        let (db, temp_dir) = temp_db();
+        let project = temp_dir.join("project").to_string_lossy().into_owned();
+        let entry = UserEntry {
+            id: 0,
+            name: " Work Project ".into(),
+            command: "code".into(),
+            args: vec![project.clone(), "--new-window".into()],
+            working_dir: Some("  ".into()),
+            env: [("NODE_ENV".to_string(), "development".to_string())].into(),
+            icon: None,
+            keywords: vec!["工作".into(), " ".into()],
+        };
+
+        let saved = db.insert_user_entry(&entry).unwrap();
+        assert!(saved.id > 0);
+        assert_eq!(saved.name, "Work Project");
+        assert_eq!(saved.working_dir, None);
+        assert_eq!(saved.keywords, vec!["工作"]);
+        assert_eq!(db.get_user_entry(saved.id).unwrap(), Some(saved.clone()));
+        assert!(db.list_user_entries().unwrap().contains(&saved));
+
+        let edited = UserEntry { working_dir: Some(project), ..saved.clone() };
+        db.update_user_entry(&edited).unwrap();
+        assert_eq!(db.get_user_entry(saved.id).unwrap(), Some(edited));
+        assert!(db.insert_user_entry(&UserEntry { command: " ".into(), ..entry }).is_err());
+
+        // JSON 列损坏时报错，不把参数当成空的
+        db.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE user_entries SET args = 'oops' WHERE id = ?1",
+                params![saved.id as i64],
+            )
+            .unwrap();
+        assert!(db.get_user_entry(saved.id).is_err());
+
+        db.delete_user_entry(saved.id).unwrap();
+        assert!(db.get_user_entry(saved.id).unwrap().is_none());
+        assert!(db.update_user_entry(&saved).is_err());
+    }
+
     #[test]
     fn test_delete() {
         let (db, _temp_dir) = temp_db();
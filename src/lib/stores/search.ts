@@ -1,8 +1,11 @@
 import { writable } from 'svelte/store';
 
 interface ResultItem {
-  name: string;
-  path: string;
+  id: string;
+  title: string;
+  subtitle: string;
+  kind: string;
+  highlights?: [number, number][];
 }
 
 interface SearchState {
@@ -9,4 +9,25 @@
  * - 限制最大长度
  */
 export const sanitize = (v: string): string =>
-  v.replace(/[<>]/g, "").trim().slice(0, 256);
\ No newline at end of file
+  v.replace(/[<>]/g, "").trim().slice(0, 256);
+
+/**
+ * 按后端返回的高亮区间切分文本，供结果列表加粗命中字符
+ * - ranges 为 [start, end) 字符下标（Unicode 码点，与 Rust char 一致）
+ * - 返回相邻片段，hit 表示是否命中
+ */
+export const splitHighlights = (
+  text: string,
+  ranges: [number, number][] = []
+): { text: string; hit: boolean }[] => {
+  const chars = Array.from(text);
+  const segments: { text: string; hit: boolean }[] = [];
+  let pos = 0;
+  for (const [start, end] of ranges) {
+    if (start > pos) segments.push({ text: chars.slice(pos, start).join(""), hit: false });
+    if (end > start) segments.push({ text: chars.slice(start, end).join(""), hit: true });
+    pos = Math.max(pos, end);
+  }
+  if (pos < chars.length) segments.push({ text: chars.slice(pos).join(""), hit: false });
+  return segments;
+};